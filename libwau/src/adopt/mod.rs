@@ -0,0 +1,198 @@
+//! Adopts addons installed by another manager into the manifest and lock.
+//!
+//! [`plan`] takes the [`AddonGroup`]s the lock does not own and identifies each
+//! by the provider IDs authors embed in their `.toc` files (`X-Curse-Project-ID`,
+//! `X-WoWI-ID`, `X-Wago-ID`). Groups without any ID can be identified out of
+//! band — by CurseForge fingerprint — and passed to [`plan`] as [`Fingerprinted`]
+//! files.
+//!
+//! The group's main folder names the addon. [`apply`]
+//! records each group as a [`LockedAddon`]; the caller appends the new manifest
+//! rows with [`crate::manifest::append`]. Entries identified only from the `.toc`
+//! carry no resolved artifact; they count as current while the provider's latest
+//! version names the recorded `X-Curse-Packaged-Version` / `## Version` (see
+//! `providers::version_matches`), and are replaced by the next `sync --update`
+//! once it does not. Fingerprinted ones record
+//! the matched file and stay current until a newer file is published.
+
+use std::{collections::HashMap, fmt};
+
+use chrono::Utc;
+
+use crate::{
+    inventory::{AddonGroup, ProviderId},
+    lock::{Lock, LockedAddon},
+    manifest::{Manifest, ManifestAddon},
+    model::{Channel, Flavor},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// `resolved_id` recorded for entries adopted from `.toc` IDs; never matches a
+/// provider artifact id, so [`crate::ops::resolve`] compares versions instead.
+pub const ADOPTED_ID: &str = "adopted";
+
+/// One logical addon found on disk that can be adopted.
+#[derive(Debug, Clone)]
+pub struct AdoptedAddon {
+    /// Manifest row the addon resolves through: an existing row with the same
+    /// provider ID, or a new row named after the main folder.
+    pub row: ManifestAddon,
+    /// Whether `row` has to be appended to the manifest.
+    pub new_row: bool,
+    /// The fingerprinted file's version, else `X-Curse-Packaged-Version` or
+    /// `## Version` of the group.
+    pub version: Option<String>,
+    /// Every folder in the group, main folder first.
+    pub folders: Vec<String>,
+    /// Provider file id, when a folder of the group was fingerprinted.
+    pub file_id: Option<u64>,
+}
+
+/// A CurseForge file an installed folder was matched to by fingerprint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fingerprinted {
+    pub project_id: u64,
+    pub file_id: u64,
+    pub version: String,
+}
+
+/// A group [`plan`] leaves unmanaged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedGroup {
+    pub name: String,
+    /// Every folder in the group, main folder first.
+    pub folders: Vec<String>,
+    pub reason: SkipReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    /// No `.toc` in the group names a provider ID.
+    NoProviderId,
+    /// Only an `X-Wago-ID`, and there is no Wago provider.
+    WagoOnly(String),
+    /// A manifest row or lock entry for another project already uses the addon's name.
+    NameTaken(String),
+    /// The matching manifest row is already locked with other folders.
+    AlreadyLocked(String),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoProviderId => f.write_str("no provider ID in its .toc"),
+            Self::WagoOnly(id) => {
+                write!(f, "only a Wago ID ({id}); Wago is not a supported provider")
+            }
+            Self::NameTaken(name) => write!(f, "a different addon is already named '{name}'"),
+            Self::AlreadyLocked(name) => write!(f, "'{name}' is already locked with other folders"),
+        }
+    }
+}
+
+/// The outcome of [`plan`].
+#[derive(Debug, Clone, Default)]
+pub struct Adoption {
+    pub addons: Vec<AdoptedAddon>,
+    pub skipped: Vec<SkippedGroup>,
+}
+
+/// Identifies the `groups` (from [`crate::inventory::group`]) no lock entry owns
+/// as adoptable addons for `flavor`, matching them against existing manifest rows.
+///
+/// `fingerprinted` maps folder names to the CurseForge file they matched; it is
+/// only consulted for groups whose `.toc` files name no provider ID.
+pub fn plan(
+    groups: &[AddonGroup],
+    lock: &Lock,
+    manifest: &Manifest,
+    flavor: &Flavor,
+    fingerprinted: &HashMap<String, Fingerprinted>,
+) -> Adoption {
+    let mut adoption = Adoption::default();
+    for group in groups.iter().filter(|g| g.locked_by.is_none()) {
+        let folders: Vec<String> = group.folders().map(str::to_owned).collect();
+        let skip = |reason| SkippedGroup {
+            name: group.name.clone(),
+            folders: folders.clone(),
+            reason,
+        };
+        let matched = group.folders().find_map(|f| fingerprinted.get(f));
+        let id = group
+            .provider_id
+            .or_else(|| matched.map(|f| ProviderId::CurseForge(f.project_id)));
+        let Some(id) = id else {
+            adoption.skipped.push(skip(
+                group
+                    .x_field("X-Wago-ID")
+                    .map_or(SkipReason::NoProviderId, |id| {
+                        SkipReason::WagoOnly(id.to_owned())
+                    }),
+            ));
+            continue;
+        };
+
+        let existing = manifest.addon.iter().find(|row| id.matches(row));
+        let (row, new_row) = match existing {
+            Some(row) if lock.find(&row.name, flavor).is_some() => {
+                adoption
+                    .skipped
+                    .push(skip(SkipReason::AlreadyLocked(row.name.clone())));
+                continue;
+            }
+            Some(row) => (row.clone(), false),
+            None if manifest.addon.iter().any(|row| row.name == group.name)
+                || lock.find(&group.name, flavor).is_some() =>
+            {
+                adoption
+                    .skipped
+                    .push(skip(SkipReason::NameTaken(group.name.clone())));
+                continue;
+            }
+            None => (id.row(&group.name), true),
+        };
+        let main = group.main();
+        adoption.addons.push(AdoptedAddon {
+            row,
+            new_row,
+            version: matched
+                .map(|f| f.version.as_str())
+                .or_else(|| group.x_field("X-Curse-Packaged-Version"))
+                .or_else(|| main.version_for(flavor))
+                .map(str::to_owned),
+            folders,
+            file_id: matched.map(|f| f.file_id),
+        });
+    }
+    adoption.addons.sort_by(|a, b| a.row.name.cmp(&b.row.name));
+    adoption
+}
+
+/// Records every adopted addon in `lock` for `flavor`; the channel is the
+/// row's, else `default_channel`.
+pub fn apply(adoption: &Adoption, lock: &mut Lock, flavor: &Flavor, default_channel: &Channel) {
+    for addon in &adoption.addons {
+        lock.upsert(LockedAddon {
+            name: addon.row.name.clone(),
+            provider: addon.row.provider.clone(),
+            flavor: flavor.clone(),
+            channel: addon
+                .row
+                .channel
+                .clone()
+                .unwrap_or_else(|| default_channel.clone()),
+            project_id: addon.row.project_id,
+            resolved_version: addon.version.clone().unwrap_or_else(|| "unknown".into()),
+            resolved_id: addon
+                .file_id
+                .map_or_else(|| ADOPTED_ID.to_owned(), |id| id.to_string()),
+            download_url: String::new(),
+            sha256: None,
+            installed_dirs: addon.folders.clone(),
+            installed_at: Utc::now(),
+        });
+    }
+    lock.generated_at = Utc::now();
+}
@@ -0,0 +1,245 @@
+use std::path::PathBuf;
+
+use super::*;
+use crate::{
+    fs::InstalledAddon,
+    inventory::{ProviderId, group},
+    model::{Provider, Tag},
+    toc::TocFile,
+};
+
+fn on_disk(folder: &str, fields: &[(&str, &str)]) -> InstalledAddon {
+    InstalledAddon {
+        folder: folder.to_owned(),
+        toc_files: vec![TocFile {
+            path: PathBuf::from(format!("{folder}/{folder}.toc")),
+            version: Some("1.0".into()),
+            x_fields: fields
+                .iter()
+                .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+                .collect(),
+            ..Default::default()
+        }],
+    }
+}
+
+fn empty_manifest() -> Manifest {
+    Manifest {
+        schema: 1,
+        addon: vec![],
+    }
+}
+
+fn empty_lock() -> Lock {
+    Lock::new(Tag::new("test"))
+}
+
+fn folders(adoption: &Adoption) -> Vec<(&str, Vec<&str>)> {
+    adoption
+        .addons
+        .iter()
+        .map(|a| {
+            (
+                a.row.name.as_str(),
+                a.folders.iter().map(String::as_str).collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn plan_groups_folders_sharing_a_project_id() {
+    let scanned = [
+        on_disk("DBM-Core", &[("X-Curse-Project-ID", "3358")]),
+        on_disk("DBM-Raids-MoP", &[("X-Curse-Project-ID", "3358")]),
+        on_disk("Details", &[("X-Curse-Project-ID", "61284")]),
+    ];
+    let adoption = plan(
+        &group(&scanned, &empty_lock(), &Flavor::Retail),
+        &empty_lock(),
+        &empty_manifest(),
+        &Flavor::Retail,
+        &HashMap::new(),
+    );
+
+    assert_eq!(
+        folders(&adoption),
+        vec![
+            ("DBM-Core", vec!["DBM-Core", "DBM-Raids-MoP"]),
+            ("Details", vec!["Details"]),
+        ]
+    );
+    let dbm = &adoption.addons[0];
+    assert!(dbm.new_row);
+    assert_eq!(dbm.row.provider, Provider::CurseForge);
+    assert_eq!(dbm.row.project_id, Some(3358));
+}
+
+#[test]
+fn plan_attaches_prefixed_folders_without_ids() {
+    let scanned = [
+        on_disk("Bagnon", &[("X-Curse-Project-ID", "1592")]),
+        on_disk("Bagnon_Config", &[]),
+        on_disk("BagnonExtra", &[]),
+    ];
+    let adoption = plan(
+        &group(&scanned, &empty_lock(), &Flavor::Retail),
+        &empty_lock(),
+        &empty_manifest(),
+        &Flavor::Retail,
+        &HashMap::new(),
+    );
+
+    assert_eq!(
+        folders(&adoption),
+        vec![("Bagnon", vec!["Bagnon", "Bagnon_Config"])]
+    );
+    assert_eq!(
+        adoption.skipped,
+        vec![SkippedGroup {
+            name: "BagnonExtra".into(),
+            folders: vec!["BagnonExtra".into()],
+            reason: SkipReason::NoProviderId,
+        }]
+    );
+}
+
+#[test]
+fn plan_reads_wowi_ids_and_packaged_version() {
+    let scanned = [on_disk(
+        "Prat-3.0",
+        &[("x-wowi-id", "4876"), ("X-Curse-Packaged-Version", "3.9.1")],
+    )];
+    let adoption = plan(
+        &group(&scanned, &empty_lock(), &Flavor::Retail),
+        &empty_lock(),
+        &empty_manifest(),
+        &Flavor::Retail,
+        &HashMap::new(),
+    );
+
+    let prat = &adoption.addons[0];
+    assert_eq!(prat.row.provider, Provider::WoWInterface);
+    assert_eq!(prat.row.wowi_id, Some(4876));
+    assert_eq!(prat.version.as_deref(), Some("3.9.1"));
+}
+
+#[test]
+fn plan_skips_wago_only_and_owned_folders() {
+    let scanned = [
+        on_disk("Plater", &[("X-Wago-ID", "kRNLep6o")]),
+        on_disk("WeakAuras", &[("X-Curse-Project-ID", "65387")]),
+    ];
+    let mut lock = empty_lock();
+    let mut manifest = empty_manifest();
+    manifest.addon.push(ProviderId::CurseForge(65387).row("WA"));
+    let adoption = plan(
+        &group(&scanned, &lock, &Flavor::Retail),
+        &lock,
+        &manifest,
+        &Flavor::Retail,
+        &HashMap::new(),
+    );
+    assert_eq!(
+        adoption.skipped[0].reason,
+        SkipReason::WagoOnly("kRNLep6o".into())
+    );
+    // An existing row with the same project id is reused, not duplicated.
+    assert_eq!(adoption.addons[0].row.name, "WA");
+    assert!(!adoption.addons[0].new_row);
+
+    apply(&adoption, &mut lock, &Flavor::Retail, &Channel::Stable);
+    let again = plan(
+        &group(&scanned, &lock, &Flavor::Retail),
+        &lock,
+        &manifest,
+        &Flavor::Retail,
+        &HashMap::new(),
+    );
+    assert!(again.addons.is_empty());
+}
+
+#[test]
+fn plan_refuses_names_already_in_use() {
+    let scanned = [on_disk("Details", &[("X-Curse-Project-ID", "61284")])];
+    let mut manifest = empty_manifest();
+    manifest
+        .addon
+        .push(ProviderId::WoWInterface(1).row("Details"));
+    let adoption = plan(
+        &group(&scanned, &empty_lock(), &Flavor::Retail),
+        &empty_lock(),
+        &manifest,
+        &Flavor::Retail,
+        &HashMap::new(),
+    );
+
+    assert!(adoption.addons.is_empty());
+    assert_eq!(
+        adoption.skipped[0].reason,
+        SkipReason::NameTaken("Details".into())
+    );
+}
+
+#[test]
+fn apply_locks_adopted_addons_for_update() {
+    let scanned = [
+        on_disk("Bagnon", &[("X-Curse-Project-ID", "1592")]),
+        on_disk("Bagnon_Config", &[]),
+    ];
+    let adoption = plan(
+        &group(&scanned, &empty_lock(), &Flavor::Retail),
+        &empty_lock(),
+        &empty_manifest(),
+        &Flavor::Retail,
+        &HashMap::new(),
+    );
+    let mut lock = empty_lock();
+    apply(&adoption, &mut lock, &Flavor::Retail, &Channel::Beta);
+
+    let entry = lock.find("Bagnon", &Flavor::Retail).unwrap();
+    assert_eq!(entry.project_id, Some(1592));
+    assert_eq!(entry.channel, Channel::Beta);
+    assert_eq!(entry.resolved_version, "1.0");
+    assert_eq!(entry.resolved_id, ADOPTED_ID);
+    assert_eq!(entry.installed_dirs, vec!["Bagnon", "Bagnon_Config"]);
+}
+
+#[test]
+fn plan_uses_fingerprints_for_folders_without_ids() {
+    let scanned = [
+        on_disk("Details", &[]),
+        on_disk("Details_Streamer", &[]),
+        on_disk("Loose", &[]),
+    ];
+    let fingerprinted = HashMap::from([(
+        "Details".to_owned(),
+        Fingerprinted {
+            project_id: 61284,
+            file_id: 7001,
+            version: "Details.20251018".into(),
+        },
+    )]);
+    let adoption = plan(
+        &group(&scanned, &empty_lock(), &Flavor::Retail),
+        &empty_lock(),
+        &empty_manifest(),
+        &Flavor::Retail,
+        &fingerprinted,
+    );
+
+    assert_eq!(
+        folders(&adoption),
+        vec![("Details", vec!["Details", "Details_Streamer"])]
+    );
+    let details = &adoption.addons[0];
+    assert_eq!(details.row.project_id, Some(61284));
+    assert_eq!(details.version.as_deref(), Some("Details.20251018"));
+
+    let mut lock = empty_lock();
+    apply(&adoption, &mut lock, &Flavor::Retail, &Channel::Stable);
+    assert_eq!(
+        lock.find("Details", &Flavor::Retail).unwrap().resolved_id,
+        "7001"
+    );
+}
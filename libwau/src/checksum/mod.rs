@@ -0,0 +1,134 @@
+//! Artifact digests: the algorithm-tagged checksums providers publish, and
+//! hashing of downloaded files to verify them.
+//!
+//! Providers do not agree on an algorithm (CurseForge publishes SHA-1 and MD5,
+//! GitHub SHA-256), so every expected digest carries its [`Algorithm`]. The lock
+//! always records a SHA-256 computed locally, whatever the provider offered.
+
+use std::{fs, io::Read, path::Path};
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::Sha256;
+
+use crate::Result;
+
+#[cfg(test)]
+mod tests;
+
+/// Hash algorithm of a published digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    Sha1,
+    Md5,
+    Sha256,
+}
+
+impl Algorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha1 => "sha1",
+            Self::Md5 => "md5",
+            Self::Sha256 => "sha256",
+        }
+    }
+}
+
+impl std::fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An expected digest: algorithm plus hex value as published by the provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digest {
+    pub algorithm: Algorithm,
+    pub hex: String,
+}
+
+impl Digest {
+    pub fn sha1(hex: impl Into<String>) -> Self {
+        Self::new(Algorithm::Sha1, hex)
+    }
+
+    pub fn md5(hex: impl Into<String>) -> Self {
+        Self::new(Algorithm::Md5, hex)
+    }
+
+    pub fn sha256(hex: impl Into<String>) -> Self {
+        Self::new(Algorithm::Sha256, hex)
+    }
+
+    fn new(algorithm: Algorithm, hex: impl Into<String>) -> Self {
+        Self {
+            algorithm,
+            hex: hex.into(),
+        }
+    }
+
+    /// Compares against a computed hex digest, ignoring case.
+    pub fn matches(&self, actual_hex: &str) -> bool {
+        self.hex.eq_ignore_ascii_case(actual_hex)
+    }
+}
+
+impl std::fmt::Display for Digest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.hex)
+    }
+}
+
+/// Hashes the file at `path` with `algorithm` and returns the lowercase hex digest.
+pub fn hash_file(path: &Path, algorithm: Algorithm) -> Result<String> {
+    let file = fs::File::open(path)?;
+    let bytes = match algorithm {
+        Algorithm::Sha1 => hash_reader::<Sha1>(file)?,
+        Algorithm::Md5 => hash_reader::<Md5>(file)?,
+        Algorithm::Sha256 => hash_reader::<Sha256>(file)?,
+    };
+    Ok(to_hex(&bytes))
+}
+
+/// Verifies the file at `path` against `expected` and returns its SHA-256.
+///
+/// Fails with [`crate::Error::ChecksumMismatch`] when the file does not match,
+/// which covers both tampered and truncated downloads.
+pub fn verify_file(name: &str, path: &Path, expected: Option<&Digest>) -> Result<String> {
+    let sha256 = hash_file(path, Algorithm::Sha256)?;
+
+    if let Some(expected) = expected {
+        let actual = match expected.algorithm {
+            Algorithm::Sha256 => sha256.clone(),
+            other => hash_file(path, other)?,
+        };
+        if !expected.matches(&actual) {
+            return Err(crate::Error::ChecksumMismatch {
+                name: name.to_owned(),
+                algorithm: expected.algorithm,
+                expected: expected.hex.clone(),
+                actual,
+            });
+        }
+        tracing::debug!(name, digest = %expected, "checksum verified");
+    }
+
+    Ok(sha256)
+}
+
+fn hash_reader<D: sha2::Digest>(mut reader: impl Read) -> Result<Vec<u8>> {
+    let mut hasher = D::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+// Well-known digests of the ASCII string "abc".
+const ABC_SHA1: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
+const ABC_MD5: &str = "900150983cd24fb0d6963f7d28e17f72";
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+fn write_abc(dir: &Path) -> std::path::PathBuf {
+    let path = dir.join("abc.bin");
+    std::fs::write(&path, b"abc").unwrap();
+    path
+}
+
+#[test]
+fn hash_file_each_algorithm() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_abc(dir.path());
+    assert_eq!(hash_file(&path, Algorithm::Sha1).unwrap(), ABC_SHA1);
+    assert_eq!(hash_file(&path, Algorithm::Md5).unwrap(), ABC_MD5);
+    assert_eq!(hash_file(&path, Algorithm::Sha256).unwrap(), ABC_SHA256);
+}
+
+#[test]
+fn digest_matches_ignores_case() {
+    assert!(Digest::sha1(ABC_SHA1.to_uppercase()).matches(ABC_SHA1));
+    assert!(!Digest::sha1(ABC_SHA1).matches(ABC_MD5));
+}
+
+#[test]
+fn digest_display_prefixes_algorithm() {
+    assert_eq!(Digest::md5("00ff").to_string(), "md5:00ff");
+}
+
+#[test]
+fn verify_file_returns_sha256_without_expected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_abc(dir.path());
+    assert_eq!(verify_file("Abc", &path, None).unwrap(), ABC_SHA256);
+}
+
+#[test]
+fn verify_file_accepts_matching_md5() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_abc(dir.path());
+    let sha256 = verify_file("Abc", &path, Some(&Digest::md5(ABC_MD5))).unwrap();
+    assert_eq!(sha256, ABC_SHA256);
+}
+
+#[test]
+fn verify_file_rejects_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_abc(dir.path());
+    let result = verify_file("Abc", &path, Some(&Digest::sha256("00".repeat(32))));
+    match result {
+        Err(crate::Error::ChecksumMismatch {
+            algorithm, actual, ..
+        }) => {
+            assert_eq!(algorithm, Algorithm::Sha256);
+            assert_eq!(actual, ABC_SHA256);
+        }
+        other => panic!("expected ChecksumMismatch, got {other:?}"),
+    }
+}
@@ -0,0 +1,488 @@
+//! Detects the WoW client behind an install: its product, build and interface.
+//!
+//! A Battle.net install keeps one folder per product under a shared root
+//! (`World of Warcraft/_retail_`, `_classic_`, `_classic_era_`, …) and lists
+//! every product's version in the root's `.build.info`, a `|`-separated table:
+//!
+//! ```text
+//! Branch!STRING:0|Active!DEC:1|…|Version!STRING:0|…|Product!STRING:0
+//! eu|1|…|11.2.5.64270|…|wow
+//! ```
+//!
+//! The version maps to the interface number the client expects from `.toc` files
+//! (`11.2.5` → `110205`). Private-server installs usually have neither; detection
+//! then finds nothing and callers fall back to the configured flavor, and
+//! [`client_dir`] keeps their flat `wow_root/Interface/AddOns` layout.
+//!
+//! [`discover`] looks for installs inside Wine prefixes (Lutris, plain Wine,
+//! Proton's `compatdata/<appid>/pfx`), folders of such prefixes, and WoW roots.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::Flavor;
+
+#[cfg(test)]
+mod tests;
+
+/// A Battle.net WoW product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Product {
+    #[serde(rename = "retail")]
+    Retail,
+    #[serde(rename = "ptr")]
+    Ptr,
+    #[serde(rename = "xptr")]
+    Xptr,
+    #[serde(rename = "beta")]
+    Beta,
+    #[serde(rename = "classic")]
+    Classic,
+    #[serde(rename = "classic-ptr")]
+    ClassicPtr,
+    #[serde(rename = "classic-beta")]
+    ClassicBeta,
+    #[serde(rename = "classic-era")]
+    ClassicEra,
+    #[serde(rename = "classic-era-ptr")]
+    ClassicEraPtr,
+    #[serde(rename = "anniversary")]
+    Anniversary,
+}
+
+impl Product {
+    pub const ALL: &[Self] = &[
+        Self::Retail,
+        Self::Ptr,
+        Self::Xptr,
+        Self::Beta,
+        Self::Classic,
+        Self::ClassicPtr,
+        Self::ClassicBeta,
+        Self::ClassicEra,
+        Self::ClassicEraPtr,
+        Self::Anniversary,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Retail => "retail",
+            Self::Ptr => "ptr",
+            Self::Xptr => "xptr",
+            Self::Beta => "beta",
+            Self::Classic => "classic",
+            Self::ClassicPtr => "classic-ptr",
+            Self::ClassicBeta => "classic-beta",
+            Self::ClassicEra => "classic-era",
+            Self::ClassicEraPtr => "classic-era-ptr",
+            Self::Anniversary => "anniversary",
+        }
+    }
+
+    /// The product code in `.build.info`, e.g. `wow_classic_era`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Retail => "wow",
+            Self::Ptr => "wowt",
+            Self::Xptr => "wowxptr",
+            Self::Beta => "wow_beta",
+            Self::Classic => "wow_classic",
+            Self::ClassicPtr => "wow_classic_ptr",
+            Self::ClassicBeta => "wow_classic_beta",
+            Self::ClassicEra => "wow_classic_era",
+            Self::ClassicEraPtr => "wow_classic_era_ptr",
+            Self::Anniversary => "wow_anniversary",
+        }
+    }
+
+    /// The product folder under a Battle.net root, e.g. `_classic_era_`.
+    pub fn folder(&self) -> &'static str {
+        match self {
+            Self::Retail => "_retail_",
+            Self::Ptr => "_ptr_",
+            Self::Xptr => "_xptr_",
+            Self::Beta => "_beta_",
+            Self::Classic => "_classic_",
+            Self::ClassicPtr => "_classic_ptr_",
+            Self::ClassicBeta => "_classic_beta_",
+            Self::ClassicEra => "_classic_era_",
+            Self::ClassicEraPtr => "_classic_era_ptr_",
+            Self::Anniversary => "_anniversary_",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.iter().find(|p| p.code() == code).copied()
+    }
+
+    /// Matches a product folder name case-insensitively (Wine prefixes are not
+    /// consistent about case).
+    pub fn from_folder(folder: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|p| p.folder().eq_ignore_ascii_case(folder))
+            .copied()
+    }
+
+    /// The flavor every build of this product targets, if it is fixed. Mainline
+    /// products are always [`Flavor::Retail`] and Classic Era always
+    /// [`Flavor::Era`]; the progression and anniversary products move through
+    /// expansions, so only their version tells.
+    pub fn flavor(&self) -> Option<Flavor> {
+        match self {
+            Self::Retail | Self::Ptr | Self::Xptr | Self::Beta => Some(Flavor::Retail),
+            Self::ClassicEra | Self::ClassicEraPtr => Some(Flavor::Era),
+            Self::Classic | Self::ClassicPtr | Self::ClassicBeta | Self::Anniversary => None,
+        }
+    }
+
+    /// Products whose current builds may run `flavor`, most likely first; used
+    /// to infer an install's product folder when the config does not name one.
+    pub fn for_flavor(flavor: &Flavor) -> &'static [Self] {
+        match flavor {
+            Flavor::Retail => &[Self::Retail],
+            Flavor::Era => &[Self::ClassicEra],
+            Flavor::Tbc => &[Self::Anniversary, Self::Classic],
+            Flavor::Wrath | Flavor::Cata | Flavor::Mop => &[Self::Classic],
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for Product {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A client version such as `11.2.5.64270`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub build: u32,
+}
+
+impl ClientVersion {
+    /// Parses `major.minor.patch.build`; `None` for anything else.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.trim().split('.').map(|p| p.parse::<u32>().ok());
+        let version = Self {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+            build: parts.next()??,
+        };
+        parts.next().is_none().then_some(version)
+    }
+
+    /// The `## Interface` number of this version, e.g. `110205` for `11.2.5`.
+    pub fn interface(&self) -> u32 {
+        self.major * 10000 + self.minor * 100 + self.patch
+    }
+}
+
+impl fmt::Display for ClientVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.patch, self.build
+        )
+    }
+}
+
+/// One row of `.build.info`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildInfo {
+    /// Raw product code; unknown codes are kept so callers can report them.
+    pub product: String,
+    pub active: bool,
+    pub version: Option<ClientVersion>,
+}
+
+/// Parses `.build.info` content. Rows without a `Product` column are skipped;
+/// a file without a header yields nothing.
+pub fn parse_build_info(content: &str) -> Vec<BuildInfo> {
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    // Header cells are `Name!TYPE:size`.
+    let column = |name: &str| {
+        header
+            .split('|')
+            .position(|h| h.split('!').next() == Some(name))
+    };
+    let (Some(product), active, version) = (column("Product"), column("Active"), column("Version"))
+    else {
+        return Vec::new();
+    };
+
+    lines
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('|').collect();
+            Some(BuildInfo {
+                product: cells.get(product)?.trim().to_owned(),
+                active: active
+                    .and_then(|i| cells.get(i))
+                    .is_none_or(|v| v.trim() == "1"),
+                version: version
+                    .and_then(|i| cells.get(i))
+                    .and_then(|v| ClientVersion::parse(v)),
+            })
+        })
+        .collect()
+}
+
+/// A WoW client found on disk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Client {
+    /// `None` for a flat layout without a product folder or `.build.info` row.
+    pub product: Option<Product>,
+    /// The directory holding the client's `Interface` and `WTF` folders.
+    pub dir: PathBuf,
+    pub version: Option<ClientVersion>,
+}
+
+impl Client {
+    /// The flavor the client runs: fixed by the product where it can be, else
+    /// derived from the version's interface number.
+    pub fn flavor(&self) -> Option<Flavor> {
+        self.product
+            .and_then(|p| p.flavor())
+            .or_else(|| self.interface().and_then(Flavor::from_interface))
+    }
+
+    pub fn interface(&self) -> Option<u32> {
+        self.version.map(|v| v.interface())
+    }
+
+    /// Guesses the flavor of a client [`flavor`](Self::flavor) cannot tell: the
+    /// one the `## Interface` numbers of most installed addons fall in, leaving
+    /// retail out when the folder holds `WowClassic.exe`. `None` without addons
+    /// or when two flavors tie.
+    pub fn flavor_hint(&self) -> Option<Flavor> {
+        let addons_dir = crate::fs::join_ignore_case(&self.dir, &["Interface", "AddOns"]);
+        let addons = crate::fs::scan(&addons_dir).ok()?;
+        let classic_only = crate::fs::join_ignore_case(&self.dir, &["WowClassic.exe"]).is_file();
+
+        let mut votes: Vec<(&Flavor, usize)> = Flavor::ALL
+            .iter()
+            .filter(|f| !(classic_only && **f == Flavor::Retail))
+            .map(|f| {
+                let range = f.interface_range();
+                let count = addons
+                    .iter()
+                    .filter(|a| a.all_interface_versions().iter().any(|i| range.contains(i)))
+                    .count();
+                (f, count)
+            })
+            .collect();
+        votes.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        match votes.as_slice() {
+            [(flavor, top), rest @ ..] if *top > 0 && rest.first().is_none_or(|(_, n)| n < top) => {
+                Some((*flavor).clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.product, self.version) {
+            (Some(p), Some(v)) => write!(f, "{p} {v}"),
+            (Some(p), None) => write!(f, "{p}"),
+            (None, Some(v)) => write!(f, "{v}"),
+            (None, None) => write!(f, "{}", self.dir.display()),
+        }
+    }
+}
+
+/// Resolves the directory holding the `Interface` folder of an install at
+/// `wow_root`:
+///
+/// - `wow_root` itself when it already is a product folder;
+/// - `wow_root/<product folder>` when `product` is given;
+/// - else the first of [`Product::for_flavor`] whose folder exists in `wow_root`;
+/// - else `wow_root`, the flat layout private servers use.
+///
+/// Folder names match case-insensitively, as under Wine.
+pub fn client_dir(wow_root: &Path, product: Option<Product>, flavor: &Flavor) -> PathBuf {
+    let root_is_product = wow_root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| Product::from_folder(n).is_some());
+    if root_is_product {
+        return wow_root.to_path_buf();
+    }
+    if let Some(product) = product {
+        return crate::fs::join_ignore_case(wow_root, &[product.folder()]);
+    }
+    Product::for_flavor(flavor)
+        .iter()
+        .map(|p| crate::fs::join_ignore_case(wow_root, &[p.folder()]))
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| wow_root.to_path_buf())
+}
+
+/// Detects the client whose `Interface` folder lives in `dir`.
+///
+/// The product comes from `dir`'s name (`_retail_`, …); the version from the
+/// `.build.info` in `dir` or its parent, matched by product code, else the only
+/// active row. Returns `None` when neither is found.
+pub fn client_at(dir: &Path) -> Option<Client> {
+    let product = dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(Product::from_folder);
+    let rows = [dir.join(".build.info")]
+        .into_iter()
+        .chain(dir.parent().map(|p| p.join(".build.info")))
+        .find_map(|path| match fs::read_to_string(&path) {
+            Ok(content) => Some(parse_build_info(&content)),
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "no readable .build.info");
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    let row = match product {
+        Some(p) => rows.iter().find(|r| r.product == p.code()),
+        None => {
+            let mut active = rows.iter().filter(|r| r.active);
+            active.next().filter(|_| active.next().is_none())
+        }
+    };
+    let product = product.or_else(|| row.and_then(|r| Product::from_code(&r.product)));
+    if product.is_none() && row.is_none() {
+        return None;
+    }
+    Some(Client {
+        product,
+        dir: dir.to_path_buf(),
+        version: row.and_then(|r| r.version),
+    })
+}
+
+/// Detects every client under `root`: `root` itself when it is a product folder,
+/// else each product folder inside it, else a flat install at `root`.
+pub fn detect(root: &Path) -> Vec<Client> {
+    let is_product = |dir: &Path| {
+        dir.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| Product::from_folder(n).is_some())
+    };
+    if is_product(root) {
+        return client_at(root).into_iter().collect();
+    }
+
+    let mut clients: Vec<Client> = fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && is_product(p))
+        .filter_map(|p| client_at(&p))
+        .collect();
+    if clients.is_empty() {
+        clients.extend(client_at(root));
+    }
+    clients.sort_by_key(|c| c.product.map(|p| p.as_str()));
+    clients
+}
+
+/// Where the Battle.net installer puts WoW inside a Wine prefix.
+const PREFIX_INSTALL_DIRS: &[&[&str]] = &[
+    &["drive_c", "Program Files (x86)", "World of Warcraft"],
+    &["drive_c", "Program Files", "World of Warcraft"],
+];
+
+/// Client executables that mark a flat install without `.build.info`.
+const CLIENT_EXECUTABLES: &[&str] = &["Wow.exe", "Wow-64.exe", "WowClassic.exe"];
+
+/// Finds the clients under each of `roots`, which may be WoW roots, Wine prefixes,
+/// Proton `compatdata/<appid>` entries, or folders holding several prefixes
+/// (`~/Games`, `compatdata`). Flat installs without `.build.info` are reported
+/// with neither product nor version. Folder and executable names match
+/// case-insensitively, as under Wine. Missing roots are skipped; every client is
+/// reported once, in `roots` order.
+pub fn discover(roots: &[PathBuf]) -> Vec<Client> {
+    let mut clients: Vec<Client> = Vec::new();
+    for wow_root in roots.iter().flat_map(|r| wow_roots(r)) {
+        let mut found = detect(&wow_root);
+        if found.is_empty() && looks_like_client(&wow_root) {
+            found.push(Client {
+                product: None,
+                dir: wow_root,
+                version: None,
+            });
+        }
+        for client in found {
+            if clients.iter().all(|c| c.dir != client.dir) {
+                clients.push(client);
+            }
+        }
+    }
+    clients
+}
+
+/// WoW roots at, inside, or one prefix below `path`.
+fn wow_roots(path: &Path) -> Vec<PathBuf> {
+    if is_wow_root(path) {
+        return vec![path.to_path_buf()];
+    }
+    let in_prefix = prefix_installs(path);
+    if !in_prefix.is_empty() {
+        return in_prefix;
+    }
+    let mut children: Vec<PathBuf> = fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    children.sort();
+    children.iter().flat_map(|c| prefix_installs(c)).collect()
+}
+
+/// Installs inside the Wine prefix `path`, or inside its Proton `pfx` folder.
+fn prefix_installs(path: &Path) -> Vec<PathBuf> {
+    [path.to_path_buf(), path.join("pfx")]
+        .iter()
+        .filter(|prefix| crate::fs::join_ignore_case(prefix, &["drive_c"]).is_dir())
+        .flat_map(|prefix| {
+            PREFIX_INSTALL_DIRS
+                .iter()
+                .map(move |d| crate::fs::join_ignore_case(prefix, d))
+        })
+        .filter(|dir| is_wow_root(dir))
+        .collect()
+}
+
+fn is_wow_root(dir: &Path) -> bool {
+    let has_product = || {
+        fs::read_dir(dir).into_iter().flatten().flatten().any(|e| {
+            e.path().is_dir()
+                && e.file_name()
+                    .to_str()
+                    .is_some_and(|n| Product::from_folder(n).is_some())
+        })
+    };
+    dir.join(".build.info").is_file() || looks_like_client(dir) || has_product()
+}
+
+fn looks_like_client(dir: &Path) -> bool {
+    crate::fs::join_ignore_case(dir, &["Interface"]).is_dir()
+        || CLIENT_EXECUTABLES
+            .iter()
+            .any(|e| crate::fs::join_ignore_case(dir, &[e]).is_file())
+}
@@ -0,0 +1,230 @@
+use super::*;
+
+const BUILD_INFO: &str = "\
+Branch!STRING:0|Active!DEC:1|Build Key!HEX:16|Version!STRING:0|Product!STRING:0
+eu|1|aaaa|11.2.5.64270|wow
+eu|1|bbbb|5.5.1.63311|wow_classic
+eu|1|cccc|1.15.7.63696|wow_classic_era
+eu|0|dddd|11.2.7.64500|wowt
+";
+
+fn write_root(products: &[&str]) -> tempfile::TempDir {
+    let root = tempfile::tempdir().unwrap();
+    std::fs::write(root.path().join(".build.info"), BUILD_INFO).unwrap();
+    for folder in products {
+        std::fs::create_dir_all(root.path().join(folder).join("Interface/AddOns")).unwrap();
+    }
+    root
+}
+
+#[test]
+fn parse_build_info_reads_product_version_and_active() {
+    let rows = parse_build_info(BUILD_INFO);
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[1].product, "wow_classic");
+    assert_eq!(
+        rows[1].version,
+        Some(ClientVersion {
+            major: 5,
+            minor: 5,
+            patch: 1,
+            build: 63311,
+        })
+    );
+    assert!(rows[0].active);
+    assert!(!rows[3].active);
+    assert!(parse_build_info("Branch!STRING:0\neu\n").is_empty());
+}
+
+#[test]
+fn client_version_maps_to_interface_number() {
+    let version = ClientVersion::parse("11.2.5.64270").unwrap();
+    assert_eq!(version.interface(), 110205);
+    assert_eq!(version.to_string(), "11.2.5.64270");
+    assert_eq!(
+        ClientVersion::parse("1.15.7.63696").unwrap().interface(),
+        11507
+    );
+    assert!(ClientVersion::parse("11.2.5").is_none());
+}
+
+#[test]
+fn product_flavor_is_fixed_for_mainline_and_era_only() {
+    assert_eq!(Product::Ptr.flavor(), Some(Flavor::Retail));
+    assert_eq!(Product::ClassicEra.flavor(), Some(Flavor::Era));
+    assert_eq!(Product::Classic.flavor(), None);
+    assert_eq!(
+        Product::from_folder("_Classic_Era_"),
+        Some(Product::ClassicEra)
+    );
+    assert_eq!(Product::from_code("wow_classic"), Some(Product::Classic));
+}
+
+#[test]
+fn detect_finds_every_product_folder_under_a_battle_net_root() {
+    let root = write_root(&["_retail_", "_classic_", "_classic_era_"]);
+    let clients = detect(root.path());
+
+    let found: Vec<(Option<Product>, Option<Flavor>, Option<u32>)> = clients
+        .iter()
+        .map(|c| (c.product, c.flavor(), c.interface()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (Some(Product::Classic), Some(Flavor::Mop), Some(50501)),
+            (Some(Product::ClassicEra), Some(Flavor::Era), Some(11507)),
+            (Some(Product::Retail), Some(Flavor::Retail), Some(110205)),
+        ]
+    );
+}
+
+#[test]
+fn detect_accepts_a_product_folder_as_root() {
+    let root = write_root(&["_classic_era_"]);
+    let clients = detect(&root.path().join("_classic_era_"));
+    assert_eq!(clients.len(), 1);
+    assert_eq!(clients[0].product, Some(Product::ClassicEra));
+    assert_eq!(clients[0].interface(), Some(11507));
+}
+
+#[test]
+fn detect_finds_nothing_in_a_flat_private_server_install() {
+    let root = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(root.path().join("Interface/AddOns")).unwrap();
+    assert!(detect(root.path()).is_empty());
+    assert!(client_at(root.path()).is_none());
+}
+
+#[test]
+fn client_at_uses_the_only_active_row_of_a_flat_install() {
+    let root = tempfile::tempdir().unwrap();
+    std::fs::write(
+        root.path().join(".build.info"),
+        "Active!DEC:1|Version!STRING:0|Product!STRING:0\n1|3.3.5.12340|wow\n",
+    )
+    .unwrap();
+    let client = client_at(root.path()).unwrap();
+    assert_eq!(client.product, Some(Product::Retail));
+    assert_eq!(client.interface(), Some(30305));
+}
+
+#[test]
+fn client_dir_picks_the_product_folder_of_a_battle_net_root() {
+    let root = write_root(&["_retail_", "_classic_era_"]);
+    let root = root.path();
+
+    assert_eq!(
+        client_dir(root, None, &Flavor::Retail),
+        root.join("_retail_")
+    );
+    assert_eq!(
+        client_dir(root, None, &Flavor::Era),
+        root.join("_classic_era_")
+    );
+    // Explicit products win, whether or not the folder exists yet.
+    assert_eq!(
+        client_dir(root, Some(Product::Ptr), &Flavor::Retail),
+        root.join("_ptr_")
+    );
+    // A root that already is a product folder is used as is.
+    let era = root.join("_classic_era_");
+    assert_eq!(
+        client_dir(&era, Some(Product::ClassicEra), &Flavor::Era),
+        era
+    );
+}
+
+#[test]
+fn client_dir_keeps_flat_layouts() {
+    let root = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(root.path().join("Interface/AddOns")).unwrap();
+    assert_eq!(client_dir(root.path(), None, &Flavor::Wrath), root.path());
+    assert_eq!(client_dir(root.path(), None, &Flavor::Wod), root.path());
+}
+
+#[test]
+fn flavor_hint_follows_the_interfaces_of_installed_addons() {
+    let root = tempfile::tempdir().unwrap();
+    let addon = |name: &str, interface: &str| {
+        let dir = root.path().join("Interface/AddOns").join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(format!("{name}.toc")),
+            format!("## Interface: {interface}\n"),
+        )
+        .unwrap();
+    };
+    let client = Client {
+        product: None,
+        dir: root.path().to_path_buf(),
+        version: None,
+    };
+    assert_eq!(client.flavor_hint(), None);
+
+    addon("Questie", "11507");
+    addon("Bagnon", "11507, 120001");
+    assert_eq!(client.flavor_hint(), Some(Flavor::Era));
+
+    // A tie is no hint, unless the classic executable rules retail out.
+    addon("Details", "120001");
+    assert_eq!(client.flavor_hint(), None);
+    std::fs::write(root.path().join("WowClassic.exe"), "").unwrap();
+    assert_eq!(client.flavor_hint(), Some(Flavor::Era));
+}
+
+#[test]
+fn discover_finds_installs_in_wine_and_proton_prefixes() {
+    let home = tempfile::tempdir().unwrap();
+    let install = |prefix: &str| {
+        let root = home
+            .path()
+            .join(prefix)
+            .join("drive_c/Program Files (x86)/World of Warcraft");
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    };
+    // Lutris keeps one prefix per game under ~/Games.
+    let lutris = install("Games/battlenet");
+    std::fs::write(lutris.join(".build.info"), BUILD_INFO).unwrap();
+    std::fs::create_dir_all(lutris.join("_retail_/Interface")).unwrap();
+    // Proton nests the prefix in `pfx`.
+    let proton = install("compatdata/123/pfx");
+    std::fs::create_dir_all(proton.join("Interface/AddOns")).unwrap();
+    // A prefix without WoW in it.
+    std::fs::create_dir_all(home.path().join("Games/other/drive_c")).unwrap();
+
+    let clients = discover(&[
+        home.path().join("Games"),
+        home.path().join("compatdata"),
+        home.path().join("missing"),
+        lutris.join("_retail_"),
+    ]);
+    let found: Vec<(Option<Product>, &Path)> = clients
+        .iter()
+        .map(|c| (c.product, c.dir.as_path()))
+        .collect();
+    let retail = lutris.join("_retail_");
+    assert_eq!(
+        found,
+        vec![
+            (Some(Product::Retail), retail.as_path()),
+            (None, proton.as_path()),
+        ]
+    );
+}
+
+#[test]
+fn discover_matches_prefix_folders_and_executables_in_any_case() {
+    let home = tempfile::tempdir().unwrap();
+    let root = home
+        .path()
+        .join("wine/drive_c/program files (x86)/world of warcraft");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("wow.exe"), "").unwrap();
+
+    let clients = discover(&[home.path().join("wine")]);
+    assert_eq!(clients.len(), 1);
+    assert_eq!(clients[0].dir, root);
+    assert_eq!(clients[0].product, None);
+}
@@ -2,7 +2,11 @@ use std::path::PathBuf;
 
 use thiserror::Error;
 
-use crate::model::Provider;
+use crate::{
+    checksum::Algorithm,
+    lock::FolderClaim,
+    model::{Provider, Tag},
+};
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -52,12 +56,57 @@ pub enum Error {
         pattern: String,
     },
 
+    #[error("pinned {pin} for addon '{name}' not found")]
+    PinNotFound { name: String, pin: String },
+
+    #[error("provider '{provider}' cannot honour pinned {pin} for addon '{name}'")]
+    UnsupportedPin {
+        name: String,
+        provider: Provider,
+        pin: String,
+    },
+
+    #[error("{algorithm} checksum mismatch for addon '{name}': expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        name: String,
+        algorithm: Algorithm,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("download from {url} exceeds the {limit}-byte limit")]
+    DownloadTooLarge { url: String, limit: u64 },
+
+    #[error("archive {} expands beyond the {limit}-byte limit", path.display())]
+    UnpackedTooLarge { path: PathBuf, limit: u64 },
+
     #[error("zip extraction failed: {0}")]
     ZipExtract(#[from] zip::result::ZipError),
 
+    #[error("invalid .pkgmeta at {}: {message}", path.display())]
+    InvalidPkgMeta { path: PathBuf, message: String },
+
     #[error("no installable addon directories found in zip for '{name}'")]
     NoInstallableDirs { name: String },
 
+    #[error(
+        "addon '{name}' ships folders other addons already own: {}",
+        claims.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    FolderConflict {
+        name: String,
+        claims: Vec<FolderClaim>,
+    },
+
     #[error("addon '{name}' not found in lock")]
     AddonNotInLock { name: String },
+
+    #[error(
+        "install tag '{tag}' has an interrupted run recorded in {}; roll it forward or back first",
+        path.display()
+    )]
+    InterruptedRun { tag: Tag, path: PathBuf },
+
+    #[error("cannot roll back addon '{name}': {reason}; roll forward instead")]
+    RollBackUnsupported { name: String, reason: String },
 }
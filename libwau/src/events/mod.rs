@@ -0,0 +1,113 @@
+//! Progress events emitted by [`crate::ops`] and the providers.
+//!
+//! `libwau` never prints. Callers that want to show progress pass an
+//! [`EventSink`] into the `ops` entry points; the CLI renders the stream as a
+//! status line, and a GUI frontend can forward it to its own event loop (any
+//! `Fn(Event) + Send + Sync` closure is a sink, so an mpsc sender wrapped in a
+//! closure works). Use [`NullSink`] to ignore events.
+
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// One step of an install, update, or removal, tagged with the addon's manifest name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// The provider is about to resolve the manifest row.
+    ResolveStarted { addon: String },
+    /// `bytes` of the artifact have been downloaded so far; `total` when the size is known.
+    DownloadProgress {
+        addon: String,
+        bytes: u64,
+        total: Option<u64>,
+    },
+    /// The download finished and is being verified and extracted.
+    Extracting { addon: String },
+    /// The extracted dirs are being swapped into `Interface/AddOns`.
+    SwappingIn { addon: String, dirs: Vec<String> },
+    /// The addon's dirs are being removed from `Interface/AddOns`.
+    Removing { addon: String, dirs: Vec<String> },
+    /// The in-memory lock now records the change; persisting it is up to the caller.
+    LockUpdated { addon: String },
+}
+
+impl Event {
+    /// Manifest name of the addon the event belongs to.
+    pub fn addon(&self) -> &str {
+        match self {
+            Event::ResolveStarted { addon }
+            | Event::DownloadProgress { addon, .. }
+            | Event::Extracting { addon }
+            | Event::SwappingIn { addon, .. }
+            | Event::Removing { addon, .. }
+            | Event::LockUpdated { addon } => addon,
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::ResolveStarted { addon } => write!(f, "resolving {addon}"),
+            Event::DownloadProgress {
+                addon,
+                bytes,
+                total: Some(total),
+            } => write!(f, "downloading {addon} ({bytes}/{total} bytes)"),
+            Event::DownloadProgress { addon, bytes, .. } => {
+                write!(f, "downloading {addon} ({bytes} bytes)")
+            }
+            Event::Extracting { addon } => write!(f, "extracting {addon}"),
+            Event::SwappingIn { addon, dirs } => {
+                write!(f, "swapping in {addon} ({})", dirs.join(", "))
+            }
+            Event::Removing { addon, dirs } => write!(f, "removing {addon} ({})", dirs.join(", ")),
+            Event::LockUpdated { addon } => write!(f, "locked {addon}"),
+        }
+    }
+}
+
+/// Receives [`Event`]s. Called inline on the task doing the work, so keep it cheap.
+pub trait EventSink: Send + Sync {
+    fn emit(&self, event: Event);
+}
+
+impl<F> EventSink for F
+where
+    F: Fn(Event) + Send + Sync,
+{
+    fn emit(&self, event: Event) {
+        self(event)
+    }
+}
+
+/// Discards every event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullSink;
+
+impl EventSink for NullSink {
+    fn emit(&self, _event: Event) {}
+}
+
+/// Download progress reporter handed to [`crate::providers::Provider::download`],
+/// bound to the addon being downloaded.
+pub struct Progress<'a> {
+    sink: &'a dyn EventSink,
+    addon: &'a str,
+}
+
+impl<'a> Progress<'a> {
+    pub fn new(sink: &'a dyn EventSink, addon: &'a str) -> Self {
+        Self { sink, addon }
+    }
+
+    /// Reports that `bytes` have been downloaded so far, out of `total` if known.
+    pub fn downloaded(&self, bytes: u64, total: Option<u64>) {
+        self.sink.emit(Event::DownloadProgress {
+            addon: self.addon.to_owned(),
+            bytes,
+            total,
+        });
+    }
+}
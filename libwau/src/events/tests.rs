@@ -0,0 +1,37 @@
+use std::sync::Mutex;
+
+use super::*;
+
+#[test]
+fn closure_sink_receives_progress_for_bound_addon() {
+    let seen = Mutex::new(Vec::new());
+    let sink = |event: Event| seen.lock().unwrap().push(event);
+    let progress = Progress::new(&sink, "Bagnon");
+
+    progress.downloaded(512, Some(1024));
+    progress.downloaded(1024, Some(1024));
+
+    let seen = seen.into_inner().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(seen[1].addon(), "Bagnon");
+    assert_eq!(
+        seen[1],
+        Event::DownloadProgress {
+            addon: "Bagnon".into(),
+            bytes: 1024,
+            total: Some(1024),
+        }
+    );
+}
+
+#[test]
+fn display_describes_step() {
+    let event = Event::SwappingIn {
+        addon: "Bagnon".into(),
+        dirs: vec!["Bagnon".into(), "Bagnon_Config".into()],
+    };
+    assert_eq!(
+        event.to_string(),
+        "swapping in Bagnon (Bagnon, Bagnon_Config)"
+    );
+}
@@ -5,13 +5,17 @@
 
 use std::{
     fs,
-    io::{Read, Write},
+    io::{self, Read},
     path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 use zip::ZipArchive;
 
-use crate::toc::TocFile;
+use crate::{
+    model::Flavor,
+    toc::{self, TocFile},
+};
 
 #[cfg(test)]
 mod tests;
@@ -20,6 +24,35 @@ mod tests;
 // Installed-addon inventory
 // ---------------------------------------------------------------------------
 
+/// How an addon's `## Interface` relates to a client flavor, as the in-game
+/// addon list reports it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    /// Targets the flavor's interface range.
+    Compatible,
+    /// Targets only older clients, or declares no interface; the game loads it
+    /// only with "Load out of date AddOns".
+    OutOfDate,
+    /// Targets only newer or other clients; the game never loads it.
+    Incompatible,
+}
+
+impl Compatibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Compatible => "compatible",
+            Self::OutOfDate => "out of date",
+            Self::Incompatible => "incompatible",
+        }
+    }
+}
+
+impl std::fmt::Display for Compatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// An addon directory under `Interface/AddOns` that contains at least one `.toc` file.
 #[derive(Debug, Clone)]
 pub struct InstalledAddon {
@@ -43,6 +76,11 @@ impl InstalledAddon {
         self.toc_files.iter().find_map(|t| t.version.as_deref())
     }
 
+    /// Returns the `## X-*` field `key` from the first `.toc` that sets it.
+    pub fn x_field(&self, key: &str) -> Option<&str> {
+        self.toc_files.iter().find_map(|t| t.x_field(key))
+    }
+
     /// Returns all unique interface version numbers across all `.toc` files, sorted ascending.
     pub fn all_interface_versions(&self) -> Vec<u32> {
         let mut versions: Vec<u32> = self
@@ -54,6 +92,85 @@ impl InstalledAddon {
         versions.dedup();
         versions
     }
+
+    /// Returns the `.toc` a `flavor` client loads for this folder: the first
+    /// `Folder_<Suffix>.toc` / `Folder-<Suffix>.toc` in [`Flavor::client_suffixes`]
+    /// order, else the unsuffixed `Folder.toc`. `None` when the client loads none,
+    /// e.g. a folder shipping only `Folder_Mainline.toc` on a Classic client.
+    pub fn toc_for(&self, flavor: &Flavor) -> Option<&TocFile> {
+        let names: Vec<(&TocFile, Option<&str>)> = self
+            .toc_files
+            .iter()
+            .filter_map(|t| {
+                let file_name = t.path.file_name()?.to_str()?;
+                toc::addon_name(file_name)
+                    .filter(|name| name.eq_ignore_ascii_case(&self.folder))
+                    .map(|_| (t, toc::client_suffix(file_name)))
+            })
+            .collect();
+        flavor
+            .client_suffixes()
+            .iter()
+            .find_map(|wanted| {
+                names
+                    .iter()
+                    .find(|(_, suffix)| suffix.is_some_and(|s| s.eq_ignore_ascii_case(wanted)))
+            })
+            .or_else(|| names.iter().find(|(_, suffix)| suffix.is_none()))
+            .map(|(t, _)| *t)
+    }
+
+    /// Title from the `.toc` a `flavor` client loads, else [`Self::display_title`].
+    pub fn display_title_for(&self, flavor: &Flavor) -> &str {
+        self.toc_for(flavor)
+            .and_then(|t| t.title.as_deref())
+            .unwrap_or_else(|| self.display_title())
+    }
+
+    /// Version from the `.toc` a `flavor` client loads, else [`Self::version`].
+    pub fn version_for(&self, flavor: &Flavor) -> Option<&str> {
+        self.toc_for(flavor)
+            .and_then(|t| t.version.as_deref())
+            .or_else(|| self.version())
+    }
+
+    /// Returns the interface numbers a `flavor` client reads from the `.toc` it
+    /// loads (see [`TocFile::interface_for`]), sorted ascending.
+    pub fn interfaces_for(&self, flavor: &Flavor) -> Vec<u32> {
+        let mut versions = self
+            .toc_for(flavor)
+            .map_or_else(Vec::new, |t| t.interface_for(flavor).to_vec());
+        versions.sort_unstable();
+        versions.dedup();
+        versions
+    }
+
+    /// Checks the addon against a `flavor` client: compatible when any interface
+    /// it declares for the flavor falls in [`Flavor::interface_range`], out of
+    /// date when one is older (or none is declared), incompatible otherwise or
+    /// when the client loads none of its `.toc` files.
+    ///
+    /// With the client's exact `client_interface` (see [`crate::client`]) the
+    /// range starts at its minor patch instead, as in game: `110200` is current
+    /// on a `110205` client, `110105` is out of date.
+    pub fn compatibility(&self, flavor: &Flavor, client_interface: Option<u32>) -> Compatibility {
+        if self.toc_for(flavor).is_none() {
+            return Compatibility::Incompatible;
+        }
+        let range = flavor.interface_range();
+        let (start, end) = match client_interface {
+            Some(client) => (client / 100 * 100, (*range.end()).max(client)),
+            None => (*range.start(), *range.end()),
+        };
+        let interfaces = self.interfaces_for(flavor);
+        if interfaces.iter().any(|i| (start..=end).contains(i)) {
+            Compatibility::Compatible
+        } else if interfaces.is_empty() || interfaces.iter().any(|&i| i < start) {
+            Compatibility::OutOfDate
+        } else {
+            Compatibility::Incompatible
+        }
+    }
 }
 
 /// Scans `addons_dir` and returns one [`InstalledAddon`] per subdirectory that
@@ -95,18 +212,85 @@ pub fn scan(addons_dir: &Path) -> crate::Result<Vec<InstalledAddon>> {
     Ok(addons)
 }
 
+/// One regular file inside an addon directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddonFile {
+    /// Path relative to `Interface/AddOns`, e.g. `WeakAuras/WeakAuras.toc`.
+    pub path: PathBuf,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+/// Lists every regular file under `addons_path/<folder>`, sorted by path.
+///
+/// Returns [`crate::Error::Io`] with `NotFound` if the folder does not exist.
+/// Symlinks are reported as files with the size of the link itself.
+pub fn addon_files(addons_path: &Path, folder: &str) -> crate::Result<Vec<AddonFile>> {
+    let mut files = Vec::new();
+    collect_files(addons_path, &addons_path.join(folder), &mut files)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn collect_files(base: &Path, dir: &Path, files: &mut Vec<AddonFile>) -> crate::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let meta = fs::symlink_metadata(&path)?;
+        if meta.is_dir() {
+            collect_files(base, &path, files)?;
+        } else {
+            files.push(AddonFile {
+                path: path.strip_prefix(base).unwrap_or(&path).to_path_buf(),
+                size: meta.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Zip extraction
 // ---------------------------------------------------------------------------
 
-/// Extracts `zip_path` into `dest` and returns the paths of top-level directories
-/// that contain at least one `.toc` file (the installable addon directories).
+/// Extracts `zip_path` into `dest` and returns the installable addon directories
+/// (see [`locate_addon_dirs`] for the layouts recognised).
 ///
 /// `dest` must already exist. The returned paths are absolute and sorted.
-pub fn extract_addon_zip(zip_path: &Path, dest: &Path) -> crate::Result<Vec<PathBuf>> {
+pub fn extract_addon_zip(
+    zip_path: &Path,
+    dest: &Path,
+    max_unpacked: Option<u64>,
+) -> crate::Result<Vec<PathBuf>> {
+    extract_zip(zip_path, dest, max_unpacked)?;
+    locate_addon_dirs(dest)
+}
+
+/// Extracts every entry of `zip_path` into `dest` (which must already exist).
+///
+/// Entries are streamed to disk. When `max_unpacked` is set, an archive whose
+/// declared sizes add up past it is rejected before anything is written, and
+/// extraction stops with [`crate::Error::UnpackedTooLarge`] should the actual
+/// output cross it anyway (a lying header).
+pub fn extract_zip(zip_path: &Path, dest: &Path, max_unpacked: Option<u64>) -> crate::Result<()> {
     let file = fs::File::open(zip_path)?;
     let mut archive = ZipArchive::new(file)?;
 
+    let too_large = |limit| crate::Error::UnpackedTooLarge {
+        path: zip_path.to_path_buf(),
+        limit,
+    };
+    if let Some(limit) = max_unpacked {
+        let mut declared = 0u64;
+        for i in 0..archive.len() {
+            declared = declared.saturating_add(archive.by_index_raw(i)?.size());
+        }
+        if declared > limit {
+            return Err(too_large(limit));
+        }
+    }
+
+    let mut unpacked = 0u64;
     for i in 0..archive.len() {
         let mut entry = archive.by_index(i)?;
         let out_path = match entry.enclosed_name() {
@@ -120,54 +304,90 @@ pub fn extract_addon_zip(zip_path: &Path, dest: &Path) -> crate::Result<Vec<Path
                 fs::create_dir_all(parent)?;
             }
             let mut out_file = fs::File::create(&out_path)?;
-            let mut buf = Vec::new();
-            entry.read_to_end(&mut buf)?;
-            out_file.write_all(&buf)?;
+            // Allow one byte past the remaining budget so an overrun is detectable.
+            let budget = max_unpacked.map_or(u64::MAX, |limit| {
+                limit.saturating_sub(unpacked).saturating_add(1)
+            });
+            unpacked += io::copy(&mut (&mut entry).take(budget), &mut out_file)?;
+            if let Some(limit) = max_unpacked
+                && unpacked > limit
+            {
+                return Err(too_large(limit));
+            }
         }
     }
 
-    let mut addon_dirs = Vec::new();
-    for entry in fs::read_dir(dest)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() && dir_has_toc(&path) {
-            addon_dirs.push(path);
+    Ok(())
+}
+
+/// Returns the addon directories inside an extracted archive at `root`.
+///
+/// Recognises:
+/// - addon dirs at the top level (CurseForge / WoWInterface / release assets);
+/// - a single top-level dir that is itself an addon but named differently from
+///   its `.toc`, as in GitHub's `owner-repo-<sha>/` zipballs of a repo-root addon —
+///   it is renamed in place to the `.toc` basename, the name the client expects;
+/// - a single top-level wrapper dir holding the addon dirs one level down.
+///
+/// Returns an empty list when none apply. Paths are absolute and sorted.
+pub fn locate_addon_dirs(root: &Path) -> crate::Result<Vec<PathBuf>> {
+    let subdirs = child_dirs(root)?;
+
+    let [only] = subdirs.as_slice() else {
+        return Ok(subdirs.into_iter().filter(|d| dir_has_toc(d)).collect());
+    };
+
+    let names = toc_addon_names(only);
+    let folder = only
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    match names.first() {
+        None => Ok(child_dirs(only)?
+            .into_iter()
+            .filter(|d| dir_has_toc(d))
+            .collect()),
+        Some(_) if names.iter().any(|n| n == folder) => Ok(subdirs),
+        Some(name) => {
+            let renamed = root.join(name);
+            fs::rename(only, &renamed)?;
+            tracing::debug!(from = %folder, to = %name, "renamed repo-root addon dir");
+            Ok(vec![renamed])
         }
     }
-    addon_dirs.sort();
-    Ok(addon_dirs)
 }
 
 // ---------------------------------------------------------------------------
 // Addon dir install / remove
 // ---------------------------------------------------------------------------
 
-/// Copies each directory in `addon_dirs` into `addons_path`, replacing any
+/// Installs each directory in `addon_dirs` into `addons_path`, replacing any
 /// existing directory with the same name. Returns the installed directory names.
 ///
-/// `addons_path` is created if it does not already exist.
+/// The swap is all-or-nothing (§5.1): every dir is first copied into a staging
+/// dir next to `addons_path` (same filesystem, so renames are atomic), then the
+/// existing dirs are renamed aside and the staged dirs renamed in. If any rename
+/// fails, the dirs already swapped in are removed and the old dirs restored before
+/// the error is returned. `addons_path` is created if it does not already exist.
+///
+/// Staging dirs left behind by a process that died mid-swap are deleted first.
 pub fn install_addon_dirs(
     addon_dirs: &[PathBuf],
     addons_path: &Path,
 ) -> crate::Result<Vec<String>> {
     fs::create_dir_all(addons_path)?;
+    sweep_stale_swap_dirs(addons_path);
 
-    let mut installed = Vec::with_capacity(addon_dirs.len());
-    for dir in addon_dirs {
-        let name = dir
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or_default()
-            .to_owned();
-        let dest = addons_path.join(&name);
-        if dest.exists() {
-            fs::remove_dir_all(&dest)?;
-        }
-        copy_dir_all(dir, &dest)?;
-        tracing::debug!(dir = %name, "installed addon dir");
-        installed.push(name);
+    let staging = swap_staging_dir(addons_path);
+    let staged_root = staging.join("new");
+    fs::create_dir_all(&staged_root)?;
+
+    let result = stage_and_swap(addon_dirs, addons_path, &staging, &staged_root);
+
+    if let Err(e) = fs::remove_dir_all(&staging) {
+        tracing::debug!(error = %e, path = %staging.display(), "failed to clean swap staging dir");
     }
-    Ok(installed)
+    result
 }
 
 /// Removes each directory name in `dir_names` from `addons_path`, silently
@@ -192,12 +412,218 @@ pub fn remove_addon_dirs(dir_names: &[String], addons_path: &Path) -> crate::Res
 /// Returns `true` if `dir` contains at least one `.toc` file at its top level.
 fn dir_has_toc(dir: &Path) -> bool {
     fs::read_dir(dir)
-        .map(|entries| {
+        .map(|entries| entries.flatten().any(|e| is_toc_file(&e.path())))
+        .unwrap_or(false)
+}
+
+/// Whether `path` has a `.toc` extension, in any case (`.TOC` ships too).
+fn is_toc_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|x| x.to_str())
+        .is_some_and(|x| x.eq_ignore_ascii_case("toc"))
+}
+
+/// Joins `parts` onto `base`, matching each against what is on disk
+/// case-insensitively, the way Wine resolves paths for the client: `Interface/AddOns`
+/// finds an existing `interface/addons`. An exact match wins, and from the first
+/// part with no match on, the rest are joined as given.
+pub fn join_ignore_case(base: &Path, parts: &[&str]) -> PathBuf {
+    let mut path = base.to_path_buf();
+    let mut parts = parts.iter();
+    for part in parts.by_ref() {
+        let exact = path.join(part);
+        if exact.exists() {
+            path = exact;
+            continue;
+        }
+        let found = fs::read_dir(&path).ok().and_then(|entries| {
             entries
                 .flatten()
-                .any(|e| e.path().extension().and_then(|x| x.to_str()) == Some("toc"))
+                .map(|e| e.file_name())
+                .filter(|n| n.to_string_lossy().eq_ignore_ascii_case(part))
+                .min()
+        });
+        match found {
+            Some(name) => path.push(name),
+            None => {
+                path = exact;
+                break;
+            }
+        }
+    }
+    path.extend(parts);
+    path
+}
+
+/// Returns the sorted subdirectories of `dir`.
+pub(crate) fn child_dirs(dir: &Path) -> crate::Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Returns the sorted, deduplicated addon names implied by the `.toc` files
+/// directly inside `dir` (see [`crate::toc::addon_name`]).
+pub(crate) fn toc_addon_names(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| {
+            let file_name = e.file_name();
+            crate::toc::addon_name(file_name.to_str()?).map(str::to_owned)
         })
-        .unwrap_or(false)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Prefix of the staging dirs [`install_addon_dirs`] swaps through.
+const SWAP_DIR_PREFIX: &str = ".wau-swap-";
+
+/// Returns a fresh staging path beside `addons_path`, e.g. `Interface/.wau-swap-<pid>-<n>`.
+///
+/// Falls back to a hidden dir inside `addons_path` when it has no parent. The dir
+/// has no `.toc` at its top level, so [`scan`] never reports it either way.
+fn swap_staging_dir(addons_path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    swap_base(addons_path).join(format!("{SWAP_DIR_PREFIX}{}-{n}", std::process::id()))
+}
+
+/// The dir [`swap_staging_dir`] creates staging dirs in.
+fn swap_base(addons_path: &Path) -> &Path {
+    match addons_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => addons_path,
+    }
+}
+
+/// Deletes the staging dirs beside `addons_path` whose process no longer runs,
+/// i.e. those a crash between staging and swap left behind. Best-effort.
+fn sweep_stale_swap_dirs(addons_path: &Path) {
+    let base = swap_base(addons_path);
+    let Ok(entries) = fs::read_dir(base) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(pid) = name
+            .to_str()
+            .and_then(|n| n.strip_prefix(SWAP_DIR_PREFIX))
+            .and_then(|rest| rest.split_once('-'))
+            .and_then(|(pid, _)| pid.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if pid == std::process::id() || process_alive(pid) {
+            continue;
+        }
+        let path = entry.path();
+        match fs::remove_dir_all(&path) {
+            Ok(()) => tracing::debug!(path = %path.display(), "removed stale swap dir"),
+            Err(e) => {
+                tracing::warn!(error = %e, path = %path.display(), "failed to remove stale swap dir");
+            }
+        }
+    }
+}
+
+/// Whether process `pid` still runs. Only Linux can tell; elsewhere every
+/// process counts as alive, so no staging dir is swept.
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+/// Copies `addon_dirs` under `staged_root`, then swaps them into `addons_path`.
+fn stage_and_swap(
+    addon_dirs: &[PathBuf],
+    addons_path: &Path,
+    staging: &Path,
+    staged_root: &Path,
+) -> crate::Result<Vec<String>> {
+    let mut moves = Vec::with_capacity(addon_dirs.len());
+    for dir in addon_dirs {
+        let name = dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_owned();
+        let staged = staged_root.join(&name);
+        copy_dir_all(dir, &staged)?;
+        moves.push((name, staged));
+    }
+
+    swap_in(&moves, addons_path, &staging.join("old"))?;
+    Ok(moves.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Renames each staged dir in `moves` to `addons_path/<name>`, moving any existing
+/// dir of that name into `aside_dir` first. Rolls back every completed step on failure.
+fn swap_in(moves: &[(String, PathBuf)], addons_path: &Path, aside_dir: &Path) -> crate::Result<()> {
+    fs::create_dir_all(aside_dir)?;
+
+    // (dest, Some(aside path) if an old dir was moved away)
+    let mut done: Vec<(PathBuf, Option<PathBuf>)> = Vec::with_capacity(moves.len());
+    for (name, staged) in moves {
+        let dest = addons_path.join(name);
+        let aside = aside_dir.join(name);
+
+        let moved_aside = if dest.exists() {
+            if let Err(e) = fs::rename(&dest, &aside) {
+                rollback_swap(&done);
+                return Err(e.into());
+            }
+            Some(aside)
+        } else {
+            None
+        };
+
+        if let Err(e) = fs::rename(staged, &dest) {
+            if let Some(aside) = &moved_aside
+                && let Err(re) = fs::rename(aside, &dest)
+            {
+                tracing::warn!(error = %re, dir = %dest.display(), "failed to restore addon dir");
+            }
+            rollback_swap(&done);
+            return Err(e.into());
+        }
+        tracing::debug!(dir = %name, "installed addon dir");
+        done.push((dest, moved_aside));
+    }
+    Ok(())
+}
+
+/// Undoes completed swaps in reverse order: drops the new dir, restores the old one.
+fn rollback_swap(done: &[(PathBuf, Option<PathBuf>)]) {
+    for (dest, aside) in done.iter().rev() {
+        if let Err(e) = fs::remove_dir_all(dest) {
+            tracing::warn!(error = %e, dir = %dest.display(), "failed to remove swapped-in dir");
+            continue;
+        }
+        if let Some(aside) = aside {
+            match fs::rename(aside, dest) {
+                Ok(()) => tracing::debug!(dir = %dest.display(), "restored addon dir"),
+                Err(e) => {
+                    tracing::warn!(error = %e, dir = %dest.display(), "failed to restore addon dir");
+                }
+            }
+        }
+    }
 }
 
 /// Recursively copies the `src` directory tree into `dst`.
@@ -223,7 +649,7 @@ fn copy_dir_all(src: &Path, dst: &Path) -> crate::Result<()> {
 /// Entries ending with `/` are added as directory entries.
 #[cfg(test)]
 pub(crate) fn make_test_zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
-    use std::io::Cursor;
+    use std::io::{Cursor, Write};
     use zip::{ZipWriter, write::FileOptions};
     let buf = Cursor::new(Vec::new());
     let mut zip = ZipWriter::new(buf);
@@ -253,7 +679,7 @@ fn collect_toc_files(addon_dir: &Path) -> Vec<TocFile> {
         .flatten()
         .filter_map(|e| {
             let p = e.path();
-            if p.extension().and_then(|x| x.to_str()) == Some("toc") {
+            if is_toc_file(&p) {
                 crate::toc::parse(&p)
             } else {
                 None
@@ -1,5 +1,5 @@
 use super::*;
-use crate::toc::TocFile;
+use crate::{model::Flavor, toc::TocFile};
 
 // ---------------------------------------------------------------------------
 // extract_addon_zip
@@ -27,7 +27,7 @@ fn extract_addon_zip_finds_addon_dirs() {
     );
     let extract_dir = dir.path().join("extracted");
     std::fs::create_dir_all(&extract_dir).unwrap();
-    let addon_dirs = extract_addon_zip(&zip_path, &extract_dir).unwrap();
+    let addon_dirs = extract_addon_zip(&zip_path, &extract_dir, None).unwrap();
     let names: Vec<&str> = addon_dirs
         .iter()
         .map(|p| p.file_name().unwrap().to_str().unwrap())
@@ -48,10 +48,176 @@ fn extract_addon_zip_skips_dirs_without_toc() {
     );
     let extract_dir = dir.path().join("extracted");
     std::fs::create_dir_all(&extract_dir).unwrap();
-    let addon_dirs = extract_addon_zip(&zip_path, &extract_dir).unwrap();
+    let addon_dirs = extract_addon_zip(&zip_path, &extract_dir, None).unwrap();
     assert_eq!(addon_dirs.len(), 1);
 }
 
+fn extract_names(entries: &[(&str, &[u8])]) -> (tempfile::TempDir, Vec<String>) {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = write_zip(dir.path(), entries);
+    let extract_dir = dir.path().join("extracted");
+    std::fs::create_dir_all(&extract_dir).unwrap();
+    let names = extract_addon_zip(&zip_path, &extract_dir, None)
+        .unwrap()
+        .iter()
+        .map(|p| p.file_name().unwrap().to_str().unwrap().to_owned())
+        .collect();
+    (dir, names)
+}
+
+#[test]
+fn extract_addon_zip_renames_repo_root_addon_to_toc_basename() {
+    let (dir, names) = extract_names(&[
+        ("owner-MyAddon-abc1234/", &[]),
+        (
+            "owner-MyAddon-abc1234/MyAddon.toc",
+            b"## Interface: 110200\n",
+        ),
+        (
+            "owner-MyAddon-abc1234/MyAddon_Vanilla.toc",
+            b"## Interface: 11507\n",
+        ),
+        ("owner-MyAddon-abc1234/core.lua", b""),
+    ]);
+    assert_eq!(names, vec!["MyAddon"]);
+    let extracted = dir.path().join("extracted");
+    assert!(extracted.join("MyAddon/core.lua").exists());
+    assert!(!extracted.join("owner-MyAddon-abc1234").exists());
+}
+
+#[test]
+fn extract_addon_zip_finds_addons_one_level_inside_wrapper() {
+    let (_dir, names) = extract_names(&[
+        ("owner-suite-abc1234/README.md", b"# suite"),
+        (
+            "owner-suite-abc1234/Suite/Suite.toc",
+            b"## Interface: 110200\n",
+        ),
+        (
+            "owner-suite-abc1234/Suite_Options/Suite_Options.toc",
+            b"## Interface: 110200\n",
+        ),
+        ("owner-suite-abc1234/docs/index.md", b""),
+    ]);
+    assert_eq!(names, vec!["Suite", "Suite_Options"]);
+}
+
+#[test]
+fn extract_addon_zip_prefers_top_level_addons_over_wrapper_detection() {
+    let (_dir, names) = extract_names(&[
+        ("Solo/Solo.toc", b"## Interface: 110200\n"),
+        ("Solo/Libs/Lib/Lib.toc", b"## Interface: 110200\n"),
+    ]);
+    assert_eq!(names, vec!["Solo"]);
+}
+
+#[test]
+fn extract_addon_zip_returns_nothing_for_wrapper_without_addons() {
+    let (_dir, names) = extract_names(&[("owner-repo-abc1234/src/main.lua", b"")]);
+    assert!(names.is_empty());
+}
+
+#[test]
+fn extract_addon_zip_streams_large_entries_intact() {
+    let dir = tempfile::tempdir().unwrap();
+    let blob: Vec<u8> = (0..256 * 1024).map(|i| (i % 251) as u8).collect();
+    let zip_path = write_zip(
+        dir.path(),
+        &[
+            ("Big/Big.toc", b"## Interface: 110200\n"),
+            ("Big/media/voice.ogg", &blob),
+        ],
+    );
+    let extract_dir = dir.path().join("extracted");
+    std::fs::create_dir_all(&extract_dir).unwrap();
+    extract_addon_zip(&zip_path, &extract_dir, Some(1024 * 1024)).unwrap();
+    assert_eq!(
+        std::fs::read(extract_dir.join("Big/media/voice.ogg")).unwrap(),
+        blob
+    );
+}
+
+#[test]
+fn extract_addon_zip_rejects_archive_over_unpacked_limit() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = write_zip(
+        dir.path(),
+        &[
+            ("Bomb/Bomb.toc", b"## Interface: 110200\n"),
+            ("Bomb/payload.bin", &[0u8; 4096]),
+        ],
+    );
+    let extract_dir = dir.path().join("extracted");
+    std::fs::create_dir_all(&extract_dir).unwrap();
+    let err = extract_addon_zip(&zip_path, &extract_dir, Some(1024)).unwrap_err();
+    assert!(matches!(
+        err,
+        crate::Error::UnpackedTooLarge { limit: 1024, .. }
+    ));
+    assert!(
+        !extract_dir.join("Bomb").exists(),
+        "nothing is written once declared sizes exceed the limit"
+    );
+}
+
+#[test]
+fn extract_addon_zip_writes_files_intact_under_a_u64_max_limit() {
+    // `Limits::to_bytes` saturates large MiB caps to `u64::MAX`.
+    let dir = tempfile::tempdir().unwrap();
+    let payload = [7u8; 4096];
+    let zip_path = write_zip(
+        dir.path(),
+        &[
+            ("Big/Big.toc", b"## Interface: 110200\n"),
+            ("Big/payload.bin", &payload),
+        ],
+    );
+    let extract_dir = dir.path().join("extracted");
+    std::fs::create_dir_all(&extract_dir).unwrap();
+    extract_addon_zip(&zip_path, &extract_dir, Some(u64::MAX)).unwrap();
+    assert_eq!(
+        std::fs::read(extract_dir.join("Big/payload.bin")).unwrap(),
+        payload
+    );
+}
+
+// ---------------------------------------------------------------------------
+// addon_files
+// ---------------------------------------------------------------------------
+
+#[test]
+fn addon_files_lists_nested_files_with_sizes() {
+    let dir = tempfile::tempdir().unwrap();
+    let addon = dir.path().join("MyAddon");
+    std::fs::create_dir_all(addon.join("Libs/LibStub")).unwrap();
+    std::fs::write(addon.join("MyAddon.toc"), b"## Interface: 110200\n").unwrap();
+    std::fs::write(addon.join("Libs/LibStub/LibStub.lua"), b"-- 8 bytes").unwrap();
+
+    let files = addon_files(dir.path(), "MyAddon").unwrap();
+    assert_eq!(
+        files,
+        vec![
+            AddonFile {
+                path: PathBuf::from("MyAddon/Libs/LibStub/LibStub.lua"),
+                size: 10,
+            },
+            AddonFile {
+                path: PathBuf::from("MyAddon/MyAddon.toc"),
+                size: 21,
+            },
+        ]
+    );
+}
+
+#[test]
+fn addon_files_errors_for_missing_folder() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(matches!(
+        addon_files(dir.path(), "Nope"),
+        Err(crate::Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound
+    ));
+}
+
 // ---------------------------------------------------------------------------
 // install_addon_dirs / remove_addon_dirs
 // ---------------------------------------------------------------------------
@@ -77,6 +243,83 @@ fn install_addon_dirs_copies_and_replaces() {
     assert!(addons.join("TestAddon").join("new.lua").exists());
 }
 
+#[test]
+fn install_addon_dirs_leaves_no_staging_behind() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    let addons = dir.path().join("AddOns");
+
+    let addon_src = src.join("TestAddon");
+    std::fs::create_dir_all(&addon_src).unwrap();
+    std::fs::write(addon_src.join("TestAddon.toc"), b"## Interface: 110200\n").unwrap();
+
+    install_addon_dirs(std::slice::from_ref(&addon_src), &addons).unwrap();
+    install_addon_dirs(std::slice::from_ref(&addon_src), &addons).unwrap();
+
+    let mut names: Vec<String> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["AddOns", "src"]);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn install_addon_dirs_sweeps_swap_dirs_of_dead_processes() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    let addons = dir.path().join("AddOns");
+    let addon_src = src.join("TestAddon");
+    std::fs::create_dir_all(&addon_src).unwrap();
+    std::fs::write(addon_src.join("TestAddon.toc"), b"## Interface: 110200\n").unwrap();
+
+    // Above the kernel's pid limit, so never a live process.
+    let stale = dir.path().join(".wau-swap-999999999-0");
+    std::fs::create_dir_all(stale.join("old/TestAddon")).unwrap();
+    let live = dir
+        .path()
+        .join(format!(".wau-swap-{}-999999", std::process::id()));
+    std::fs::create_dir_all(&live).unwrap();
+    let unrelated = dir.path().join(".wau-swap-notes");
+    std::fs::create_dir_all(&unrelated).unwrap();
+
+    install_addon_dirs(std::slice::from_ref(&addon_src), &addons).unwrap();
+    assert!(!stale.exists());
+    assert!(live.exists());
+    assert!(unrelated.exists());
+}
+
+#[test]
+fn swap_in_restores_old_dirs_when_a_later_dir_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let addons = dir.path().join("AddOns");
+    let staged = dir.path().join("staged");
+
+    // Existing install of a two-folder addon.
+    for name in ["Core", "Options"] {
+        std::fs::create_dir_all(addons.join(name)).unwrap();
+        std::fs::write(addons.join(name).join("old.lua"), b"").unwrap();
+    }
+    // Only the first folder is staged; the second rename will fail.
+    std::fs::create_dir_all(staged.join("Core")).unwrap();
+    std::fs::write(staged.join("Core").join("new.lua"), b"").unwrap();
+
+    let moves = vec![
+        ("Core".to_owned(), staged.join("Core")),
+        ("Options".to_owned(), staged.join("Options")),
+    ];
+    assert!(swap_in(&moves, &addons, &dir.path().join("aside")).is_err());
+
+    for name in ["Core", "Options"] {
+        assert!(
+            addons.join(name).join("old.lua").exists(),
+            "{name} restored"
+        );
+    }
+    assert!(!addons.join("Core").join("new.lua").exists());
+}
+
 #[test]
 fn remove_addon_dirs_deletes_dirs() {
     let dir = tempfile::tempdir().unwrap();
@@ -155,6 +398,124 @@ fn all_interface_versions_deduped_sorted() {
     assert_eq!(addon.all_interface_versions(), vec![11508, 50503, 110200]);
 }
 
+fn toc_at(file_name: &str, version: &str, interface: Vec<u32>) -> TocFile {
+    TocFile {
+        path: PathBuf::from(format!("x/{file_name}")),
+        ..make_toc(Some(file_name), Some(version), interface)
+    }
+}
+
+#[test]
+fn toc_for_follows_client_suffix_precedence() {
+    let addon = InstalledAddon {
+        folder: "x".into(),
+        toc_files: vec![
+            toc_at("x.toc", "base", vec![110207]),
+            toc_at("x-Classic.toc", "classic", vec![50503]),
+            toc_at("x_Mainline.toc", "mainline", vec![120001]),
+            toc_at("x_Vanilla.toc", "vanilla", vec![11508]),
+            // Named after another folder: never loaded from here.
+            toc_at("y_Cata.toc", "other", vec![40402]),
+        ],
+    };
+
+    assert_eq!(addon.version_for(&Flavor::Retail), Some("mainline"));
+    assert_eq!(addon.version_for(&Flavor::Era), Some("vanilla"));
+    assert_eq!(addon.version_for(&Flavor::Cata), Some("classic"));
+    assert_eq!(addon.display_title_for(&Flavor::Wod), "x.toc");
+    assert_eq!(addon.interfaces_for(&Flavor::Mop), vec![50503]);
+    assert_eq!(addon.version(), Some("base"));
+}
+
+#[test]
+fn toc_for_is_none_when_the_client_loads_no_toc() {
+    let addon = InstalledAddon {
+        folder: "x".into(),
+        toc_files: vec![toc_at("x_Mainline.toc", "1", vec![120001])],
+    };
+    assert!(addon.toc_for(&Flavor::Era).is_none());
+    assert_eq!(
+        addon.compatibility(&Flavor::Era, None),
+        Compatibility::Incompatible
+    );
+    assert_eq!(addon.version_for(&Flavor::Era), Some("1"));
+}
+
+#[test]
+fn compatibility_follows_the_in_game_addon_list() {
+    let addon = |interface: Vec<u32>| InstalledAddon {
+        folder: "x".into(),
+        toc_files: vec![toc_at("x.toc", "1", interface)],
+    };
+    let multi = addon(vec![120001, 50503, 11508]);
+    assert_eq!(
+        multi.compatibility(&Flavor::Retail, None),
+        Compatibility::Compatible
+    );
+    assert_eq!(
+        multi.compatibility(&Flavor::Mop, None),
+        Compatibility::Compatible
+    );
+    assert_eq!(
+        multi.compatibility(&Flavor::Cata, None),
+        Compatibility::OutOfDate
+    );
+
+    let tww = addon(vec![110207]);
+    assert_eq!(
+        tww.compatibility(&Flavor::Retail, None),
+        Compatibility::OutOfDate
+    );
+    assert_eq!(
+        tww.compatibility(&Flavor::Era, None),
+        Compatibility::Incompatible
+    );
+    assert_eq!(
+        addon(vec![]).compatibility(&Flavor::Era, None),
+        Compatibility::OutOfDate
+    );
+}
+
+#[test]
+fn compatibility_uses_the_exact_client_interface_when_known() {
+    let addon = |interface: Vec<u32>| InstalledAddon {
+        folder: "x".into(),
+        toc_files: vec![toc_at("x.toc", "1", interface)],
+    };
+    let client = Some(110205);
+    assert_eq!(
+        addon(vec![110200]).compatibility(&Flavor::Retail, client),
+        Compatibility::Compatible
+    );
+    assert_eq!(
+        addon(vec![110105]).compatibility(&Flavor::Retail, client),
+        Compatibility::OutOfDate
+    );
+    assert_eq!(
+        addon(vec![11404]).compatibility(&Flavor::Era, Some(11507)),
+        Compatibility::OutOfDate
+    );
+}
+
+#[test]
+fn compatibility_prefers_flavor_specific_interface_keys() {
+    let mut toc = toc_at("x.toc", "1", vec![120001]);
+    toc.interface_flavor.insert("Vanilla".into(), vec![11508]);
+    toc.interface_flavor.insert("classic".into(), vec![50503]);
+    let addon = InstalledAddon {
+        folder: "x".into(),
+        toc_files: vec![toc],
+    };
+
+    assert_eq!(addon.interfaces_for(&Flavor::Era), vec![11508]);
+    assert_eq!(addon.interfaces_for(&Flavor::Mop), vec![50503]);
+    assert_eq!(addon.interfaces_for(&Flavor::Retail), vec![120001]);
+    assert_eq!(
+        addon.compatibility(&Flavor::Tbc, None),
+        Compatibility::Incompatible
+    );
+}
+
 // ---------------------------------------------------------------------------
 // scan
 // ---------------------------------------------------------------------------
@@ -262,3 +623,32 @@ fn scan_toc_contains_full_metadata() {
         Some("9999")
     );
 }
+
+#[test]
+fn join_ignore_case_reuses_existing_casing() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("interface/addons")).unwrap();
+
+    assert_eq!(
+        join_ignore_case(dir.path(), &["Interface", "AddOns"]),
+        dir.path().join("interface/addons")
+    );
+    // Missing parts, and everything after them, are joined as given.
+    assert_eq!(
+        join_ignore_case(dir.path(), &["WTF", "Account"]),
+        dir.path().join("WTF/Account")
+    );
+}
+
+#[test]
+fn scan_reads_upper_case_toc_extensions() {
+    let dir = tempfile::tempdir().unwrap();
+    let addon = dir.path().join("OldAddon");
+    std::fs::create_dir_all(&addon).unwrap();
+    std::fs::write(addon.join("OldAddon.TOC"), "## Version: 1.0\n").unwrap();
+
+    let addons = scan(dir.path()).unwrap();
+    assert_eq!(addons.len(), 1);
+    assert_eq!(addons[0].folder, "OldAddon");
+    assert_eq!(addons[0].toc_files.len(), 1);
+}
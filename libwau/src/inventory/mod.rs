@@ -0,0 +1,292 @@
+//! Groups the folders [`crate::fs::scan`] finds into logical addons.
+//!
+//! One addon often ships many folders (DBM-Core plus a folder per raid, Bagnon plus
+//! Bagnon_Config, …). [`group`] clusters them, strongest signal first:
+//!
+//! 1. folders one lock entry installed;
+//! 2. folders sharing a provider ID (`X-Curse-Project-ID`, `X-WoWI-ID`);
+//! 3. a folder whose `## Dependencies` name a folder with the same name stem
+//!    (`DBM-Raids-MoP` → `DBM-Core`);
+//! 4. a `LoadOnDemand` folder and a regular folder with the same name stem
+//!    (`Details_DataStorage` → `Details`);
+//! 5. a folder without a provider ID named `<Main>_…` or `<Main>-…`.
+//!
+//! Two clusters owned by different lock entries, or carrying different provider
+//! IDs, are never merged, so a library bundled by two addons or a separately
+//! published plugin stays on its own. The name stem is the part of a folder name
+//! before its first `-` or `_`.
+
+use crate::{
+    fs::InstalledAddon,
+    lock::Lock,
+    manifest::ManifestAddon,
+    model::{Flavor, Provider},
+    toc::TocFile,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Provider identity read from a folder's `.toc` files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ProviderId {
+    CurseForge(u64),
+    WoWInterface(u64),
+}
+
+impl ProviderId {
+    /// Reads the first supported ID, preferring CurseForge.
+    pub fn of(addon: &InstalledAddon) -> Option<Self> {
+        let id = |key| addon.x_field(key).and_then(|v| v.trim().parse().ok());
+        id("X-Curse-Project-ID")
+            .map(Self::CurseForge)
+            .or_else(|| id("X-WoWI-ID").map(Self::WoWInterface))
+    }
+
+    /// Whether manifest `row` resolves through this ID.
+    pub fn matches(self, row: &ManifestAddon) -> bool {
+        match self {
+            Self::CurseForge(id) => {
+                row.provider == Provider::CurseForge && row.project_id == Some(id)
+            }
+            Self::WoWInterface(id) => {
+                row.provider == Provider::WoWInterface && row.wowi_id == Some(id)
+            }
+        }
+    }
+
+    /// A new manifest row named `name` that resolves through this ID.
+    pub fn row(self, name: &str) -> ManifestAddon {
+        let (provider, project_id, wowi_id) = match self {
+            Self::CurseForge(id) => (Provider::CurseForge, Some(id), None),
+            Self::WoWInterface(id) => (Provider::WoWInterface, None, Some(id)),
+        };
+        ManifestAddon {
+            name: name.to_owned(),
+            provider,
+            channel: None,
+            flavors: None,
+            pin: None,
+            project_id,
+            wowi_id,
+            repo: None,
+            asset_regex: None,
+            git_ref: None,
+            url: None,
+        }
+    }
+}
+
+/// Folders that belong to one logical addon.
+#[derive(Debug, Clone)]
+pub struct AddonGroup {
+    /// The owning lock entry's name, else the main folder's name.
+    pub name: String,
+    /// Main folder first, then the rest alphabetically.
+    pub addons: Vec<InstalledAddon>,
+    /// Name of the lock entry that installed the group, if any.
+    pub locked_by: Option<String>,
+    /// Provider ID any folder's `.toc` names.
+    pub provider_id: Option<ProviderId>,
+}
+
+impl AddonGroup {
+    /// The folder the group is named after.
+    pub fn main(&self) -> &InstalledAddon {
+        &self.addons[0]
+    }
+
+    pub fn folders(&self) -> impl Iterator<Item = &str> {
+        self.addons.iter().map(|a| a.folder.as_str())
+    }
+
+    /// Returns the `## X-*` field `key` from the main folder, else any other folder.
+    pub fn x_field(&self, key: &str) -> Option<&str> {
+        self.addons.iter().find_map(|a| a.x_field(key))
+    }
+}
+
+/// Clusters `scanned` into [`AddonGroup`]s, sorted by name. Dependencies and
+/// `LoadOnDemand` are read from the `.toc` a `flavor` client loads.
+pub fn group(scanned: &[InstalledAddon], lock: &Lock, flavor: &Flavor) -> Vec<AddonGroup> {
+    let ids: Vec<Option<ProviderId>> = scanned.iter().map(ProviderId::of).collect();
+    let mut sets = Clusters::new(scanned, lock, &ids);
+    let index = |folder: &str| scanned.iter().position(|a| a.folder == folder);
+
+    for entry in &lock.addon {
+        let mut dirs = entry.installed_dirs.iter().filter_map(|d| index(d));
+        if let Some(first) = dirs.next() {
+            for other in dirs {
+                sets.union(first, other);
+            }
+        }
+    }
+
+    for (i, id) in ids.iter().enumerate() {
+        if id.is_none() {
+            continue;
+        }
+        if let Some(j) = ids[..i].iter().position(|other| other == id) {
+            sets.union(j, i);
+        }
+    }
+
+    for (i, a) in scanned.iter().enumerate() {
+        let deps = loaded_tocs(a, flavor).flat_map(|t| &t.dependencies);
+        for dep in deps {
+            if let Some(j) = scanned.iter().position(|p| {
+                p.folder.eq_ignore_ascii_case(dep) && stem(&p.folder) == stem(&a.folder)
+            }) {
+                sets.union(j, i);
+            }
+        }
+    }
+
+    for (i, a) in scanned.iter().enumerate() {
+        if !load_on_demand(a, flavor) {
+            continue;
+        }
+        let parent = scanned
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !load_on_demand(p, flavor) && stem(&p.folder) == stem(&a.folder))
+            .min_by_key(|(_, p)| (p.folder.len(), p.folder.as_str()));
+        if let Some((j, _)) = parent {
+            sets.union(j, i);
+        }
+    }
+
+    for (i, a) in scanned.iter().enumerate() {
+        if ids[i].is_some() {
+            continue;
+        }
+        let parent = scanned.iter().position(|p| {
+            a.folder
+                .strip_prefix(p.folder.as_str())
+                .is_some_and(|rest| rest.starts_with(['_', '-']))
+        });
+        if let Some(j) = parent {
+            sets.union(j, i);
+        }
+    }
+
+    sets.into_groups(scanned, flavor)
+}
+
+/// The part of a folder name before its first `-` or `_`.
+fn stem(folder: &str) -> &str {
+    folder.split(['-', '_']).next().unwrap_or(folder)
+}
+
+/// The `.toc` a `flavor` client loads, or every `.toc` when it loads none.
+fn loaded_tocs<'a>(
+    addon: &'a InstalledAddon,
+    flavor: &Flavor,
+) -> impl Iterator<Item = &'a TocFile> {
+    match addon.toc_for(flavor) {
+        Some(toc) => std::slice::from_ref(toc).iter(),
+        None => addon.toc_files.iter(),
+    }
+}
+
+fn load_on_demand(addon: &InstalledAddon, flavor: &Flavor) -> bool {
+    let mut tocs = loaded_tocs(addon, flavor).peekable();
+    tocs.peek().is_some() && tocs.all(|t| t.load_on_demand)
+}
+
+/// Union-find over folder indices; each root carries the lock owner and provider
+/// ID of its cluster so incompatible clusters are never merged.
+struct Clusters {
+    parent: Vec<usize>,
+    owners: Vec<Option<String>>,
+    ids: Vec<Option<ProviderId>>,
+}
+
+impl Clusters {
+    fn new(scanned: &[InstalledAddon], lock: &Lock, ids: &[Option<ProviderId>]) -> Self {
+        Self {
+            parent: (0..scanned.len()).collect(),
+            owners: scanned
+                .iter()
+                .map(|a| lock.owners(&a.folder).first().map(|e| e.name.clone()))
+                .collect(),
+            ids: ids.to_vec(),
+        }
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        self.parent[i] = root;
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        fn compatible<T: PartialEq>(x: &Option<T>, y: &Option<T>) -> bool {
+            x.is_none() || y.is_none() || x == y
+        }
+        if !compatible(&self.owners[a], &self.owners[b]) || !compatible(&self.ids[a], &self.ids[b])
+        {
+            return;
+        }
+        self.parent[b] = a;
+        if self.owners[a].is_none() {
+            self.owners[a] = self.owners[b].take();
+        }
+        if self.ids[a].is_none() {
+            self.ids[a] = self.ids[b];
+        }
+    }
+
+    fn into_groups(mut self, scanned: &[InstalledAddon], flavor: &Flavor) -> Vec<AddonGroup> {
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); scanned.len()];
+        for i in 0..scanned.len() {
+            let root = self.find(i);
+            members[root].push(i);
+        }
+
+        let mut groups: Vec<AddonGroup> = members
+            .into_iter()
+            .enumerate()
+            .filter(|(_, m)| !m.is_empty())
+            .map(|(root, m)| {
+                let mut addons: Vec<InstalledAddon> =
+                    m.iter().map(|&i| scanned[i].clone()).collect();
+                let owner = self.owners[root].clone();
+                let main = main_index(&addons, owner.as_deref(), flavor);
+                let main = addons.remove(main);
+                addons.sort_by(|a, b| a.folder.cmp(&b.folder));
+                addons.insert(0, main);
+                AddonGroup {
+                    name: owner.clone().unwrap_or_else(|| addons[0].folder.clone()),
+                    addons,
+                    locked_by: owner,
+                    provider_id: self.ids[root],
+                }
+            })
+            .collect();
+        groups.sort_by_key(|g| g.name.to_lowercase());
+        groups
+    }
+}
+
+/// Picks the main folder: the one named after the lock entry, else the shortest
+/// regular (not `LoadOnDemand`) folder name.
+fn main_index(addons: &[InstalledAddon], owner: Option<&str>, flavor: &Flavor) -> usize {
+    owner
+        .and_then(|name| addons.iter().position(|a| a.folder == name))
+        .unwrap_or_else(|| {
+            (0..addons.len())
+                .min_by_key(|&i| {
+                    let a = &addons[i];
+                    (load_on_demand(a, flavor), a.folder.len(), a.folder.as_str())
+                })
+                .unwrap_or(0)
+        })
+}
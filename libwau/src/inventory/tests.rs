@@ -0,0 +1,157 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+
+use super::*;
+use crate::{
+    lock::LockedAddon,
+    model::{Channel, Flavor, Provider, Tag},
+    toc::TocFile,
+};
+
+fn on_disk(folder: &str, fields: &[(&str, &str)], deps: &[&str], lod: bool) -> InstalledAddon {
+    InstalledAddon {
+        folder: folder.to_owned(),
+        toc_files: vec![TocFile {
+            path: PathBuf::from(format!("{folder}/{folder}.toc")),
+            x_fields: fields
+                .iter()
+                .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+                .collect(),
+            dependencies: deps.iter().map(|d| (*d).to_owned()).collect(),
+            load_on_demand: lod,
+            ..Default::default()
+        }],
+    }
+}
+
+fn plain(folder: &str) -> InstalledAddon {
+    on_disk(folder, &[], &[], false)
+}
+
+fn locked(name: &str, dirs: &[&str]) -> LockedAddon {
+    LockedAddon {
+        name: name.into(),
+        provider: Provider::CurseForge,
+        flavor: Flavor::Retail,
+        channel: Channel::Stable,
+        project_id: None,
+        resolved_version: "1.0".into(),
+        resolved_id: "1".into(),
+        download_url: String::new(),
+        sha256: None,
+        installed_dirs: dirs.iter().map(|d| (*d).to_owned()).collect(),
+        installed_at: Utc::now(),
+    }
+}
+
+fn empty_lock() -> Lock {
+    Lock::new(Tag::new("test"))
+}
+
+fn layout(groups: &[AddonGroup]) -> Vec<(&str, Vec<&str>)> {
+    groups
+        .iter()
+        .map(|g| (g.name.as_str(), g.folders().collect()))
+        .collect()
+}
+
+#[test]
+fn group_clusters_folders_of_one_lock_entry() {
+    let scanned = [plain("BigWigs"), plain("BigWigs_Core"), plain("LittleWigs")];
+    let mut lock = empty_lock();
+    lock.upsert(locked(
+        "BigWigs Bossmods",
+        &["BigWigs_Core", "BigWigs", "LittleWigs"],
+    ));
+
+    let groups = group(&scanned, &lock, &Flavor::Retail);
+    assert_eq!(
+        layout(&groups),
+        vec![(
+            "BigWigs Bossmods",
+            vec!["BigWigs", "BigWigs_Core", "LittleWigs"]
+        )]
+    );
+    assert_eq!(groups[0].locked_by.as_deref(), Some("BigWigs Bossmods"));
+}
+
+#[test]
+fn group_clusters_shared_ids_dependencies_and_load_on_demand_modules() {
+    let scanned = [
+        on_disk("DBM-Core", &[("X-Curse-Project-ID", "3358")], &[], false),
+        on_disk("DBM-Raids-MoP", &[], &["DBM-Core"], true),
+        on_disk(
+            "DBM-StatusBarTimers",
+            &[("X-Curse-Project-ID", "3358")],
+            &[],
+            false,
+        ),
+        on_disk("Details", &[], &[], false),
+        on_disk("Details_DataStorage", &[], &[], true),
+        // Depends on a library with a different stem: stays on its own.
+        on_disk("WeakAuras", &[], &["LibStub"], false),
+        plain("LibStub"),
+    ];
+
+    let groups = group(&scanned, &empty_lock(), &Flavor::Retail);
+    assert_eq!(
+        layout(&groups),
+        vec![
+            (
+                "DBM-Core",
+                vec!["DBM-Core", "DBM-Raids-MoP", "DBM-StatusBarTimers"]
+            ),
+            ("Details", vec!["Details", "Details_DataStorage"]),
+            ("LibStub", vec!["LibStub"]),
+            ("WeakAuras", vec!["WeakAuras"]),
+        ]
+    );
+    assert_eq!(groups[0].provider_id, Some(ProviderId::CurseForge(3358)));
+}
+
+#[test]
+fn group_attaches_prefixed_folders_without_ids() {
+    let scanned = [
+        on_disk("Bagnon", &[("X-Curse-Project-ID", "1592")], &[], false),
+        plain("Bagnon_Config"),
+        plain("BagnonExtra"),
+        // Published separately: its own ID keeps it apart.
+        on_disk(
+            "Bagnon_Scrap",
+            &[("X-Curse-Project-ID", "99")],
+            &["Bagnon"],
+            false,
+        ),
+    ];
+
+    assert_eq!(
+        layout(&group(&scanned, &empty_lock(), &Flavor::Retail)),
+        vec![
+            ("Bagnon", vec!["Bagnon", "Bagnon_Config"]),
+            ("Bagnon_Scrap", vec!["Bagnon_Scrap"]),
+            ("BagnonExtra", vec!["BagnonExtra"]),
+        ]
+    );
+}
+
+#[test]
+fn group_never_merges_folders_of_different_lock_entries() {
+    let scanned = [
+        on_disk("Plater", &[("X-Curse-Project-ID", "100")], &[], false),
+        on_disk("Plater_Skins", &[("X-Curse-Project-ID", "100")], &[], false),
+        plain("Plater-Extra"),
+    ];
+    let mut lock = empty_lock();
+    lock.upsert(locked("Plater", &["Plater"]));
+    lock.upsert(locked("Plater Skins", &["Plater_Skins"]));
+
+    let groups = group(&scanned, &lock, &Flavor::Retail);
+    assert_eq!(
+        layout(&groups),
+        vec![
+            ("Plater", vec!["Plater", "Plater-Extra"]),
+            ("Plater Skins", vec!["Plater_Skins"]),
+        ]
+    );
+}
@@ -0,0 +1,312 @@
+//! Crash-safe journal for commands that change `Interface/AddOns`.
+//!
+//! `wau sync` and `wau remove` open a journal at `<cache>/journal/<tag>.toml`
+//! before touching the addons dir, append one `[[entry]]` per completed install or
+//! removal, and delete the file when the command ends. A journal that still exists
+//! when the next command starts therefore marks an interrupted run.
+//!
+//! Each entry is appended and fsynced *before* the lock is saved, and carries both
+//! the lock row it replaced and the row it wrote, plus the manifest row a sync
+//! installed from. That is enough to:
+//! - roll forward: replay every entry onto the lock ([`Journal::roll_forward`]);
+//! - roll back: restore each previous row in reverse order ([`crate::ops::revert`]).
+//!
+//! The names of addons that failed during the last sync are kept beside the journal
+//! in `<tag>.failed.toml`, so a follow-up run can retry only those.
+
+use std::{
+    fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Result,
+    lock::{Lock, LockedAddon},
+    manifest::ManifestAddon,
+    model::{Flavor, Provider, Tag},
+};
+
+#[cfg(test)]
+mod tests;
+
+pub const SUPPORTED_SCHEMA: u32 = 1;
+
+/// An open (or recovered) journal for one install tag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Journal {
+    pub schema: u32,
+    pub install_tag: Tag,
+    /// Command that opened the journal, e.g. `"sync"`.
+    pub command: String,
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    #[serde(default)]
+    pub entry: Vec<JournalEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// One completed install, update, or removal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub action: Action,
+    pub name: String,
+    pub flavor: Flavor,
+    pub recorded_at: DateTime<Utc>,
+    /// Lock row before the change; `None` for a fresh install.
+    pub previous: Option<LockedAddon>,
+    /// Lock row after the change; `None` for a removal.
+    pub current: Option<LockedAddon>,
+    /// Manifest row the change was installed from; tells a roll-back how to lay
+    /// the previous artifact out (e.g. a GitHub snapshot's `.pkgmeta`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ManifestAddon>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Install,
+    Remove,
+}
+
+/// How to resolve an interrupted run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    /// Keep every journalled change and bring the lock up to date with it.
+    RollForward,
+    /// Undo every journalled change, newest first.
+    RollBack,
+}
+
+impl fmt::Display for Recovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recovery::RollForward => f.write_str("roll forward"),
+            Recovery::RollBack => f.write_str("roll back"),
+        }
+    }
+}
+
+/// Header written when the journal is created; entries are appended after it.
+#[derive(Serialize)]
+struct Header<'a> {
+    schema: u32,
+    install_tag: &'a Tag,
+    command: &'a str,
+    pid: u32,
+    started_at: DateTime<Utc>,
+}
+
+/// Wraps one entry so it serializes as an `[[entry]]` block (with `[entry.previous]`
+/// sub-tables) that can be appended to the file as-is.
+#[derive(Serialize)]
+struct EntryBlock<'a> {
+    entry: [&'a JournalEntry; 1],
+}
+
+/// Returns the journal path for `tag` under `cache_dir`.
+pub fn path(cache_dir: &Path, tag: &Tag) -> PathBuf {
+    cache_dir
+        .join("journal")
+        .join(format!("{}.toml", tag.as_str()))
+}
+
+/// Returns the failed-addon list path for `tag` under `cache_dir`.
+pub fn failed_path(cache_dir: &Path, tag: &Tag) -> PathBuf {
+    cache_dir
+        .join("journal")
+        .join(format!("{}.failed.toml", tag.as_str()))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FailedList {
+    #[serde(default)]
+    failed: Vec<String>,
+}
+
+/// Loads the addon names recorded by [`save_failed`]; empty when there is no list.
+pub fn load_failed(path: &Path) -> Result<Vec<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(toml::from_str::<FailedList>(&content)?.failed),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Records the addons that failed in the last sync; an empty list deletes the file.
+pub fn save_failed(path: &Path, names: &[String]) -> Result<()> {
+    if names.is_empty() {
+        return match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string(&FailedList {
+        failed: names.to_vec(),
+    })?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Loads the journal at `path`, or `None` when no run was interrupted.
+///
+/// A torn final entry (the process died mid-append) is dropped with a warning;
+/// the change it described never reached the lock either.
+pub fn load(path: &Path) -> Result<Option<Journal>> {
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut journal: Journal = match toml::from_str(&content) {
+        Ok(j) => j,
+        Err(e) => match content.rfind("\n[[entry]]") {
+            Some(cut) => {
+                tracing::warn!(error = %e, path = %path.display(), "dropping torn journal entry");
+                toml::from_str(&content[..cut])?
+            }
+            None => return Err(e.into()),
+        },
+    };
+    journal.path = path.to_path_buf();
+    Ok(Some(journal))
+}
+
+impl Journal {
+    /// Creates the journal file at `path` for a new `command` run on `install_tag`.
+    ///
+    /// Fails with [`crate::Error::InterruptedRun`] if a journal already exists there;
+    /// callers must resolve it first.
+    pub fn begin(path: &Path, install_tag: &Tag, command: &str) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let journal = Journal {
+            schema: SUPPORTED_SCHEMA,
+            install_tag: install_tag.clone(),
+            command: command.to_owned(),
+            pid: std::process::id(),
+            started_at: Utc::now(),
+            entry: Vec::new(),
+            path: path.to_path_buf(),
+        };
+        let header = toml::to_string(&Header {
+            schema: journal.schema,
+            install_tag,
+            command,
+            pid: journal.pid,
+            started_at: journal.started_at,
+        })?;
+
+        let mut file = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+        {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                return Err(crate::Error::InterruptedRun {
+                    tag: install_tag.clone(),
+                    path: path.to_path_buf(),
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        file.write_all(header.as_bytes())?;
+        file.sync_all()?;
+        Ok(journal)
+    }
+
+    /// Appends `entry` to the file and flushes it to disk before returning.
+    pub fn record(&mut self, entry: JournalEntry) -> Result<()> {
+        let block = toml::to_string(&EntryBlock { entry: [&entry] })?;
+        let mut file = fs::OpenOptions::new().append(true).open(&self.path)?;
+        file.write_all(format!("\n{block}").as_bytes())?;
+        file.sync_data()?;
+        self.entry.push(entry);
+        Ok(())
+    }
+
+    /// Replays every entry onto `lock`, so it reflects all completed changes even
+    /// if the process died between recording an entry and saving the lock.
+    pub fn roll_forward(&self, lock: &mut Lock) {
+        for entry in &self.entry {
+            match &entry.current {
+                Some(current) => lock.upsert(current.clone()),
+                None => {
+                    lock.take(&entry.name, &entry.flavor);
+                }
+            }
+        }
+        lock.generated_at = Utc::now();
+    }
+
+    /// Ends a run that stopped on an ordinary error rather than a crash: replays
+    /// the recorded changes onto `lock`, saves it to `lock_path`, then deletes the
+    /// journal. When the lock cannot be saved the journal is kept, since it is then
+    /// the only record of those changes.
+    pub fn close(self, lock: &mut Lock, lock_path: &Path) -> Result<()> {
+        if !self.entry.is_empty() {
+            self.roll_forward(lock);
+            crate::lock::save(lock, lock_path)?;
+        }
+        self.finish()
+    }
+
+    /// Deletes the journal file: the run it describes is complete or resolved.
+    pub fn finish(self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl JournalEntry {
+    /// Builds an entry from the lock rows before and after a change.
+    pub fn new(
+        name: &str,
+        flavor: &Flavor,
+        previous: Option<LockedAddon>,
+        current: Option<LockedAddon>,
+    ) -> Self {
+        Self {
+            action: if current.is_some() {
+                Action::Install
+            } else {
+                Action::Remove
+            },
+            name: name.to_owned(),
+            flavor: flavor.clone(),
+            recorded_at: Utc::now(),
+            previous,
+            current,
+            source: None,
+        }
+    }
+
+    /// Records the manifest row `addon` this change was installed from.
+    pub fn with_source(mut self, addon: &ManifestAddon) -> Self {
+        self.source = Some(addon.clone());
+        self
+    }
+
+    /// Provider of the addon this entry changed.
+    pub fn provider(&self) -> Option<&Provider> {
+        self.current
+            .as_ref()
+            .or(self.previous.as_ref())
+            .map(|a| &a.provider)
+    }
+}
@@ -0,0 +1,226 @@
+use super::*;
+use crate::model::{Channel, Flavor, Provider, Tag};
+
+fn locked(name: &str, version: &str) -> LockedAddon {
+    LockedAddon {
+        name: name.into(),
+        provider: Provider::Local,
+        flavor: Flavor::Retail,
+        channel: Channel::Stable,
+        project_id: None,
+        resolved_version: version.into(),
+        resolved_id: format!("local:{name}@{version}"),
+        download_url: format!("/tmp/{name}.zip"),
+        sha256: Some("ab".repeat(32)),
+        installed_dirs: vec![name.into()],
+        installed_at: Utc::now(),
+    }
+}
+
+#[test]
+fn entry_source_survives_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let tag = Tag::new("retail");
+    let path = path(dir.path(), &tag);
+    let row: ManifestAddon = toml::from_str(
+        "name = \"MyAddon\"\nprovider = \"github\"\nrepo = \"owner/MyAddon\"\ngit_ref = \"main\"\n",
+    )
+    .unwrap();
+
+    let mut journal = Journal::begin(&path, &tag, "sync").unwrap();
+    journal
+        .record(
+            JournalEntry::new(
+                "MyAddon",
+                &Flavor::Retail,
+                None,
+                Some(locked("MyAddon", "1")),
+            )
+            .with_source(&row),
+        )
+        .unwrap();
+
+    let loaded = load(&path).unwrap().unwrap();
+    let source = loaded.entry[0].source.as_ref().unwrap();
+    assert_eq!(source.repo.as_deref(), Some("owner/MyAddon"));
+    assert_eq!(source.git_ref.as_deref(), Some("main"));
+    assert!(source.asset_regex.is_none());
+}
+
+#[test]
+fn load_missing_journal_is_none() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(load(&dir.path().join("retail.toml")).unwrap().is_none());
+}
+
+#[test]
+fn recorded_entries_survive_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let tag = Tag::new("retail");
+    let path = path(dir.path(), &tag);
+
+    let mut journal = Journal::begin(&path, &tag, "sync").unwrap();
+    journal
+        .record(JournalEntry::new(
+            "Bagnon",
+            &Flavor::Retail,
+            Some(locked("Bagnon", "1.0")),
+            Some(locked("Bagnon", "1.1")),
+        ))
+        .unwrap();
+    journal
+        .record(JournalEntry::new(
+            "Details",
+            &Flavor::Retail,
+            Some(locked("Details", "2.0")),
+            None,
+        ))
+        .unwrap();
+
+    let loaded = load(&path).unwrap().unwrap();
+    assert_eq!(loaded.command, "sync");
+    assert_eq!(loaded.install_tag, tag);
+    assert_eq!(loaded.entry.len(), 2);
+    assert_eq!(loaded.entry[0].action, Action::Install);
+    assert_eq!(
+        loaded.entry[0].current.as_ref().unwrap().resolved_version,
+        "1.1"
+    );
+    assert_eq!(loaded.entry[1].action, Action::Remove);
+    assert!(loaded.entry[1].current.is_none());
+
+    loaded.finish().unwrap();
+    assert!(!path.exists());
+}
+
+#[test]
+fn begin_refuses_existing_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let tag = Tag::new("retail");
+    let path = path(dir.path(), &tag);
+
+    let _first = Journal::begin(&path, &tag, "sync").unwrap();
+    let err = Journal::begin(&path, &tag, "remove").unwrap_err();
+    assert!(matches!(err, crate::Error::InterruptedRun { .. }));
+}
+
+#[test]
+fn torn_final_entry_is_dropped() {
+    let dir = tempfile::tempdir().unwrap();
+    let tag = Tag::new("retail");
+    let path = path(dir.path(), &tag);
+
+    let mut journal = Journal::begin(&path, &tag, "sync").unwrap();
+    journal
+        .record(JournalEntry::new(
+            "Bagnon",
+            &Flavor::Retail,
+            None,
+            Some(locked("Bagnon", "1.0")),
+        ))
+        .unwrap();
+    let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(b"\n[[entry]]\naction = \"inst").unwrap();
+
+    let loaded = load(&path).unwrap().unwrap();
+    assert_eq!(loaded.entry.len(), 1);
+}
+
+#[test]
+fn roll_forward_replays_entries_onto_lock() {
+    let mut lock = Lock::new(Tag::new("retail"));
+    lock.addon.push(locked("Bagnon", "1.0"));
+    lock.addon.push(locked("Details", "2.0"));
+
+    let journal = Journal {
+        schema: SUPPORTED_SCHEMA,
+        install_tag: Tag::new("retail"),
+        command: "sync".into(),
+        pid: 1,
+        started_at: Utc::now(),
+        entry: vec![
+            JournalEntry::new(
+                "Bagnon",
+                &Flavor::Retail,
+                Some(locked("Bagnon", "1.0")),
+                Some(locked("Bagnon", "1.1")),
+            ),
+            JournalEntry::new(
+                "Details",
+                &Flavor::Retail,
+                Some(locked("Details", "2.0")),
+                None,
+            ),
+            JournalEntry::new(
+                "Plater",
+                &Flavor::Retail,
+                None,
+                Some(locked("Plater", "3.0")),
+            ),
+        ],
+        path: PathBuf::new(),
+    };
+    journal.roll_forward(&mut lock);
+
+    let names: Vec<&str> = lock.addon.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["Bagnon", "Plater"]);
+    assert_eq!(
+        lock.find("Bagnon", &Flavor::Retail)
+            .unwrap()
+            .resolved_version,
+        "1.1"
+    );
+}
+
+#[test]
+fn failed_list_round_trips_and_clears() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = failed_path(dir.path(), &Tag::new("retail"));
+    assert!(load_failed(&path).unwrap().is_empty());
+
+    save_failed(&path, &["Bagnon".to_owned(), "Details".to_owned()]).unwrap();
+    assert_eq!(load_failed(&path).unwrap(), vec!["Bagnon", "Details"]);
+
+    save_failed(&path, &[]).unwrap();
+    assert!(!path.exists());
+    save_failed(&path, &[]).unwrap();
+}
+
+#[test]
+fn close_saves_recorded_changes_and_deletes_the_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let tag = Tag::new("retail");
+    let path = path(dir.path(), &tag);
+    let lock_path = dir.path().join("retail.lock.toml");
+
+    let mut journal = Journal::begin(&path, &tag, "sync").unwrap();
+    journal
+        .record(JournalEntry::new(
+            "Bagnon",
+            &Flavor::Retail,
+            None,
+            Some(locked("Bagnon", "1.1")),
+        ))
+        .unwrap();
+    let mut lock = Lock::new(tag.clone());
+    journal.close(&mut lock, &lock_path).unwrap();
+
+    assert!(load(&path).unwrap().is_none());
+    let saved = crate::lock::load(&lock_path).unwrap();
+    assert_eq!(
+        saved
+            .find("Bagnon", &Flavor::Retail)
+            .unwrap()
+            .resolved_version,
+        "1.1"
+    );
+
+    // A lock that cannot be saved keeps the journal as the only record.
+    let mut journal = Journal::begin(&path, &tag, "sync").unwrap();
+    journal
+        .record(JournalEntry::new("Bagnon", &Flavor::Retail, None, None))
+        .unwrap();
+    let unwritable = dir.path().join("missing/dir/lock.toml");
+    assert!(journal.close(&mut lock, &unwritable).is_err());
+    assert!(load(&path).unwrap().is_some());
+}
@@ -1,11 +1,20 @@
+pub mod adopt;
+pub mod checksum;
+pub mod client;
 pub mod error;
+pub mod events;
 pub mod fs;
+pub mod inventory;
+pub mod journal;
 pub mod lock;
 pub mod manifest;
 pub mod model;
 pub mod ops;
+#[cfg(feature = "github")]
+pub mod pkgmeta;
 pub mod providers;
 pub mod resolve;
+pub mod status;
 pub mod toc;
 
 pub use error::Error;
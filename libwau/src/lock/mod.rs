@@ -1,4 +1,4 @@
-use std::{fs, path::Path};
+use std::{fmt, fs, path::Path};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -46,6 +46,72 @@ impl Lock {
             addon: Vec::new(),
         }
     }
+
+    /// Returns the locked entry for `name` + `flavor`, if any.
+    pub fn find(&self, name: &str, flavor: &Flavor) -> Option<&LockedAddon> {
+        self.addon
+            .iter()
+            .find(|a| a.name == name && &a.flavor == flavor)
+    }
+
+    /// Inserts `entry`, replacing any existing entry with the same name + flavor.
+    pub fn upsert(&mut self, entry: LockedAddon) {
+        self.take(&entry.name, &entry.flavor);
+        self.addon.push(entry);
+    }
+
+    /// Returns every entry whose `installed_dirs` include `folder`.
+    ///
+    /// A folder with more than one owner is shared: it stays installed until the
+    /// last of them is removed.
+    pub fn owners(&self, folder: &str) -> Vec<&LockedAddon> {
+        self.addon
+            .iter()
+            .filter(|a| a.installed_dirs.iter().any(|d| d == folder))
+            .collect()
+    }
+
+    /// Returns the folders among `dirs` that entries other than `name` + `flavor`
+    /// already own, together with those owners.
+    pub fn claims(&self, name: &str, flavor: &Flavor, dirs: &[String]) -> Vec<FolderClaim> {
+        dirs.iter()
+            .filter_map(|folder| {
+                let owners: Vec<String> = self
+                    .owners(folder)
+                    .into_iter()
+                    .filter(|a| !(a.name == name && &a.flavor == flavor))
+                    .map(|a| a.name.clone())
+                    .collect();
+                (!owners.is_empty()).then(|| FolderClaim {
+                    folder: folder.clone(),
+                    owners,
+                })
+            })
+            .collect()
+    }
+
+    /// Removes and returns the entry for `name` + `flavor`, if any.
+    pub fn take(&mut self, name: &str, flavor: &Flavor) -> Option<LockedAddon> {
+        let pos = self
+            .addon
+            .iter()
+            .position(|a| a.name == name && &a.flavor == flavor)?;
+        Some(self.addon.remove(pos))
+    }
+}
+
+/// A folder that other locked addons already own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderClaim {
+    pub folder: String,
+    /// Names of the owning lock entries.
+    pub owners: Vec<String>,
+}
+
+impl fmt::Display for FolderClaim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (owned by {})", self.folder, self.owners.join(", "))
+    }
 }
 
 /// Loads a lock from `path`.
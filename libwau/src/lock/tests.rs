@@ -94,3 +94,63 @@ fn load_returns_not_found_error() {
     let result = load(std::path::Path::new("/nonexistent/path/lock.toml"));
     assert!(matches!(result, Err(crate::Error::LockNotFound { .. })));
 }
+
+#[test]
+fn upsert_replaces_same_name_and_flavor() {
+    let mut lock = parse(EXAMPLE_LOCK).unwrap();
+    let mut updated = lock.addon[0].clone();
+    updated.resolved_version = "10.1.0".into();
+    lock.upsert(updated);
+
+    assert_eq!(lock.addon.len(), 1);
+    let found = lock.find("Bagnon", &Flavor::Retail).unwrap();
+    assert_eq!(found.resolved_version, "10.1.0");
+    assert!(lock.find("Bagnon", &Flavor::Era).is_none());
+
+    assert!(lock.take("Bagnon", &Flavor::Retail).is_some());
+    assert!(lock.addon.is_empty());
+}
+
+fn owned(name: &str, dirs: &[&str]) -> LockedAddon {
+    LockedAddon {
+        name: name.into(),
+        provider: Provider::Local,
+        flavor: Flavor::Retail,
+        channel: Channel::Stable,
+        project_id: None,
+        resolved_version: "1".into(),
+        resolved_id: "1".into(),
+        download_url: "file:///x.zip".into(),
+        sha256: None,
+        installed_dirs: dirs.iter().map(|d| d.to_string()).collect(),
+        installed_at: Utc::now(),
+    }
+}
+
+#[test]
+fn owners_lists_every_entry_with_the_folder() {
+    let mut lock = Lock::new(Tag::new("t"));
+    lock.upsert(owned("Pack", &["Pack", "LibShared"]));
+    lock.upsert(owned("Other", &["Other", "LibShared"]));
+
+    let names: Vec<&str> = lock
+        .owners("LibShared")
+        .iter()
+        .map(|a| a.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Pack", "Other"]);
+    assert!(lock.owners("Missing").is_empty());
+}
+
+#[test]
+fn claims_ignore_the_entry_itself() {
+    let mut lock = Lock::new(Tag::new("t"));
+    lock.upsert(owned("Pack", &["Pack", "LibShared"]));
+
+    let dirs = vec!["Pack".to_owned(), "LibShared".to_owned()];
+    assert!(lock.claims("Pack", &Flavor::Retail, &dirs).is_empty());
+
+    let claims = lock.claims("Plugin", &Flavor::Retail, &dirs);
+    assert_eq!(claims.len(), 2);
+    assert_eq!(claims[1].to_string(), "LibShared (owned by Pack)");
+}
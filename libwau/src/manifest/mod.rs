@@ -1,9 +1,9 @@
 //! `manifest.toml` schema, loading, and validation.
 //!
-//! **Pin policy**: `pin` fields are parsed for forward compatibility but the resolver
-//! must not honour them until Phase 8. Manifests containing pins are accepted; a
-//! `tracing::warn` is emitted per pinned addon at load time so the limitation is
-//! visible without being fatal.
+//! **Pin policy**: `pin` fields are honoured by every provider's `resolve`. A pin the
+//! provider cannot express (e.g. a CurseForge `file_id` on a GitHub row) is rejected
+//! with [`crate::Error::UnsupportedPin`]; a pinned target that no longer exists fails
+//! with [`crate::Error::PinNotFound`] instead of falling back to latest.
 
 use std::{fs, path::Path};
 
@@ -45,7 +45,15 @@ pub struct ManifestAddon {
     pub url: Option<String>,
 }
 
-/// Version pin — parsed for forward compatibility, ignored by the resolver until Phase 8.
+impl ManifestAddon {
+    /// Returns `true` for GitHub rows installed from a raw repository snapshot
+    /// (`git_ref`, or a tag/commit pin without `asset_regex`) rather than a release asset.
+    pub fn is_git_snapshot(&self) -> bool {
+        self.provider == Provider::GitHub && self.asset_regex.is_none()
+    }
+}
+
+/// Version pin — selects one exact artifact instead of the latest for the row's channel.
 ///
 /// Variants are ordered from most specific to least specific for untagged serde matching.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +66,18 @@ pub enum Pin {
     Commit { commit: String },
 }
 
+impl std::fmt::Display for Pin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TagWithSha { tag, sha256 } => write!(f, "tag {tag} (sha256 {sha256})"),
+            Self::Tag { tag } => write!(f, "tag {tag}"),
+            Self::Version { version } => write!(f, "version {version}"),
+            Self::FileId { file_id } => write!(f, "file_id {file_id}"),
+            Self::Commit { commit } => write!(f, "commit {commit}"),
+        }
+    }
+}
+
 /// Loads and validates a manifest from `path`.
 pub fn load(path: &Path) -> Result<Manifest> {
     let content = fs::read_to_string(path).map_err(|e| {
@@ -72,20 +92,37 @@ pub fn load(path: &Path) -> Result<Manifest> {
     parse(&content)
 }
 
-/// Parses a manifest from a TOML string and emits warnings for unsupported features.
+/// Parses a manifest from a TOML string.
 pub fn parse(s: &str) -> Result<Manifest> {
-    let manifest: Manifest = toml::from_str(s)?;
-    warn_pins(&manifest);
-    Ok(manifest)
+    Ok(toml::from_str(s)?)
 }
 
-fn warn_pins(manifest: &Manifest) {
-    for addon in &manifest.addon {
-        if addon.pin.is_some() {
-            tracing::warn!(
-                addon = %addon.name,
-                "pin is unsupported until Phase 8 and will be ignored by the resolver"
-            );
+/// Appends `rows` as `[[addon]]` tables to the manifest at `path`, creating it
+/// (with `schema`) if it does not exist.
+///
+/// The manifest is hand-edited, so the existing text is kept as-is rather than
+/// re-serialized; comments and row order survive.
+pub fn append(path: &Path, rows: &[ManifestAddon]) -> Result<()> {
+    #[derive(Serialize)]
+    struct Rows<'a> {
+        addon: &'a [ManifestAddon],
+    }
+
+    let mut content = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            format!("schema = {SUPPORTED_SCHEMA}\n")
         }
+        Err(e) => return Err(e.into()),
+    };
+    if rows.is_empty() {
+        return Ok(());
+    }
+    if !content.ends_with('\n') {
+        content.push('\n');
     }
+    content.push('\n');
+    content.push_str(&toml::to_string(&Rows { addon: rows })?);
+    fs::write(path, content)?;
+    Ok(())
 }
@@ -157,3 +157,49 @@ fn empty_addon_list_parses() {
     let m = parse("schema = 1\n").unwrap();
     assert_eq!(m.addon.len(), 0);
 }
+
+#[test]
+fn pin_display_names_target() {
+    let m = parse(GITHUB_ADDON).unwrap();
+    let pins: Vec<String> = m
+        .addon
+        .iter()
+        .map(|a| a.pin.as_ref().unwrap().to_string())
+        .collect();
+    assert_eq!(pins[0], "tag v2.1.0-beta.1");
+    assert!(pins[1].starts_with("tag v3.0.0 (sha256 "));
+    assert_eq!(pins[2], "commit deadbeef0123456789deadbeef0123456789abcd");
+}
+
+#[test]
+fn append_keeps_existing_text_and_adds_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("manifest.toml");
+    std::fs::write(&path, format!("# my addons\n{SIMPLE}")).unwrap();
+
+    let mut row = parse(SIMPLE).unwrap().addon.remove(0);
+    row.name = "Details".into();
+    row.project_id = Some(61284);
+    row.flavors = None;
+    append(&path, &[row]).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("# my addons\n"));
+    let manifest = parse(&content).unwrap();
+    assert_eq!(manifest.addon.len(), 3);
+    assert_eq!(manifest.addon[2].name, "Details");
+    assert_eq!(manifest.addon[2].project_id, Some(61284));
+    assert!(manifest.addon[2].flavors.is_none());
+}
+
+#[test]
+fn append_creates_missing_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("manifest.toml");
+    let row = parse(SIMPLE).unwrap().addon.remove(0);
+    append(&path, &[row]).unwrap();
+
+    let manifest = load(&path).unwrap();
+    assert_eq!(manifest.schema, SUPPORTED_SCHEMA);
+    assert_eq!(manifest.addon[0].name, "Bagnon");
+}
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
@@ -52,6 +54,21 @@ pub enum Flavor {
 }
 
 impl Flavor {
+    pub const ALL: &[Self] = &[
+        Self::Retail,
+        Self::Era,
+        Self::Tbc,
+        Self::Wrath,
+        Self::Cata,
+        Self::Mop,
+        Self::Wod,
+        Self::Legion,
+        Self::Bfa,
+        Self::Shadowlands,
+        Self::Dragonflight,
+        Self::Tww,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Retail => "retail",
@@ -68,6 +85,48 @@ impl Flavor {
             Self::Tww => "classic-tww",
         }
     }
+
+    /// The `## Interface` numbers that target this flavor (see the type docs).
+    pub fn interface_range(&self) -> RangeInclusive<u32> {
+        match self {
+            Self::Retail => 120000..=u32::MAX,
+            Self::Tww => 110000..=119999,
+            Self::Dragonflight => 100000..=109999,
+            Self::Shadowlands => 90000..=99999,
+            Self::Bfa => 80000..=89999,
+            Self::Legion => 70000..=79999,
+            Self::Wod => 60000..=69999,
+            Self::Mop => 50000..=59999,
+            Self::Cata => 40000..=49999,
+            Self::Wrath => 30000..=39999,
+            Self::Tbc => 20000..=29999,
+            Self::Era => 10000..=19999,
+        }
+    }
+
+    /// The flavor whose [`interface_range`](Self::interface_range) contains
+    /// `interface`; `None` below 10000 (pre-Classic or unset).
+    pub fn from_interface(interface: u32) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|f| f.interface_range().contains(&interface))
+            .cloned()
+    }
+
+    /// Suffixes Blizzard clients of this flavor recognise on `## Interface-<Suffix>`
+    /// keys and `.toc` file names, most specific first. `Classic` applies to every
+    /// Classic client; flavors without an official client have none.
+    pub fn client_suffixes(&self) -> &'static [&'static str] {
+        match self {
+            Self::Retail => &["Mainline"],
+            Self::Era => &["Vanilla", "Classic"],
+            Self::Tbc => &["TBC", "BCC", "Classic"],
+            Self::Wrath => &["Wrath", "WOTLKC", "Classic"],
+            Self::Cata => &["Cata", "Classic"],
+            Self::Mop => &["Mists", "Classic"],
+            _ => &[],
+        }
+    }
 }
 
 impl std::fmt::Display for Flavor {
@@ -76,6 +135,14 @@ impl std::fmt::Display for Flavor {
     }
 }
 
+impl std::str::FromStr for Flavor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_slug(Self::ALL, Self::as_str, "flavor", s)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Channel {
     #[serde(rename = "stable")]
@@ -87,6 +154,8 @@ pub enum Channel {
 }
 
 impl Channel {
+    pub const ALL: &[Self] = &[Self::Stable, Self::Beta, Self::Alpha];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Stable => "stable",
@@ -102,6 +171,14 @@ impl std::fmt::Display for Channel {
     }
 }
 
+impl std::str::FromStr for Channel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_slug(Self::ALL, Self::as_str, "channel", s)
+    }
+}
+
 /// Known addon providers. New providers are added via PR.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Provider {
@@ -116,6 +193,13 @@ pub enum Provider {
 }
 
 impl Provider {
+    pub const ALL: &[Self] = &[
+        Self::CurseForge,
+        Self::WoWInterface,
+        Self::GitHub,
+        Self::Local,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::CurseForge => "curseforge",
@@ -132,6 +216,31 @@ impl std::fmt::Display for Provider {
     }
 }
 
+impl std::str::FromStr for Provider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_slug(Self::ALL, Self::as_str, "provider", s)
+    }
+}
+
+/// Parses `s` as the value in `all` whose `as_str` slug it is, for the `FromStr`
+/// impls of slug enums; the error names `what` and lists the known slugs.
+pub(crate) fn parse_slug<T: Clone>(
+    all: &[T],
+    as_str: fn(&T) -> &'static str,
+    what: &str,
+    s: &str,
+) -> Result<T, String> {
+    all.iter().find(|v| as_str(v) == s).cloned().ok_or_else(|| {
+        let known: Vec<&str> = all.iter().map(as_str).collect();
+        format!(
+            "unknown {what} '{s}' (expected one of: {})",
+            known.join(", ")
+        )
+    })
+}
+
 /// User-defined label identifying one WoW install tree in `config.toml`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
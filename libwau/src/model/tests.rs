@@ -153,3 +153,46 @@ fn display_impls() {
     assert_eq!(Tag::new("turtle-wow").to_string(), "turtle-wow");
     assert_eq!(LogLevel::Warn.to_string(), "warn");
 }
+
+#[test]
+fn from_str_accepts_every_slug() {
+    for flavor in Flavor::ALL {
+        assert_eq!(flavor.as_str().parse::<Flavor>().as_ref(), Ok(flavor));
+    }
+    for channel in Channel::ALL {
+        assert_eq!(channel.as_str().parse::<Channel>().as_ref(), Ok(channel));
+    }
+    for provider in Provider::ALL {
+        assert_eq!(provider.as_str().parse::<Provider>().as_ref(), Ok(provider));
+    }
+}
+
+#[test]
+fn from_str_rejects_unknown_slug_listing_choices() {
+    let err = "classic".parse::<Flavor>().unwrap_err();
+    assert!(err.contains("unknown flavor 'classic'"));
+    assert!(err.contains("classic-era"));
+    assert!("CurseForge".parse::<Provider>().is_err());
+}
+
+#[test]
+fn from_interface_maps_each_range_to_its_flavor() {
+    assert_eq!(Flavor::from_interface(120001), Some(Flavor::Retail));
+    assert_eq!(Flavor::from_interface(110207), Some(Flavor::Tww));
+    assert_eq!(Flavor::from_interface(50503), Some(Flavor::Mop));
+    assert_eq!(Flavor::from_interface(38000), Some(Flavor::Wrath));
+    assert_eq!(Flavor::from_interface(11508), Some(Flavor::Era));
+    assert_eq!(Flavor::from_interface(0), None);
+}
+
+#[test]
+fn interface_ranges_cover_every_flavor_without_overlap() {
+    for flavor in Flavor::ALL {
+        let range = flavor.interface_range();
+        assert_eq!(
+            Flavor::from_interface(*range.start()).as_ref(),
+            Some(flavor)
+        );
+        assert_eq!(Flavor::from_interface(*range.end()).as_ref(), Some(flavor));
+    }
+}
@@ -3,31 +3,57 @@
 //! All filesystem operations are delegated to [`crate::fs`]; this module owns
 //! only the staging lifecycle, provider calls, and lock bookkeeping.
 //!
-//! Install flow (§5.1):
+//! Install flow (§5.1) runs in two phases. [`resolve`] covers step 1 and compares
+//! the result with the lock; [`apply`] covers steps 2–5 and is a no-op when the lock
+//! already records the resolved artifact. [`install`] chains both. [`apply`] is
+//! itself split into [`fetch`] (steps 2–3, network + staging) and [`commit`]
+//! (steps 4–5, swap-in + lock), so [`sync`] can run many fetches concurrently while
+//! keeping every swap-in and lock update serialized.
 //!  1. Provider resolves manifest row → `ResolvedArtifact`.
-//!  2. Zip is downloaded to a staging directory under `cache_dir`.
+//!  2. Zip is downloaded to a staging directory under `cache_dir` and verified
+//!     against the provider's digest; its sha256 is computed for the lock.
 //!  3. Zip is extracted; top-level dirs with at least one `.toc` are identified.
-//!  4. Each addon dir is copied into `addons_path` (existing dir replaced).
-//!  5. Lock is updated with the resolved artifact + installed directories.
+//!     Dirs another lock entry owns are refused unless `ctx.conflicts` allows
+//!     sharing them ([`crate::providers::ConflictPolicy`]).
+//!  4. Addon dirs are staged beside `addons_path` and swapped in by rename; a
+//!     failure part-way restores the previous dirs (see [`crate::fs::install_addon_dirs`]).
+//!  5. Lock is updated with the resolved artifact + installed directories, only
+//!     after every dir has been swapped in; dirs the previous version had and
+//!     this one lacks are removed unless another entry owns them.
 //!
 //! Remove flow:
 //!  1. Lock entry is found by addon name + flavor.
-//!  2. Each recorded directory is removed from `addons_path`.
-//!  3. Lock entry is removed.
+//!  2. Lock entry is removed.
+//!  3. Each recorded directory no remaining entry owns is removed from `addons_path`;
+//!     shared folders stay until their last owner goes.
+//!
+//! Every entry point reports its steps to a caller-supplied [`EventSink`]
+//! (see [`crate::events`]); pass [`crate::events::NullSink`] to ignore them.
+//!
+//! [`revert`] undoes one [`crate::journal`] entry by re-applying the lock row it
+//! replaced, so an interrupted run can be rolled back.
 
 use std::{
+    collections::HashMap,
     fs,
-    path::Path,
+    ops::ControlFlow,
+    path::PathBuf,
     sync::atomic::{AtomicU64, Ordering},
 };
 
 use chrono::Utc;
+use futures_util::{StreamExt, stream::FuturesUnordered};
+use tokio::sync::Semaphore;
 
 use crate::{
     Result,
+    checksum::Digest,
+    events::{Event, EventSink, Progress},
+    journal::JournalEntry,
     lock::{Lock, LockedAddon},
     manifest::ManifestAddon,
-    providers::{InstallContext, Provider},
+    model::{self, Channel},
+    providers::{ConflictPolicy, InstallContext, Provider, ResolvedArtifact},
 };
 
 #[cfg(test)]
@@ -35,112 +61,723 @@ mod tests;
 
 static STAGING_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// Result of the resolve phase for one manifest row.
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub artifact: ResolvedArtifact,
+    /// Version recorded in the lock for this addon + flavor, if it is locked.
+    pub locked_version: Option<String>,
+    /// `true` when the lock already records `artifact.id` and every locked
+    /// directory is still present on disk.
+    pub up_to_date: bool,
+}
+
+/// What [`apply`] did with a [`Resolution`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallOutcome {
+    /// The addon was not locked before.
+    Installed {
+        version: String,
+        skipped_externals: Vec<SkippedExternal>,
+    },
+    /// The addon was locked at a different artifact and has been replaced.
+    Updated {
+        from: String,
+        to: String,
+        skipped_externals: Vec<SkippedExternal>,
+    },
+    /// The lock already matched the resolved artifact; nothing was downloaded.
+    UpToDate { version: String },
+}
+
+/// A `.pkgmeta` external of a git snapshot that wau could not fetch (SVN and
+/// non-GitHub git hosts such as repos.wowace.com), so its files are missing from
+/// the install.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedExternal {
+    /// Target path inside the addon, e.g. `Libs/LibStub`.
+    pub path: String,
+    pub url: String,
+}
+
+/// An artifact fetched by [`fetch`] and waiting for [`commit`].
+#[derive(Debug)]
+pub struct Fetched {
+    addon_dirs: Vec<PathBuf>,
+    skipped_externals: Vec<SkippedExternal>,
+    /// sha256 of the downloaded zip, recorded in the lock.
+    sha256: String,
+    _staging: StagingDir,
+}
+
+impl Fetched {
+    fn dir_names(&self) -> Vec<String> {
+        self.addon_dirs
+            .iter()
+            .filter_map(|d| d.file_name()?.to_str().map(str::to_owned))
+            .collect()
+    }
+}
+
+/// Concurrent resolve + download jobs per provider when nothing else is configured.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Per-provider cap on concurrent resolve + download jobs in [`sync`].
+#[derive(Debug, Clone)]
+pub struct Concurrency {
+    /// Limit for providers without an entry in `per_provider`.
+    pub default: usize,
+    pub per_provider: HashMap<model::Provider, usize>,
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self {
+            default: DEFAULT_CONCURRENCY,
+            per_provider: HashMap::new(),
+        }
+    }
+}
+
+impl Concurrency {
+    /// Returns the limit for `provider`; never less than 1.
+    pub fn limit(&self, provider: &model::Provider) -> usize {
+        self.per_provider
+            .get(provider)
+            .copied()
+            .unwrap_or(self.default)
+            .max(1)
+    }
+}
+
+/// One manifest row for [`sync`], with the provider that serves it.
+pub struct SyncJob<'a> {
+    pub addon: &'a ManifestAddon,
+    pub provider: Box<dyn Provider>,
+}
+
+/// What happened to one [`SyncJob`], as passed to the callback of [`sync`].
+#[derive(Debug)]
+pub struct SyncResult<'a> {
+    pub addon: &'a ManifestAddon,
+    /// Lock row for this addon before the job was committed.
+    pub previous: Option<LockedAddon>,
+    pub outcome: Result<InstallOutcome>,
+}
+
+/// A manifest row's latest artifact compared with the lock, for read-only reports.
+#[derive(Debug, Clone)]
+pub struct UpdateStatus {
+    pub name: String,
+    pub provider: model::Provider,
+    /// Effective channel: the row's override, else the install context's.
+    pub channel: Channel,
+    /// Locked version, or `None` when the addon has never been installed.
+    pub installed: Option<String>,
+    pub available: String,
+    /// `true` when a sync with `--update` would download something.
+    pub outdated: bool,
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
 
-/// Installs an addon from the given manifest row into the install context.
-///
-/// Downloads the artifact, extracts the zip, copies addon directories into
-/// `ctx.addons_path`, and records the result in `lock`.
+/// Installs or updates an addon from the given manifest row: [`resolve`] then [`apply`].
 pub async fn install(
     provider: &dyn Provider,
     addon: &ManifestAddon,
     ctx: &InstallContext,
     lock: &mut Lock,
-) -> Result<()> {
+    events: &dyn EventSink,
+) -> Result<InstallOutcome> {
+    let resolution = resolve(provider, addon, ctx, lock, events).await?;
+    apply(provider, addon, &resolution, ctx, lock, events).await
+}
+
+/// Resolve phase: asks the provider for the artifact and compares it with the lock.
+///
+/// Touches neither the network download path nor `ctx.addons_path` beyond checking
+/// that locked directories exist.
+pub async fn resolve(
+    provider: &dyn Provider,
+    addon: &ManifestAddon,
+    ctx: &InstallContext,
+    lock: &Lock,
+    events: &dyn EventSink,
+) -> Result<Resolution> {
+    events.emit(Event::ResolveStarted {
+        addon: addon.name.clone(),
+    });
     let artifact = provider.resolve(addon, ctx).await?;
     tracing::debug!(name = %addon.name, version = %artifact.version, "resolved artifact");
 
+    let locked = lock.find(&addon.name, &ctx.flavor);
+
+    // Entries adopted from `.toc` IDs have no artifact id yet; the `.toc` version
+    // they were adopted at stands in for it, matched against the provider's
+    // display name (`v1.2.3` is current for `Addon-v1.2.3`).
+    let up_to_date = locked.is_some_and(|l| {
+        (l.resolved_id == artifact.id
+            || (l.resolved_id == crate::adopt::ADOPTED_ID
+                && crate::providers::version_matches(&l.resolved_version, &artifact.version)))
+            && l.installed_dirs
+                .iter()
+                .all(|d| ctx.addons_path.join(d).is_dir())
+    });
+
+    Ok(Resolution {
+        locked_version: locked.map(|l| l.resolved_version.clone()),
+        up_to_date,
+        artifact,
+    })
+}
+
+/// Resolves `addon` and reports whether the lock is behind, without touching
+/// `ctx.addons_path` or the lock.
+pub async fn check(
+    provider: &dyn Provider,
+    addon: &ManifestAddon,
+    ctx: &InstallContext,
+    lock: &Lock,
+    events: &dyn EventSink,
+) -> Result<UpdateStatus> {
+    let resolution = resolve(provider, addon, ctx, lock, events).await?;
+    Ok(UpdateStatus {
+        name: addon.name.clone(),
+        provider: addon.provider.clone(),
+        channel: addon.channel.clone().unwrap_or_else(|| ctx.channel.clone()),
+        installed: resolution.locked_version,
+        available: resolution.artifact.version,
+        outdated: !resolution.up_to_date,
+    })
+}
+
+/// Apply phase: downloads, verifies, extracts, and swaps in the resolved artifact,
+/// then records it in `lock`. Returns immediately when the resolution is up to date.
+pub async fn apply(
+    provider: &dyn Provider,
+    addon: &ManifestAddon,
+    resolution: &Resolution,
+    ctx: &InstallContext,
+    lock: &mut Lock,
+    events: &dyn EventSink,
+) -> Result<InstallOutcome> {
+    if resolution.up_to_date {
+        return Ok(up_to_date(addon, resolution));
+    }
+    let fetched = fetch(provider, addon, &resolution.artifact, ctx, events).await?;
+    commit(addon, resolution, fetched, ctx, lock, events)
+}
+
+/// Download phase of [`apply`] (steps 2–3): fetches `artifact` into a fresh staging
+/// dir under `ctx.cache_dir`, verifies it, and extracts the installable addon dirs.
+///
+/// Touches neither `ctx.addons_path` nor the lock, so any number of fetches may run
+/// concurrently. The staging dir is deleted when the returned [`Fetched`] is dropped.
+pub async fn fetch(
+    provider: &dyn Provider,
+    addon: &ManifestAddon,
+    artifact: &ResolvedArtifact,
+    ctx: &InstallContext,
+    events: &dyn EventSink,
+) -> Result<Fetched> {
     let staging_base = ctx.cache_dir.join("staging");
     fs::create_dir_all(&staging_base)?;
 
     let n = STAGING_COUNTER.fetch_add(1, Ordering::Relaxed);
     let pid = std::process::id();
-    let staging_dir = staging_base.join(format!("{}_{pid}_{n:06}", addon.name));
-    fs::create_dir_all(&staging_dir)?;
+    let staging = StagingDir(staging_base.join(format!("{}_{pid}_{n:06}", addon.name)));
+    fs::create_dir_all(&staging.0)?;
+
+    let zip_path = staging.0.join("addon.zip");
+    let progress = Progress::new(events, &addon.name);
+    provider
+        .download(
+            artifact,
+            &zip_path,
+            ctx.limits.max_download_bytes,
+            &progress,
+        )
+        .await?;
+    tracing::debug!(path = %zip_path.display(), "downloaded");
+
+    events.emit(Event::Extracting {
+        addon: addon.name.clone(),
+    });
+    let sha256 = crate::checksum::verify_file(&addon.name, &zip_path, artifact.digest.as_ref())?;
 
-    let result = install_inner(provider, addon, ctx, lock, &artifact, &staging_dir).await;
+    let extract_dir = staging.0.join("extracted");
+    fs::create_dir_all(&extract_dir)?;
+    #[cfg(feature = "github")]
+    let (addon_dirs, skipped_externals) = if addon.is_git_snapshot() {
+        unpack_snapshot(provider, addon, &zip_path, &extract_dir, ctx, events).await?
+    } else {
+        let dirs =
+            crate::fs::extract_addon_zip(&zip_path, &extract_dir, ctx.limits.max_unpacked_bytes)?;
+        (dirs, Vec::new())
+    };
+    #[cfg(not(feature = "github"))]
+    let (addon_dirs, skipped_externals) = (
+        crate::fs::extract_addon_zip(&zip_path, &extract_dir, ctx.limits.max_unpacked_bytes)?,
+        Vec::new(),
+    );
 
-    // Always clean up staging, even on failure.
-    if let Err(e) = fs::remove_dir_all(&staging_dir) {
-        tracing::debug!(error = %e, path = %staging_dir.display(), "failed to clean staging dir");
+    if addon_dirs.is_empty() {
+        return Err(crate::Error::NoInstallableDirs {
+            name: addon.name.clone(),
+        });
     }
 
-    result
+    Ok(Fetched {
+        addon_dirs,
+        skipped_externals,
+        sha256,
+        _staging: staging,
+    })
 }
 
-/// Removes an installed addon by looking up its recorded directories in the lock.
+/// Extracts a raw GitHub repository snapshot into `extract_dir` and lays it out the
+/// way the BigWigs packager would, following the repository's `.pkgmeta` (see
+/// [`crate::pkgmeta`]); without one, only dotfiles are dropped.
 ///
-/// Each directory listed in `lock` for `addon_name` + `ctx.flavor` is removed
-/// from `ctx.addons_path`. The lock entry is then deleted.
-pub async fn remove(addon_name: &str, ctx: &InstallContext, lock: &mut Lock) -> Result<()> {
-    let pos = lock
-        .addon
-        .iter()
-        .position(|a| a.name == addon_name && a.flavor == ctx.flavor)
-        .ok_or_else(|| crate::Error::AddonNotInLock {
-            name: addon_name.to_owned(),
-        })?;
-
-    let entry = lock.addon.remove(pos);
-    tracing::debug!(name = %addon_name, dirs = ?entry.installed_dirs, "removing addon dirs");
+/// GitHub-hosted externals are resolved and downloaded through `provider`; others
+/// (SVN, non-GitHub git hosts) cannot be fetched and are returned alongside the
+/// addon dirs so the install outcome can report them.
+#[cfg(feature = "github")]
+async fn unpack_snapshot(
+    provider: &dyn Provider,
+    addon: &ManifestAddon,
+    zip_path: &std::path::Path,
+    extract_dir: &std::path::Path,
+    ctx: &InstallContext,
+    events: &dyn EventSink,
+) -> Result<(Vec<PathBuf>, Vec<SkippedExternal>)> {
+    crate::fs::extract_zip(zip_path, extract_dir, ctx.limits.max_unpacked_bytes)?;
+    let root = match crate::fs::child_dirs(extract_dir)?.as_slice() {
+        [root] => root.clone(),
+        _ => return Ok((crate::fs::locate_addon_dirs(extract_dir)?, Vec::new())),
+    };
 
-    crate::fs::remove_addon_dirs(&entry.installed_dirs, &ctx.addons_path)?;
+    let meta = crate::pkgmeta::load(&root)?.unwrap_or_default();
+    meta.prune(&root)?;
+    let scratch = extract_dir.with_file_name("externals");
+    let mut skipped = Vec::new();
+    for (i, (path, external)) in meta.externals.iter().enumerate() {
+        let Some(repo) = external.github_repo() else {
+            tracing::warn!(addon = %addon.name, url = %external.url(), "skipping non-GitHub external");
+            skipped.push(SkippedExternal {
+                path: path.clone(),
+                url: external.url().to_owned(),
+            });
+            continue;
+        };
+        if external.wants_latest_tag() {
+            tracing::warn!(
+                addon = %addon.name,
+                path = %path,
+                "external asks for `tag: latest`; installing its default branch instead"
+            );
+        }
+        let row = ManifestAddon {
+            name: addon.name.clone(),
+            provider: model::Provider::GitHub,
+            channel: None,
+            flavors: None,
+            pin: None,
+            project_id: None,
+            wowi_id: None,
+            repo: Some(repo),
+            asset_regex: None,
+            git_ref: Some(external.git_ref().to_owned()),
+            url: None,
+        };
+        let dir = scratch.join(i.to_string());
+        fetch_external(provider, &row, &root.join(path), &dir, ctx, events).await?;
+        tracing::debug!(addon = %addon.name, path = %path, "fetched external");
+    }
 
-    lock.generated_at = Utc::now();
-    Ok(())
+    meta.arrange(&root, extract_dir)?;
+    Ok((crate::fs::locate_addon_dirs(extract_dir)?, skipped))
 }
 
-// ---------------------------------------------------------------------------
-// Internal helpers
-// ---------------------------------------------------------------------------
-
-async fn install_inner(
+/// Downloads the snapshot for one `.pkgmeta` external described by `row` and moves
+/// it to `target`, using `scratch` as its working dir.
+#[cfg(feature = "github")]
+async fn fetch_external(
     provider: &dyn Provider,
-    addon: &ManifestAddon,
+    row: &ManifestAddon,
+    target: &std::path::Path,
+    scratch: &std::path::Path,
     ctx: &InstallContext,
-    lock: &mut Lock,
-    artifact: &crate::providers::ResolvedArtifact,
-    staging_dir: &Path,
+    events: &dyn EventSink,
 ) -> Result<()> {
-    let zip_path = staging_dir.join("addon.zip");
-    provider.download(artifact, &zip_path).await?;
-    tracing::debug!(path = %zip_path.display(), "downloaded");
+    let artifact = provider.resolve(row, ctx).await?;
+    fs::create_dir_all(scratch)?;
+    let zip_path = scratch.join("external.zip");
+    let progress = Progress::new(events, &row.name);
+    provider
+        .download(
+            &artifact,
+            &zip_path,
+            ctx.limits.max_download_bytes,
+            &progress,
+        )
+        .await?;
 
-    let extract_dir = staging_dir.join("extracted");
-    fs::create_dir_all(&extract_dir)?;
-    let addon_dirs = crate::fs::extract_addon_zip(&zip_path, &extract_dir)?;
+    let unpacked = scratch.join("extracted");
+    fs::create_dir_all(&unpacked)?;
+    crate::fs::extract_zip(&zip_path, &unpacked, ctx.limits.max_unpacked_bytes)?;
+    let src = match crate::fs::child_dirs(&unpacked)?.as_slice() {
+        [only] => only.clone(),
+        _ => unpacked,
+    };
+    crate::pkgmeta::load(&src)?
+        .unwrap_or_default()
+        .prune(&src)?;
 
-    if addon_dirs.is_empty() {
-        return Err(crate::Error::NoInstallableDirs {
-            name: addon.name.clone(),
-        });
+    if target.exists() {
+        fs::remove_dir_all(target)?;
     }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&src, target)?;
+    Ok(())
+}
 
-    let installed_dirs = crate::fs::install_addon_dirs(&addon_dirs, &ctx.addons_path)?;
-
-    let flavor = ctx.flavor.clone();
-    let channel = ctx.channel.clone();
+/// Swap-in phase of [`apply`] (steps 4–5): moves the fetched dirs into
+/// `ctx.addons_path`, records the artifact in `lock`, and removes the unowned dirs
+/// only the replaced version had.
+pub fn commit(
+    addon: &ManifestAddon,
+    resolution: &Resolution,
+    fetched: Fetched,
+    ctx: &InstallContext,
+    lock: &mut Lock,
+    events: &dyn EventSink,
+) -> Result<InstallOutcome> {
+    let artifact = &resolution.artifact;
+    let dirs = fetched.dir_names();
+    let claims = lock.claims(&addon.name, &ctx.flavor, &dirs);
+    if !claims.is_empty() {
+        if ctx.conflicts == ConflictPolicy::Refuse {
+            return Err(crate::Error::FolderConflict {
+                name: addon.name.clone(),
+                claims,
+            });
+        }
+        tracing::debug!(name = %addon.name, ?claims, "sharing folders with other addons");
+    }
 
-    // Replace existing lock entry for this addon+flavor (covers updates).
-    lock.addon
-        .retain(|a| !(a.name == addon.name && a.flavor == flavor));
+    events.emit(Event::SwappingIn {
+        addon: addon.name.clone(),
+        dirs,
+    });
+    let installed_dirs = crate::fs::install_addon_dirs(&fetched.addon_dirs, &ctx.addons_path)?;
+    let dropped: Vec<String> = lock
+        .find(&addon.name, &ctx.flavor)
+        .map(|old| {
+            old.installed_dirs
+                .iter()
+                .filter(|d| !installed_dirs.contains(d))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
 
-    lock.addon.push(LockedAddon {
+    // Replaces any existing lock entry for this addon+flavor (covers updates).
+    lock.upsert(LockedAddon {
         name: addon.name.clone(),
         provider: addon.provider.clone(),
-        flavor,
-        channel,
+        flavor: ctx.flavor.clone(),
+        channel: ctx.channel.clone(),
         project_id: addon.project_id,
         resolved_version: artifact.version.clone(),
         resolved_id: artifact.id.clone(),
         download_url: artifact.url.clone(),
-        sha256: artifact.sha256.clone(),
+        sha256: Some(fetched.sha256),
         installed_dirs,
         installed_at: Utc::now(),
     });
     lock.generated_at = Utc::now();
+    events.emit(Event::LockUpdated {
+        addon: addon.name.clone(),
+    });
+
+    // Folders the previous version shipped and this one does not would otherwise
+    // linger unowned; the swap already committed, so a failure here only warns.
+    let dropped = unowned_dirs(lock, &dropped);
+    if let Err(e) = crate::fs::remove_addon_dirs(&dropped, &ctx.addons_path) {
+        tracing::warn!(name = %addon.name, ?dropped, error = %e, "failed to remove dropped folders");
+    }
+
+    let skipped_externals = fetched.skipped_externals;
+    Ok(match &resolution.locked_version {
+        Some(from) => InstallOutcome::Updated {
+            from: from.clone(),
+            to: artifact.version.clone(),
+            skipped_externals,
+        },
+        None => InstallOutcome::Installed {
+            version: artifact.version.clone(),
+            skipped_externals,
+        },
+    })
+}
+
+/// Syncs `jobs` with bounded parallelism.
+///
+/// Resolve and download (see [`fetch`]) run concurrently, with at most
+/// `concurrency.limit(provider)` jobs in flight per provider. Swap-in and lock
+/// updates stay serialized: they happen one job at a time, in completion order, on
+/// the caller's task. After each job `on_result` receives its result together with
+/// the updated `lock` (e.g. to journal and persist it); returning
+/// [`ControlFlow::Break`] drops the jobs still in flight, and an `Err` aborts the
+/// whole sync.
+pub async fn sync<'a, F>(
+    jobs: Vec<SyncJob<'a>>,
+    ctx: &InstallContext,
+    lock: &mut Lock,
+    concurrency: &Concurrency,
+    events: &dyn EventSink,
+    mut on_result: F,
+) -> Result<()>
+where
+    F: FnMut(SyncResult<'a>, &Lock) -> Result<ControlFlow<()>>,
+{
+    // Resolution only compares each job's own lock row, which no other job touches.
+    let snapshot = lock.clone();
+
+    let mut semaphores: HashMap<model::Provider, Semaphore> = HashMap::new();
+    for job in &jobs {
+        semaphores
+            .entry(job.addon.provider.clone())
+            .or_insert_with(|| Semaphore::new(concurrency.limit(&job.addon.provider)));
+    }
+
+    let mut pending: FuturesUnordered<_> = jobs
+        .iter()
+        .map(|job| {
+            let semaphore = &semaphores[&job.addon.provider];
+            let snapshot = &snapshot;
+            async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("semaphore is never closed");
+                let provider = job.provider.as_ref();
+                let result = async {
+                    let resolution = resolve(provider, job.addon, ctx, snapshot, events).await?;
+                    let fetched = if resolution.up_to_date {
+                        None
+                    } else {
+                        let artifact = &resolution.artifact;
+                        Some(fetch(provider, job.addon, artifact, ctx, events).await?)
+                    };
+                    Ok((resolution, fetched))
+                }
+                .await;
+                (job.addon, result)
+            }
+        })
+        .collect();
+
+    while let Some((addon, result)) = pending.next().await {
+        let previous = lock.find(&addon.name, &ctx.flavor).cloned();
+        let outcome = result.and_then(|(resolution, fetched)| match fetched {
+            Some(fetched) => commit(addon, &resolution, fetched, ctx, lock, events),
+            None => Ok(up_to_date(addon, &resolution)),
+        });
+        let result = SyncResult {
+            addon,
+            previous,
+            outcome,
+        };
+        if on_result(result, lock)?.is_break() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Removes an installed addon by looking up its recorded directories in the lock.
+///
+/// Each directory listed in `lock` for `addon_name` + `ctx.flavor` is removed
+/// from `ctx.addons_path`. The lock entry is then deleted.
+pub async fn remove(
+    addon_name: &str,
+    ctx: &InstallContext,
+    lock: &mut Lock,
+    events: &dyn EventSink,
+) -> Result<()> {
+    let entry = lock
+        .take(addon_name, &ctx.flavor)
+        .ok_or_else(|| crate::Error::AddonNotInLock {
+            name: addon_name.to_owned(),
+        })?;
+    let dirs = unowned_dirs(lock, &entry.installed_dirs);
+    tracing::debug!(name = %addon_name, ?dirs, "removing addon dirs");
+
+    events.emit(Event::Removing {
+        addon: addon_name.to_owned(),
+        dirs: dirs.clone(),
+    });
+    crate::fs::remove_addon_dirs(&dirs, &ctx.addons_path)?;
+
+    lock.generated_at = Utc::now();
+    events.emit(Event::LockUpdated {
+        addon: addon_name.to_owned(),
+    });
+    Ok(())
+}
+
+/// Undoes one journalled change on `ctx.addons_path` and `lock`.
+///
+/// A fresh install is removed; an update or removal is reverted by downloading the
+/// previously locked artifact again (verified against its recorded sha256) and
+/// dropping any dirs only the newer version had. Reverting an entry twice is
+/// harmless, so a failed roll-back can simply be retried. Entries
+/// [`check_revert`] rejects are refused before anything changes.
+pub async fn revert(
+    provider: &dyn Provider,
+    entry: &JournalEntry,
+    ctx: &InstallContext,
+    lock: &mut Lock,
+    events: &dyn EventSink,
+) -> Result<()> {
+    check_revert(entry)?;
+    let Some(previous) = &entry.previous else {
+        let dirs = lock
+            .take(&entry.name, &entry.flavor)
+            .or_else(|| entry.current.clone())
+            .map(|a| unowned_dirs(lock, &a.installed_dirs))
+            .unwrap_or_default();
+        events.emit(Event::Removing {
+            addon: entry.name.clone(),
+            dirs: dirs.clone(),
+        });
+        crate::fs::remove_addon_dirs(&dirs, &ctx.addons_path)?;
+        lock.generated_at = Utc::now();
+        events.emit(Event::LockUpdated {
+            addon: entry.name.clone(),
+        });
+        return Ok(());
+    };
 
+    // The locked artifact is fetched directly, so only the row's layout fields
+    // (GitHub `repo` / `asset_regex` / `git_ref`) matter.
+    let base = ManifestAddon {
+        name: previous.name.clone(),
+        provider: previous.provider.clone(),
+        channel: Some(previous.channel.clone()),
+        flavors: None,
+        pin: None,
+        project_id: previous.project_id,
+        wowi_id: None,
+        repo: None,
+        asset_regex: None,
+        git_ref: None,
+        url: None,
+    };
+    let addon = match revert_source(entry, previous) {
+        Some(row) => ManifestAddon {
+            wowi_id: row.wowi_id,
+            repo: row.repo.clone(),
+            asset_regex: row.asset_regex.clone(),
+            git_ref: row.git_ref.clone(),
+            url: row.url.clone(),
+            ..base
+        },
+        None => base,
+    };
+    // Restoring the previous state may legitimately re-share folders.
+    let ctx = InstallContext {
+        flavor: previous.flavor.clone(),
+        channel: previous.channel.clone(),
+        conflicts: ConflictPolicy::Share,
+        ..ctx.clone()
+    };
+    let resolution = Resolution {
+        artifact: ResolvedArtifact {
+            version: previous.resolved_version.clone(),
+            id: previous.resolved_id.clone(),
+            url: previous.download_url.clone(),
+            digest: previous.sha256.as_deref().map(Digest::sha256),
+        },
+        locked_version: entry.current.as_ref().map(|c| c.resolved_version.clone()),
+        up_to_date: false,
+    };
+    // `commit` drops the dirs only the newer version had.
+    apply(provider, &addon, &resolution, &ctx, lock, events).await?;
     Ok(())
 }
+
+/// Checks that [`revert`] can restore `entry`'s previous version, so a roll-back
+/// can be refused before it changes anything.
+///
+/// Fails with [`crate::Error::RollBackUnsupported`] when the previous version was
+/// adopted (nothing to download) or is a GitHub install journalled without its
+/// manifest row (a release asset and a repository snapshot unpack differently).
+pub fn check_revert(entry: &JournalEntry) -> Result<()> {
+    let Some(previous) = &entry.previous else {
+        return Ok(());
+    };
+    let reason = if previous.download_url.is_empty() {
+        Some("its previous version was adopted, so there is nothing to download")
+    } else if previous.provider == model::Provider::GitHub
+        && revert_source(entry, previous).is_none()
+    {
+        Some("the GitHub row its previous version came from was not journalled")
+    } else {
+        None
+    };
+    match reason {
+        Some(reason) => Err(crate::Error::RollBackUnsupported {
+            name: entry.name.clone(),
+            reason: reason.to_owned(),
+        }),
+        None => Ok(()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/// The journalled manifest row, when it describes the same provider as `previous`.
+fn revert_source<'a>(entry: &'a JournalEntry, previous: &LockedAddon) -> Option<&'a ManifestAddon> {
+    entry
+        .source
+        .as_ref()
+        .filter(|row| row.provider == previous.provider)
+}
+
+/// Returns the dirs in `dirs` that no entry left in `lock` owns, i.e. those safe to
+/// delete once their addon is gone.
+fn unowned_dirs(lock: &Lock, dirs: &[String]) -> Vec<String> {
+    dirs.iter()
+        .filter(|d| lock.owners(d).is_empty())
+        .cloned()
+        .collect()
+}
+
+fn up_to_date(addon: &ManifestAddon, resolution: &Resolution) -> InstallOutcome {
+    let artifact = &resolution.artifact;
+    tracing::debug!(name = %addon.name, id = %artifact.id, "already up to date");
+    InstallOutcome::UpToDate {
+        version: artifact.version.clone(),
+    }
+}
+
+/// Private staging dir under `cache_dir`; removed on drop, even on failure or cancellation.
+#[derive(Debug)]
+struct StagingDir(PathBuf);
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.0) {
+            tracing::debug!(error = %e, path = %self.0.display(), "failed to clean staging dir");
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::*;
 use crate::{
+    events::{Event, NullSink, Progress},
     lock::Lock,
     manifest::ManifestAddon,
     model::{Channel, Flavor, Provider as ModelProvider, Tag},
@@ -18,6 +19,8 @@ fn make_ctx(addons_path: PathBuf, cache_dir: PathBuf) -> InstallContext {
         channel: Channel::Stable,
         addons_path,
         cache_dir,
+        limits: Default::default(),
+        conflicts: Default::default(),
     }
 }
 
@@ -37,11 +40,59 @@ fn make_addon(name: &str, url: &str) -> ManifestAddon {
     }
 }
 
+/// A retail lock row for `name` at `version` that owns the folder `name`.
+fn locked(name: &str, version: &str) -> LockedAddon {
+    LockedAddon {
+        name: name.into(),
+        provider: ModelProvider::Local,
+        flavor: Flavor::Retail,
+        channel: Channel::Stable,
+        project_id: None,
+        resolved_version: version.into(),
+        resolved_id: format!("{name}@{version}"),
+        download_url: "unused".into(),
+        sha256: None,
+        installed_dirs: vec![name.into()],
+        installed_at: chrono::Utc::now(),
+    }
+}
+
 /// A minimal provider stub that serves a pre-built zip from a given path.
 struct ZipFileProvider {
     zip_path: PathBuf,
 }
 
+/// Like [`ZipFileProvider`], but advertises a fixed digest for the artifact.
+struct DigestProvider {
+    inner: ZipFileProvider,
+    digest: crate::checksum::Digest,
+}
+
+#[async_trait::async_trait]
+impl Provider for DigestProvider {
+    async fn resolve(
+        &self,
+        addon: &ManifestAddon,
+        ctx: &InstallContext,
+    ) -> crate::Result<ResolvedArtifact> {
+        let mut artifact = self.inner.resolve(addon, ctx).await?;
+        artifact.digest = Some(self.digest.clone());
+        Ok(artifact)
+    }
+
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        max_bytes: Option<u64>,
+        progress: &Progress<'_>,
+    ) -> crate::Result<()> {
+        self.inner
+            .download(artifact, dest, max_bytes, progress)
+            .await
+    }
+}
+
 #[async_trait::async_trait]
 impl Provider for ZipFileProvider {
     async fn resolve(
@@ -53,11 +104,17 @@ impl Provider for ZipFileProvider {
             version: "test".into(),
             id: "test:1".into(),
             url: self.zip_path.to_str().unwrap().to_owned(),
-            sha256: None,
+            digest: None,
         })
     }
 
-    async fn download(&self, _artifact: &ResolvedArtifact, dest: &Path) -> crate::Result<()> {
+    async fn download(
+        &self,
+        _artifact: &ResolvedArtifact,
+        dest: &Path,
+        _max_bytes: Option<u64>,
+        _progress: &Progress<'_>,
+    ) -> crate::Result<()> {
         fs::copy(&self.zip_path, dest)?;
         Ok(())
     }
@@ -97,7 +154,9 @@ async fn install_updates_lock_and_copies_dirs() {
     let ctx = make_ctx(addons_dir.clone(), cache_dir);
     let mut lock = Lock::new(Tag::new("test"));
 
-    install(&provider, &addon, &ctx, &mut lock).await.unwrap();
+    install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
 
     assert_eq!(lock.addon.len(), 1);
     let entry = &lock.addon[0];
@@ -107,6 +166,41 @@ async fn install_updates_lock_and_copies_dirs() {
     assert!(addons_dir.join("WeakAuras").join("WeakAuras.toc").exists());
 }
 
+#[tokio::test]
+async fn install_emits_events_in_pipeline_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(dir.path(), &[("Core/Core.toc", b"")]);
+    let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let seen = std::sync::Mutex::new(Vec::new());
+    let sink = |event: Event| seen.lock().unwrap().push(event);
+    let addon = make_addon("Core", zip_path.to_str().unwrap());
+    install(&UrlProvider, &addon, &ctx, &mut lock, &sink)
+        .await
+        .unwrap();
+
+    let seen = seen.into_inner().unwrap();
+    assert_eq!(
+        seen,
+        vec![
+            Event::ResolveStarted {
+                addon: "Core".into()
+            },
+            Event::Extracting {
+                addon: "Core".into()
+            },
+            Event::SwappingIn {
+                addon: "Core".into(),
+                dirs: vec!["Core".into()],
+            },
+            Event::LockUpdated {
+                addon: "Core".into()
+            },
+        ]
+    );
+}
+
 #[tokio::test]
 async fn install_twice_replaces_lock_entry() {
     let dir = tempfile::tempdir().unwrap();
@@ -126,8 +220,12 @@ async fn install_twice_replaces_lock_entry() {
     let ctx = make_ctx(addons_dir.clone(), cache_dir);
     let mut lock = Lock::new(Tag::new("test"));
 
-    install(&provider, &addon, &ctx, &mut lock).await.unwrap();
-    install(&provider, &addon, &ctx, &mut lock).await.unwrap();
+    install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
 
     assert_eq!(
         lock.addon.len(),
@@ -136,6 +234,287 @@ async fn install_twice_replaces_lock_entry() {
     );
 }
 
+/// Serves a pre-built zip under a caller-chosen artifact id and counts downloads.
+struct CountingProvider {
+    zip_path: PathBuf,
+    id: std::sync::Mutex<String>,
+    downloads: AtomicU64,
+}
+
+#[async_trait::async_trait]
+impl Provider for CountingProvider {
+    async fn resolve(
+        &self,
+        _addon: &ManifestAddon,
+        _ctx: &InstallContext,
+    ) -> crate::Result<ResolvedArtifact> {
+        let id = self.id.lock().unwrap().clone();
+        Ok(ResolvedArtifact {
+            version: format!("v{id}"),
+            id,
+            url: self.zip_path.to_str().unwrap().to_owned(),
+            digest: None,
+        })
+    }
+
+    async fn download(
+        &self,
+        _artifact: &ResolvedArtifact,
+        dest: &Path,
+        _max_bytes: Option<u64>,
+        _progress: &Progress<'_>,
+    ) -> crate::Result<()> {
+        self.downloads.fetch_add(1, Ordering::Relaxed);
+        fs::copy(&self.zip_path, dest)?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn install_skips_download_when_resolved_id_unchanged() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(
+        dir.path(),
+        &[("Same/", &[]), ("Same/Same.toc", b"## Interface: 110200\n")],
+    );
+    let provider = CountingProvider {
+        zip_path,
+        id: std::sync::Mutex::new("1".into()),
+        downloads: AtomicU64::new(0),
+    };
+    let addon = make_addon("Same", "placeholder");
+    let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let first = install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    assert_eq!(
+        first,
+        InstallOutcome::Installed {
+            version: "v1".into(),
+            skipped_externals: Vec::new(),
+        }
+    );
+
+    let second = install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    assert_eq!(
+        second,
+        InstallOutcome::UpToDate {
+            version: "v1".into()
+        }
+    );
+    assert_eq!(provider.downloads.load(Ordering::Relaxed), 1);
+
+    *provider.id.lock().unwrap() = "2".into();
+    let third = install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    assert_eq!(
+        third,
+        InstallOutcome::Updated {
+            from: "v1".into(),
+            to: "v2".into(),
+            skipped_externals: Vec::new(),
+        }
+    );
+    assert_eq!(provider.downloads.load(Ordering::Relaxed), 2);
+    assert_eq!(lock.addon[0].resolved_id, "2");
+}
+
+#[tokio::test]
+async fn check_reports_versions_without_installing() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(
+        dir.path(),
+        &[
+            ("Checked/", &[]),
+            ("Checked/Checked.toc", b"## Interface: 110200\n"),
+        ],
+    );
+    let provider = CountingProvider {
+        zip_path,
+        id: std::sync::Mutex::new("1".into()),
+        downloads: AtomicU64::new(0),
+    };
+    let addon = make_addon("Checked", "placeholder");
+    let addons_dir = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let fresh = check(&provider, &addon, &ctx, &lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(fresh.outdated);
+    assert!(fresh.installed.is_none());
+    assert!(!addons_dir.exists());
+
+    install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    *provider.id.lock().unwrap() = "2".into();
+
+    let behind = check(&provider, &addon, &ctx, &lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(behind.outdated);
+    assert_eq!(behind.installed.as_deref(), Some("v1"));
+    assert_eq!(behind.available, "v2");
+    assert_eq!(behind.channel, Channel::Stable);
+    assert_eq!(provider.downloads.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn adopted_entry_is_current_while_the_version_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    let provider = CountingProvider {
+        zip_path: dir.path().join("unused.zip"),
+        id: std::sync::Mutex::new("1".into()),
+        downloads: AtomicU64::new(0),
+    };
+    let addon = make_addon("Adopted", "placeholder");
+    let addons_dir = dir.path().join("AddOns");
+    fs::create_dir_all(addons_dir.join("Adopted")).unwrap();
+    let ctx = make_ctx(addons_dir, dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+    lock.upsert(LockedAddon {
+        resolved_id: crate::adopt::ADOPTED_ID.into(),
+        ..locked("Adopted", "v1")
+    });
+
+    let current = check(&provider, &addon, &ctx, &lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(!current.outdated);
+    let outcome = install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(matches!(outcome, InstallOutcome::UpToDate { .. }));
+    assert_eq!(provider.downloads.load(Ordering::Relaxed), 0);
+
+    *provider.id.lock().unwrap() = "2".into();
+    let behind = check(&provider, &addon, &ctx, &lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(behind.outdated);
+}
+
+#[tokio::test]
+async fn adopted_version_matches_the_provider_display_name() {
+    let dir = tempfile::tempdir().unwrap();
+    // CountingProvider reports version `v<id>`, here `vAddon-v1.2.3`.
+    let provider = CountingProvider {
+        zip_path: dir.path().join("unused.zip"),
+        id: std::sync::Mutex::new("Addon-v1.2.3".into()),
+        downloads: AtomicU64::new(0),
+    };
+    let addon = make_addon("Adopted", "placeholder");
+    let addons_dir = dir.path().join("AddOns");
+    fs::create_dir_all(addons_dir.join("Adopted")).unwrap();
+    let ctx = make_ctx(addons_dir, dir.path().join("cache"));
+
+    for adopted in ["v1.2.3", "1.2.3"] {
+        let mut lock = Lock::new(Tag::new("test"));
+        lock.upsert(LockedAddon {
+            resolved_id: crate::adopt::ADOPTED_ID.into(),
+            ..locked("Adopted", adopted)
+        });
+        let current = check(&provider, &addon, &ctx, &lock, &NullSink)
+            .await
+            .unwrap();
+        assert!(!current.outdated, "{adopted} should match");
+    }
+
+    let mut lock = Lock::new(Tag::new("test"));
+    lock.upsert(LockedAddon {
+        resolved_id: crate::adopt::ADOPTED_ID.into(),
+        ..locked("Adopted", "1.2")
+    });
+    let behind = check(&provider, &addon, &ctx, &lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(behind.outdated);
+}
+
+#[tokio::test]
+async fn update_removes_folders_the_new_version_dropped() {
+    let dir = tempfile::tempdir().unwrap();
+    let v1 = dir.path().join("v1");
+    let v2 = dir.path().join("v2");
+    fs::create_dir_all(&v1).unwrap();
+    fs::create_dir_all(&v2).unwrap();
+    let v1_zip = make_addon_zip(
+        &v1,
+        &[
+            ("Core/Core.toc", b"## Version: 1\n"),
+            ("Core_Old/Core_Old.toc", b""),
+            ("Shared/Shared.toc", b""),
+        ],
+    );
+    let v2_zip = make_addon_zip(&v2, &[("Core/Core.toc", b"## Version: 2\n")]);
+
+    let addons_dir = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+    install(
+        &UrlProvider,
+        &make_addon("Core", v1_zip.to_str().unwrap()),
+        &ctx,
+        &mut lock,
+        &NullSink,
+    )
+    .await
+    .unwrap();
+    // Another addon also claims `Shared`.
+    lock.upsert(LockedAddon {
+        installed_dirs: vec!["Shared".into()],
+        ..locked("Other", "1")
+    });
+
+    install(
+        &UrlProvider,
+        &make_addon("Core", v2_zip.to_str().unwrap()),
+        &ctx,
+        &mut lock,
+        &NullSink,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        lock.find("Core", &Flavor::Retail).unwrap().installed_dirs,
+        vec!["Core"]
+    );
+    assert!(!addons_dir.join("Core_Old").exists());
+    assert!(addons_dir.join("Shared").exists());
+}
+
+#[tokio::test]
+async fn resolve_not_up_to_date_when_dir_missing() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(
+        dir.path(),
+        &[("Gone/", &[]), ("Gone/Gone.toc", b"## Interface: 110200\n")],
+    );
+    let provider = ZipFileProvider { zip_path };
+    let addon = make_addon("Gone", "placeholder");
+    let addons_dir = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    fs::remove_dir_all(addons_dir.join("Gone")).unwrap();
+
+    let resolution = resolve(&provider, &addon, &ctx, &lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(!resolution.up_to_date);
+    assert_eq!(resolution.locked_version.as_deref(), Some("test"));
+}
+
 #[tokio::test]
 async fn install_fails_when_zip_has_no_addon_dirs() {
     let dir = tempfile::tempdir().unwrap();
@@ -149,7 +528,7 @@ async fn install_fails_when_zip_has_no_addon_dirs() {
     let ctx = make_ctx(addons_dir, cache_dir);
     let mut lock = Lock::new(Tag::new("test"));
 
-    let result = install(&provider, &addon, &ctx, &mut lock).await;
+    let result = install(&provider, &addon, &ctx, &mut lock, &NullSink).await;
     assert!(matches!(
         result,
         Err(crate::Error::NoInstallableDirs { .. })
@@ -157,6 +536,286 @@ async fn install_fails_when_zip_has_no_addon_dirs() {
     assert!(lock.addon.is_empty());
 }
 
+#[tokio::test]
+async fn install_records_computed_sha256() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(
+        dir.path(),
+        &[
+            ("Hashed/", &[]),
+            ("Hashed/Hashed.toc", b"## Interface: 110200\n"),
+        ],
+    );
+    let expected =
+        crate::checksum::hash_file(&zip_path, crate::checksum::Algorithm::Sha256).unwrap();
+
+    let provider = DigestProvider {
+        digest: crate::checksum::Digest::sha1(
+            crate::checksum::hash_file(&zip_path, crate::checksum::Algorithm::Sha1).unwrap(),
+        ),
+        inner: ZipFileProvider { zip_path },
+    };
+    let addon = make_addon("Hashed", "placeholder");
+    let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    assert_eq!(lock.addon[0].sha256.as_deref(), Some(expected.as_str()));
+}
+
+#[tokio::test]
+async fn install_refuses_checksum_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(
+        dir.path(),
+        &[
+            ("Tampered/", &[]),
+            ("Tampered/Tampered.toc", b"## Interface: 110200\n"),
+        ],
+    );
+
+    let addons_dir = dir.path().join("AddOns");
+    let provider = DigestProvider {
+        inner: ZipFileProvider { zip_path },
+        digest: crate::checksum::Digest::md5("00".repeat(16)),
+    };
+    let addon = make_addon("Tampered", "placeholder");
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let result = install(&provider, &addon, &ctx, &mut lock, &NullSink).await;
+    assert!(matches!(result, Err(crate::Error::ChecksumMismatch { .. })));
+    assert!(lock.addon.is_empty());
+    assert!(!addons_dir.join("Tampered").exists());
+}
+
+// ---------------------------------------------------------------------------
+// git snapshots (.pkgmeta)
+// ---------------------------------------------------------------------------
+
+/// Serves a different snapshot zip per `repo`, standing in for GitHub zipballs,
+/// and records every `repo@ref` it resolves.
+#[cfg(feature = "github")]
+#[derive(Default)]
+struct RepoZipProvider {
+    zips: HashMap<String, PathBuf>,
+    resolved: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(feature = "github")]
+#[async_trait::async_trait]
+impl Provider for RepoZipProvider {
+    async fn resolve(
+        &self,
+        addon: &ManifestAddon,
+        _ctx: &InstallContext,
+    ) -> crate::Result<ResolvedArtifact> {
+        let repo = addon.repo.clone().unwrap();
+        let id = format!("{repo}@{}", addon.git_ref.as_deref().unwrap());
+        self.resolved.lock().unwrap().push(id.clone());
+        Ok(ResolvedArtifact {
+            version: "abc1234".into(),
+            id,
+            url: self.zips[&repo].to_str().unwrap().to_owned(),
+            digest: None,
+        })
+    }
+
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        _max_bytes: Option<u64>,
+        _progress: &Progress<'_>,
+    ) -> crate::Result<()> {
+        fs::copy(&artifact.url, dest)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "github")]
+#[tokio::test]
+async fn install_git_snapshot_applies_pkgmeta_and_externals() {
+    let dir = tempfile::tempdir().unwrap();
+    let main_zip = dir.path().join("main.zip");
+    fs::write(
+        &main_zip,
+        crate::fs::make_test_zip(&[
+            (
+                "owner-MyAddon-abc1234/.pkgmeta",
+                b"package-as: MyAddon\n\
+                  ignore:\n  - README.md\n\
+                  externals:\n  Libs/LibFoo: https://github.com/lib/LibFoo\n\
+                  \x20 Libs/LibBar:\n    url: https://github.com/lib/LibBar\n    tag: latest\n\
+                  \x20 Libs/LibStub: https://repos.wowace.com/wow/libstub/trunk\n\
+                  move-folders:\n  MyAddon/Options: MyAddon_Options\n",
+            ),
+            ("owner-MyAddon-abc1234/.github/workflows/ci.yml", b""),
+            ("owner-MyAddon-abc1234/README.md", b"# MyAddon"),
+            (
+                "owner-MyAddon-abc1234/MyAddon.toc",
+                b"## Interface: 110200\n",
+            ),
+            (
+                "owner-MyAddon-abc1234/Options/MyAddon_Options.toc",
+                b"## Interface: 110200\n",
+            ),
+        ]),
+    )
+    .unwrap();
+    let lib_zip = dir.path().join("lib.zip");
+    fs::write(
+        &lib_zip,
+        crate::fs::make_test_zip(&[
+            ("lib-LibFoo-def5678/LibFoo.lua", b"-- lib"),
+            ("lib-LibFoo-def5678/.gitignore", b""),
+        ]),
+    )
+    .unwrap();
+
+    let provider = RepoZipProvider {
+        zips: HashMap::from([
+            ("owner/MyAddon".to_owned(), main_zip),
+            ("lib/LibFoo".to_owned(), lib_zip.clone()),
+            ("lib/LibBar".to_owned(), lib_zip),
+        ]),
+        ..Default::default()
+    };
+    let addon = ManifestAddon {
+        name: "my-addon".into(),
+        provider: ModelProvider::GitHub,
+        repo: Some("owner/MyAddon".into()),
+        git_ref: Some("main".into()),
+        url: None,
+        ..make_addon("my-addon", "")
+    };
+    let addons_path = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_path.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let outcome = install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    let InstallOutcome::Installed {
+        skipped_externals, ..
+    } = outcome
+    else {
+        panic!("expected a fresh install, got {outcome:?}");
+    };
+    assert_eq!(
+        skipped_externals,
+        vec![SkippedExternal {
+            path: "Libs/LibStub".into(),
+            url: "https://repos.wowace.com/wow/libstub/trunk".into(),
+        }]
+    );
+
+    let entry = lock.find("my-addon", &Flavor::Retail).unwrap();
+    assert_eq!(entry.installed_dirs, vec!["MyAddon", "MyAddon_Options"]);
+    assert!(addons_path.join("MyAddon/MyAddon.toc").exists());
+    assert!(addons_path.join("MyAddon/Libs/LibFoo/LibFoo.lua").exists());
+    // `tag: latest` is not a ref GitHub knows; the default branch is fetched.
+    assert!(addons_path.join("MyAddon/Libs/LibBar/LibFoo.lua").exists());
+    assert_eq!(
+        *provider.resolved.lock().unwrap(),
+        vec!["owner/MyAddon@main", "lib/LibBar@HEAD", "lib/LibFoo@HEAD"]
+    );
+    assert!(
+        addons_path
+            .join("MyAddon_Options/MyAddon_Options.toc")
+            .exists()
+    );
+    for gone in [
+        "MyAddon/.pkgmeta",
+        "MyAddon/.github",
+        "MyAddon/README.md",
+        "MyAddon/Options",
+        "MyAddon/Libs/LibFoo/.gitignore",
+    ] {
+        assert!(
+            !addons_path.join(gone).exists(),
+            "{gone} should not be installed"
+        );
+    }
+}
+
+#[cfg(feature = "github")]
+#[tokio::test]
+async fn revert_lays_a_git_snapshot_out_again() {
+    let dir = tempfile::tempdir().unwrap();
+    let main_zip = dir.path().join("main.zip");
+    fs::write(
+        &main_zip,
+        crate::fs::make_test_zip(&[
+            (
+                "owner-MyAddon-abc1234/.pkgmeta",
+                b"move-folders:\n  MyAddon/Options: MyAddon_Options\n",
+            ),
+            (
+                "owner-MyAddon-abc1234/MyAddon.toc",
+                b"## Interface: 110200\n",
+            ),
+            (
+                "owner-MyAddon-abc1234/Options/MyAddon_Options.toc",
+                b"## Interface: 110200\n",
+            ),
+        ]),
+    )
+    .unwrap();
+    let provider = RepoZipProvider {
+        zips: HashMap::from([("owner/MyAddon".to_owned(), main_zip)]),
+        ..Default::default()
+    };
+    let addon = ManifestAddon {
+        name: "my-addon".into(),
+        provider: ModelProvider::GitHub,
+        repo: Some("owner/MyAddon".into()),
+        git_ref: Some("main".into()),
+        url: None,
+        ..make_addon("my-addon", "")
+    };
+    let addons_path = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_path.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+    install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+
+    // A newer version that dropped the options folder.
+    let previous = lock.find("my-addon", &Flavor::Retail).cloned();
+    let current = LockedAddon {
+        resolved_id: "owner/MyAddon@next".into(),
+        installed_dirs: vec!["MyAddon".into()],
+        ..previous.clone().unwrap()
+    };
+    fs::remove_dir_all(addons_path.join("MyAddon_Options")).unwrap();
+    lock.upsert(current.clone());
+    let entry = JournalEntry::new("my-addon", &Flavor::Retail, previous.clone(), Some(current));
+
+    // Without the manifest row a snapshot cannot be told from a release asset.
+    let err = revert(&provider, &entry, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, crate::Error::RollBackUnsupported { .. }));
+
+    let entry = entry.with_source(&addon);
+    revert(&provider, &entry, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    let restored = lock.find("my-addon", &Flavor::Retail).unwrap();
+    assert_eq!(restored.resolved_id, previous.unwrap().resolved_id);
+    assert_eq!(restored.installed_dirs, vec!["MyAddon", "MyAddon_Options"]);
+    assert!(
+        addons_path
+            .join("MyAddon_Options/MyAddon_Options.toc")
+            .exists()
+    );
+    assert!(!addons_path.join("MyAddon/Options").exists());
+    assert!(!addons_path.join("MyAddon/.pkgmeta").exists());
+}
+
 // ---------------------------------------------------------------------------
 // remove
 // ---------------------------------------------------------------------------
@@ -193,21 +852,110 @@ async fn remove_deletes_dirs_and_updates_lock() {
         channel: Channel::Stable,
         addons_path: addons_dir.clone(),
         cache_dir: dir.path().join("cache"),
+        limits: Default::default(),
+        conflicts: Default::default(),
     };
 
-    remove("Questie", &ctx, &mut lock).await.unwrap();
+    remove("Questie", &ctx, &mut lock, &NullSink).await.unwrap();
 
     assert!(lock.addon.is_empty());
     assert!(!addons_dir.join("Questie").exists());
 }
 
+#[tokio::test]
+async fn install_refuses_folders_owned_by_another_addon() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(
+        dir.path(),
+        &[
+            ("Plugin/Plugin.toc", b"## Interface: 110200\n"),
+            ("LibShared/LibShared.toc", b"## Interface: 110200\n"),
+        ],
+    );
+    let provider = ZipFileProvider { zip_path };
+    let addons_path = dir.path().join("AddOns");
+    fs::create_dir_all(addons_path.join("LibShared")).unwrap();
+    fs::write(addons_path.join("LibShared/pack-copy.lua"), b"").unwrap();
+
+    let mut lock = Lock::new(Tag::new("test"));
+    lock.upsert(LockedAddon {
+        installed_dirs: vec!["Pack".into(), "LibShared".into()],
+        ..locked("Pack", "1.0")
+    });
+
+    let ctx = make_ctx(addons_path.clone(), dir.path().join("cache"));
+    let addon = make_addon("Plugin", "unused");
+    let err = install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap_err();
+
+    let crate::Error::FolderConflict { claims, .. } = err else {
+        panic!("expected FolderConflict, got {err:?}");
+    };
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].folder, "LibShared");
+    assert_eq!(claims[0].owners, vec!["Pack"]);
+    assert!(lock.find("Plugin", &Flavor::Retail).is_none());
+    assert!(addons_path.join("LibShared/pack-copy.lua").exists());
+    assert!(!addons_path.join("Plugin").exists());
+}
+
+#[tokio::test]
+async fn shared_folder_survives_until_last_owner_is_removed() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(
+        dir.path(),
+        &[
+            ("Plugin/Plugin.toc", b"## Interface: 110200\n"),
+            ("LibShared/LibShared.toc", b"## Interface: 110200\n"),
+        ],
+    );
+    let provider = ZipFileProvider { zip_path };
+    let addons_path = dir.path().join("AddOns");
+    for d in ["Pack", "LibShared"] {
+        fs::create_dir_all(addons_path.join(d)).unwrap();
+    }
+
+    let mut lock = Lock::new(Tag::new("test"));
+    lock.upsert(LockedAddon {
+        installed_dirs: vec!["Pack".into(), "LibShared".into()],
+        ..locked("Pack", "1.0")
+    });
+
+    let ctx = InstallContext {
+        conflicts: ConflictPolicy::Share,
+        ..make_ctx(addons_path.clone(), dir.path().join("cache"))
+    };
+    install(
+        &provider,
+        &make_addon("Plugin", "unused"),
+        &ctx,
+        &mut lock,
+        &NullSink,
+    )
+    .await
+    .unwrap();
+    assert_eq!(lock.owners("LibShared").len(), 2);
+
+    remove("Pack", &ctx, &mut lock, &NullSink).await.unwrap();
+    assert!(!addons_path.join("Pack").exists());
+    assert!(
+        addons_path.join("LibShared").exists(),
+        "Plugin still owns it"
+    );
+
+    remove("Plugin", &ctx, &mut lock, &NullSink).await.unwrap();
+    assert!(!addons_path.join("LibShared").exists());
+    assert!(!addons_path.join("Plugin").exists());
+}
+
 #[tokio::test]
 async fn remove_returns_error_when_not_in_lock() {
     let dir = tempfile::tempdir().unwrap();
     let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
     let mut lock = Lock::new(Tag::new("test"));
 
-    let result = remove("NoSuchAddon", &ctx, &mut lock).await;
+    let result = remove("NoSuchAddon", &ctx, &mut lock, &NullSink).await;
     assert!(matches!(result, Err(crate::Error::AddonNotInLock { .. })));
 }
 
@@ -233,6 +981,340 @@ async fn remove_tolerates_missing_dir() {
     });
 
     let ctx = make_ctx(addons_dir, dir.path().join("cache"));
-    remove("Gone", &ctx, &mut lock).await.unwrap();
+    remove("Gone", &ctx, &mut lock, &NullSink).await.unwrap();
     assert!(lock.addon.is_empty());
 }
+
+// ---------------------------------------------------------------------------
+// revert
+// ---------------------------------------------------------------------------
+
+/// Serves whatever zip the manifest row's `url` points at; the id is the url.
+struct UrlProvider;
+
+#[async_trait::async_trait]
+impl Provider for UrlProvider {
+    async fn resolve(
+        &self,
+        addon: &ManifestAddon,
+        _ctx: &InstallContext,
+    ) -> crate::Result<ResolvedArtifact> {
+        let url = addon.url.clone().unwrap();
+        Ok(ResolvedArtifact {
+            version: url.clone(),
+            id: url.clone(),
+            url,
+            digest: None,
+        })
+    }
+
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        _max_bytes: Option<u64>,
+        _progress: &Progress<'_>,
+    ) -> crate::Result<()> {
+        fs::copy(&artifact.url, dest)?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn revert_update_restores_previous_artifact() {
+    let dir = tempfile::tempdir().unwrap();
+    let v1 = dir.path().join("v1");
+    let v2 = dir.path().join("v2");
+    fs::create_dir_all(&v1).unwrap();
+    fs::create_dir_all(&v2).unwrap();
+    let v1_zip = make_addon_zip(&v1, &[("Core/Core.toc", b"## Version: 1\n")]);
+    let v2_zip = make_addon_zip(
+        &v2,
+        &[
+            ("Core/Core.toc", b"## Version: 2\n"),
+            ("Extra/Extra.toc", b"## Version: 2\n"),
+        ],
+    );
+
+    let addons_dir = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    install(
+        &UrlProvider,
+        &make_addon("Core", v1_zip.to_str().unwrap()),
+        &ctx,
+        &mut lock,
+        &NullSink,
+    )
+    .await
+    .unwrap();
+    let previous = lock.find("Core", &Flavor::Retail).cloned();
+    install(
+        &UrlProvider,
+        &make_addon("Core", v2_zip.to_str().unwrap()),
+        &ctx,
+        &mut lock,
+        &NullSink,
+    )
+    .await
+    .unwrap();
+    let current = lock.find("Core", &Flavor::Retail).cloned();
+    let entry = JournalEntry::new("Core", &Flavor::Retail, previous, current);
+
+    revert(&UrlProvider, &entry, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+
+    let locked = lock.find("Core", &Flavor::Retail).unwrap();
+    assert_eq!(locked.resolved_id, v1_zip.to_str().unwrap());
+    assert_eq!(locked.installed_dirs, vec!["Core"]);
+    let toc = fs::read_to_string(addons_dir.join("Core/Core.toc")).unwrap();
+    assert!(toc.contains("Version: 1"));
+    assert!(!addons_dir.join("Extra").exists());
+}
+
+#[tokio::test]
+async fn revert_fresh_install_removes_addon() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(dir.path(), &[("Core/Core.toc", b"")]);
+    let addons_dir = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    install(
+        &UrlProvider,
+        &make_addon("Core", zip_path.to_str().unwrap()),
+        &ctx,
+        &mut lock,
+        &NullSink,
+    )
+    .await
+    .unwrap();
+    let current = lock.find("Core", &Flavor::Retail).cloned();
+    let entry = JournalEntry::new("Core", &Flavor::Retail, None, current);
+
+    revert(&UrlProvider, &entry, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(lock.addon.is_empty());
+    assert!(!addons_dir.join("Core").exists());
+
+    // Reverting again is a no-op.
+    revert(&UrlProvider, &entry, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn revert_refuses_an_adopted_previous_version_up_front() {
+    let dir = tempfile::tempdir().unwrap();
+    let zip_path = make_addon_zip(dir.path(), &[("Core/Core.toc", b"## Version: 2\n")]);
+    let addons_dir = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let previous = LockedAddon {
+        resolved_id: crate::adopt::ADOPTED_ID.into(),
+        download_url: String::new(),
+        ..locked("Core", "1")
+    };
+    lock.upsert(previous.clone());
+    install(
+        &UrlProvider,
+        &make_addon("Core", zip_path.to_str().unwrap()),
+        &ctx,
+        &mut lock,
+        &NullSink,
+    )
+    .await
+    .unwrap();
+    let current = lock.find("Core", &Flavor::Retail).cloned();
+    let entry = JournalEntry::new("Core", &Flavor::Retail, Some(previous), current.clone());
+
+    assert!(matches!(
+        check_revert(&entry),
+        Err(crate::Error::RollBackUnsupported { .. })
+    ));
+    let err = revert(&UrlProvider, &entry, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, crate::Error::RollBackUnsupported { .. }));
+    // Nothing was touched.
+    assert_eq!(
+        lock.find("Core", &Flavor::Retail).unwrap().resolved_id,
+        current.unwrap().resolved_id
+    );
+    assert!(addons_dir.join("Core/Core.toc").exists());
+}
+
+// ---------------------------------------------------------------------------
+// sync
+// ---------------------------------------------------------------------------
+
+/// [`UrlProvider`] that records how many resolves run at the same time.
+struct GaugedProvider {
+    in_flight: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    peak: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl Provider for GaugedProvider {
+    async fn resolve(
+        &self,
+        addon: &ManifestAddon,
+        ctx: &InstallContext,
+    ) -> crate::Result<ResolvedArtifact> {
+        use std::sync::atomic::Ordering::SeqCst;
+        let now = self.in_flight.fetch_add(1, SeqCst) + 1;
+        self.peak.fetch_max(now, SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        self.in_flight.fetch_sub(1, SeqCst);
+        UrlProvider.resolve(addon, ctx).await
+    }
+
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        max_bytes: Option<u64>,
+        progress: &Progress<'_>,
+    ) -> crate::Result<()> {
+        UrlProvider
+            .download(artifact, dest, max_bytes, progress)
+            .await
+    }
+}
+
+fn make_addon_zips(dir: &Path, names: &[&str]) -> Vec<ManifestAddon> {
+    names
+        .iter()
+        .map(|name| {
+            let sub = dir.join(name);
+            fs::create_dir_all(&sub).unwrap();
+            let toc = format!("{name}/{name}.toc");
+            let zip = make_addon_zip(&sub, &[(toc.as_str(), b"")]);
+            make_addon(name, zip.to_str().unwrap())
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn sync_commits_every_job_and_reports_each() {
+    let dir = tempfile::tempdir().unwrap();
+    let addons = make_addon_zips(dir.path(), &["Alpha", "Beta", "Gamma"]);
+    let addons_dir = dir.path().join("AddOns");
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let jobs = addons
+        .iter()
+        .map(|addon| SyncJob {
+            addon,
+            provider: Box::new(UrlProvider),
+        })
+        .collect();
+    let mut reported = Vec::new();
+    sync(
+        jobs,
+        &ctx,
+        &mut lock,
+        &Concurrency::default(),
+        &NullSink,
+        |result, lock| {
+            assert!(matches!(
+                result.outcome,
+                Ok(InstallOutcome::Installed { .. })
+            ));
+            assert!(lock.find(&result.addon.name, &Flavor::Retail).is_some());
+            reported.push(result.addon.name.clone());
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .await
+    .unwrap();
+
+    reported.sort();
+    assert_eq!(reported, vec!["Alpha", "Beta", "Gamma"]);
+    assert_eq!(lock.addon.len(), 3);
+    for name in ["Alpha", "Beta", "Gamma"] {
+        assert!(addons_dir.join(name).is_dir());
+    }
+    assert_eq!(
+        fs::read_dir(ctx.cache_dir.join("staging")).unwrap().count(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn sync_caps_jobs_in_flight_per_provider() {
+    let dir = tempfile::tempdir().unwrap();
+    let addons = make_addon_zips(dir.path(), &["A", "B", "C", "D", "E"]);
+    let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let in_flight = std::sync::Arc::default();
+    let peak = std::sync::Arc::<std::sync::atomic::AtomicUsize>::default();
+    let jobs = addons
+        .iter()
+        .map(|addon| SyncJob {
+            addon,
+            provider: Box::new(GaugedProvider {
+                in_flight: std::sync::Arc::clone(&in_flight),
+                peak: std::sync::Arc::clone(&peak),
+            }),
+        })
+        .collect();
+    let concurrency = Concurrency {
+        default: 8,
+        per_provider: [(ModelProvider::Local, 2)].into(),
+    };
+    sync(jobs, &ctx, &mut lock, &concurrency, &NullSink, |_, _| {
+        Ok(ControlFlow::Continue(()))
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(lock.addon.len(), 5);
+    assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn sync_break_drops_remaining_jobs() {
+    let dir = tempfile::tempdir().unwrap();
+    let addons = make_addon_zips(dir.path(), &["A", "B", "C"]);
+    let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    let jobs = addons
+        .iter()
+        .map(|addon| SyncJob {
+            addon,
+            provider: Box::new(UrlProvider),
+        })
+        .collect();
+    let concurrency = Concurrency {
+        default: 1,
+        per_provider: Default::default(),
+    };
+    let mut calls = 0;
+    sync(jobs, &ctx, &mut lock, &concurrency, &NullSink, |_, _| {
+        calls += 1;
+        Ok(ControlFlow::Break(()))
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(calls, 1);
+    assert_eq!(lock.addon.len(), 1);
+}
+
+#[test]
+fn concurrency_limit_falls_back_and_never_reaches_zero() {
+    let concurrency = Concurrency {
+        default: 3,
+        per_provider: [(ModelProvider::GitHub, 0)].into(),
+    };
+    assert_eq!(concurrency.limit(&ModelProvider::CurseForge), 3);
+    assert_eq!(concurrency.limit(&ModelProvider::GitHub), 1);
+}
@@ -0,0 +1,277 @@
+//! `.pkgmeta` support for raw git snapshots.
+//!
+//! Addon authors describe their release layout in a `.pkgmeta` file that the
+//! BigWigs packager applies when building release zips. A GitHub `git_ref` install
+//! downloads the repository as-is, so [`crate::ops`] replays the relevant rules on
+//! the extracted tree before it is swapped in:
+//!
+//! 1. [`PkgMeta::prune`] drops dotfiles (`.github`, `.pkgmeta`, …) and `ignore` paths;
+//! 2. `externals` are fetched into place by the caller through the provider layer
+//!    ([`External::github_repo`] / [`External::git_ref`]);
+//! 3. [`PkgMeta::arrange`] applies `move-folders` and names the package dir after
+//!    `package-as` (or its `.toc` basename).
+//!
+//! Other keys (changelog, license, nolib, …) only affect packaging and are ignored.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::Result;
+
+#[cfg(test)]
+mod tests;
+
+/// Filenames the packager accepts, in lookup order.
+const FILE_NAMES: &[&str] = &[".pkgmeta", ".pkgmeta.yaml", "pkgmeta.yaml"];
+
+/// The subset of a `.pkgmeta` file that shapes the installed tree.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PkgMeta {
+    /// Folder name the repository root is packaged as.
+    pub package_as: Option<String>,
+    /// Paths (relative to the repository root) to leave out; `*` and `?` wildcards.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Target path (relative to the repository root) → external checkout.
+    #[serde(default)]
+    pub externals: BTreeMap<String, External>,
+    /// `<package-as>/path/in/repo` → top-level folder it is shipped as.
+    #[serde(default)]
+    pub move_folders: BTreeMap<String, String>,
+}
+
+/// One `externals` entry: either a bare URL or a table with a ref.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum External {
+    Url(String),
+    Detailed {
+        url: String,
+        tag: Option<String>,
+        branch: Option<String>,
+        commit: Option<String>,
+    },
+}
+
+impl External {
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Url(url) | Self::Detailed { url, .. } => url,
+        }
+    }
+
+    /// Returns `owner/repo` when the URL points at a GitHub repository.
+    pub fn github_repo(&self) -> Option<String> {
+        let rest = self
+            .url()
+            .trim_end_matches('/')
+            .split_once("github.com/")?
+            .1;
+        let mut parts = rest.split('/');
+        let owner = parts.next().filter(|s| !s.is_empty())?;
+        let repo = parts.next().filter(|s| !s.is_empty())?;
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        Some(format!("{owner}/{repo}"))
+    }
+
+    /// Returns the ref to check out: `tag`, then `commit`, then `branch`, else `HEAD`.
+    ///
+    /// `tag: latest` asks the packager for the newest tag, which a plain ref cannot
+    /// name; it falls through to `commit` / `branch` / `HEAD` like an unset tag
+    /// (see [`Self::wants_latest_tag`]).
+    pub fn git_ref(&self) -> &str {
+        match self {
+            Self::Url(_) => "HEAD",
+            Self::Detailed {
+                tag,
+                branch,
+                commit,
+                ..
+            } => tag
+                .as_deref()
+                .filter(|_| !self.wants_latest_tag())
+                .or(commit.as_deref())
+                .or(branch.as_deref())
+                .unwrap_or("HEAD"),
+        }
+    }
+
+    /// Returns `true` for `tag: latest`, the packager's "newest tag" shorthand.
+    pub fn wants_latest_tag(&self) -> bool {
+        matches!(self, Self::Detailed { tag: Some(tag), .. } if tag == LATEST_TAG)
+    }
+}
+
+/// `tag` value the packager reads as "the repository's newest tag".
+const LATEST_TAG: &str = "latest";
+
+/// Loads the `.pkgmeta` directly inside `root`, if there is one.
+pub fn load(root: &Path) -> Result<Option<PkgMeta>> {
+    let Some(path) = FILE_NAMES
+        .iter()
+        .map(|name| root.join(name))
+        .find(|p| p.is_file())
+    else {
+        return Ok(None);
+    };
+    let content = fs::read_to_string(&path)?;
+    parse(&content)
+        .map(Some)
+        .map_err(|message| crate::Error::InvalidPkgMeta { path, message })
+}
+
+/// Parses `.pkgmeta` YAML, returning the parser message on failure.
+pub fn parse(content: &str) -> std::result::Result<PkgMeta, String> {
+    // An empty file is valid and means "package everything as-is".
+    if content.trim().is_empty() {
+        return Ok(PkgMeta::default());
+    }
+    let meta: PkgMeta = serde_yaml_ng::from_str(content).map_err(|e| e.to_string())?;
+
+    let paths = meta
+        .externals
+        .keys()
+        .chain(meta.move_folders.keys())
+        .chain(meta.move_folders.values())
+        .chain(&meta.package_as);
+    for path in paths {
+        if !is_contained(path) {
+            return Err(format!("path '{path}' escapes the package"));
+        }
+    }
+    Ok(meta)
+}
+
+/// Returns `true` if `rel` is a non-empty relative path that stays inside its base.
+fn is_contained(rel: &str) -> bool {
+    use std::path::Component;
+    let path = Path::new(rel.trim_end_matches('/'));
+    path.components().next().is_some()
+        && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+impl PkgMeta {
+    /// Removes dotfiles and every path matching an `ignore` pattern under `root`.
+    pub fn prune(&self, root: &Path) -> Result<()> {
+        let patterns: Vec<&str> = self
+            .ignore
+            .iter()
+            .map(|p| p.trim_matches('/'))
+            .filter(|p| !p.is_empty())
+            .collect();
+        prune_dir(root, root, &patterns)
+    }
+
+    /// Applies `move-folders` and `package-as` to the repository checkout at `root`,
+    /// moving the resulting addon dirs next to it in `out`.
+    ///
+    /// Returns the package dir's final path. A `move-folders` source that does not
+    /// exist is skipped with a warning, as the packager does.
+    pub fn arrange(&self, root: &Path, out: &Path) -> Result<PathBuf> {
+        for (from, to) in &self.move_folders {
+            let Some(src) = self.move_source(root, from) else {
+                tracing::warn!(from = %from, "move-folders source not found; skipping");
+                continue;
+            };
+            let dest = out.join(to.trim_matches('/'));
+            if dest.exists() {
+                fs::remove_dir_all(&dest)?;
+            }
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(&src, &dest)?;
+            tracing::debug!(from = %from, to = %to, "moved folder");
+        }
+
+        let name = self
+            .package_as
+            .clone()
+            .or_else(|| crate::fs::toc_addon_names(root).into_iter().next());
+        match name {
+            Some(name) if out.join(&name) != root => {
+                let dest = out.join(&name);
+                fs::rename(root, &dest)?;
+                Ok(dest)
+            }
+            _ => Ok(root.to_path_buf()),
+        }
+    }
+
+    /// Resolves a `move-folders` key to a path under `root`.
+    ///
+    /// Keys are written as `<package-as>/sub/dir`; the leading component is tried
+    /// both as part of the path and as the package name.
+    fn move_source(&self, root: &Path, from: &str) -> Option<PathBuf> {
+        let from = from.trim_matches('/');
+        let without_package = from.split_once('/').map(|(_, rest)| rest);
+        [Some(from), without_package]
+            .into_iter()
+            .flatten()
+            .map(|rel| root.join(rel))
+            .find(|p| p.exists())
+    }
+}
+
+/// Recursively removes entries under `dir` that are dotfiles or match `patterns`
+/// (by their `/`-separated path relative to `root`).
+fn prune_dir(root: &Path, dir: &Path, patterns: &[&str]) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let is_dir = entry.file_type()?.is_dir();
+
+        if name.to_string_lossy().starts_with('.') || patterns.iter().any(|p| glob_match(p, &rel)) {
+            if is_dir {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+            tracing::debug!(path = %rel, "pruned");
+        } else if is_dir {
+            prune_dir(root, &path, patterns)?;
+        }
+    }
+    Ok(())
+}
+
+/// Matches `text` against a shell-style `pattern`: `*` matches any run of
+/// characters (including `/`, like the packager's bash patterns), `?` exactly one.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let (p, t): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        match p.get(pi) {
+            Some('*') => {
+                star = Some((pi, ti));
+                pi += 1;
+            }
+            Some(&c) if c == '?' || c == t[ti] => {
+                pi += 1;
+                ti += 1;
+            }
+            _ => match star {
+                Some((sp, st)) => {
+                    pi = sp + 1;
+                    ti = st + 1;
+                    star = Some((sp, st + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
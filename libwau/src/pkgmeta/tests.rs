@@ -0,0 +1,162 @@
+use super::*;
+
+const FIXTURE: &str = r#"
+package-as: MyAddon
+
+ignore:
+  - README.md
+  - Tests
+  - "*.psd"
+
+externals:
+  Libs/LibStub: https://repos.wowace.com/wow/libstub/trunk
+  Libs/AceAddon-3.0:
+    url: https://github.com/WoWUIDev/Ace3.git
+    tag: Release-r1341
+  Libs/LibDBIcon-1.0:
+    url: https://github.com/WoWUIDev/LibDBIcon-1.0
+    tag: latest
+
+move-folders:
+  MyAddon/Modules/Options: MyAddon_Options
+
+enable-nolib-creation: no
+manual-changelog: CHANGELOG.md
+"#;
+
+fn touch(path: &Path) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, b"").unwrap();
+}
+
+#[test]
+fn parse_reads_layout_keys_and_ignores_the_rest() {
+    let meta = parse(FIXTURE).unwrap();
+    assert_eq!(meta.package_as.as_deref(), Some("MyAddon"));
+    assert_eq!(meta.ignore, vec!["README.md", "Tests", "*.psd"]);
+    assert_eq!(meta.externals.len(), 3);
+    assert_eq!(
+        meta.move_folders
+            .get("MyAddon/Modules/Options")
+            .map(String::as_str),
+        Some("MyAddon_Options")
+    );
+}
+
+#[test]
+fn parse_accepts_empty_file() {
+    let meta = parse("  \n").unwrap();
+    assert!(meta.package_as.is_none());
+    assert!(meta.externals.is_empty());
+}
+
+#[test]
+fn parse_rejects_malformed_yaml() {
+    assert!(parse("ignore: [unterminated").is_err());
+}
+
+#[test]
+fn parse_rejects_paths_escaping_the_package() {
+    assert!(parse("externals:\n  ../../evil: https://github.com/a/b").is_err());
+    assert!(parse("move-folders:\n  A/B: /etc").is_err());
+    assert!(parse("package-as: ..").is_err());
+}
+
+#[test]
+fn load_reports_invalid_file_path() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join(".pkgmeta"), "ignore: [unterminated").unwrap();
+    let err = load(dir.path()).unwrap_err();
+    assert!(matches!(err, crate::Error::InvalidPkgMeta { .. }));
+}
+
+#[test]
+fn load_returns_none_without_pkgmeta() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(load(dir.path()).unwrap().is_none());
+}
+
+#[test]
+fn external_github_repo_and_ref() {
+    let meta = parse(FIXTURE).unwrap();
+    let ace = &meta.externals["Libs/AceAddon-3.0"];
+    assert_eq!(ace.github_repo().as_deref(), Some("WoWUIDev/Ace3"));
+    assert_eq!(ace.git_ref(), "Release-r1341");
+
+    assert!(!ace.wants_latest_tag());
+
+    let stub = &meta.externals["Libs/LibStub"];
+    assert_eq!(stub.github_repo(), None);
+    assert_eq!(stub.git_ref(), "HEAD");
+
+    let icon = &meta.externals["Libs/LibDBIcon-1.0"];
+    assert!(icon.wants_latest_tag());
+    assert_eq!(icon.git_ref(), "HEAD");
+}
+
+#[test]
+fn glob_match_wildcards() {
+    assert!(glob_match("README.md", "README.md"));
+    assert!(glob_match("*.psd", "Media/logo.psd"));
+    assert!(glob_match("Media/?.tga", "Media/a.tga"));
+    assert!(!glob_match("Tests", "Tests/unit.lua"));
+    assert!(!glob_match("*.psd", "logo.tga"));
+}
+
+#[test]
+fn prune_removes_dotfiles_and_ignored_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    for rel in [
+        ".pkgmeta",
+        ".github/workflows/release.yml",
+        "README.md",
+        "Tests/unit.lua",
+        "Media/logo.psd",
+        "Media/logo.tga",
+        "MyAddon.toc",
+    ] {
+        touch(&root.join(rel));
+    }
+
+    parse(FIXTURE).unwrap().prune(root).unwrap();
+
+    assert!(root.join("MyAddon.toc").exists());
+    assert!(root.join("Media/logo.tga").exists());
+    for gone in [
+        ".pkgmeta",
+        ".github",
+        "README.md",
+        "Tests",
+        "Media/logo.psd",
+    ] {
+        assert!(!root.join(gone).exists(), "{gone} should be pruned");
+    }
+}
+
+#[test]
+fn arrange_moves_folders_and_renames_package() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("out");
+    let root = out.join("owner-MyAddon-abc1234");
+    touch(&root.join("MyAddon.toc"));
+    touch(&root.join("Modules/Options/MyAddon_Options.toc"));
+
+    let package = parse(FIXTURE).unwrap().arrange(&root, &out).unwrap();
+
+    assert_eq!(package, out.join("MyAddon"));
+    assert!(out.join("MyAddon/MyAddon.toc").exists());
+    assert!(out.join("MyAddon_Options/MyAddon_Options.toc").exists());
+    assert!(!out.join("MyAddon/Modules/Options").exists());
+    assert!(!root.exists());
+}
+
+#[test]
+fn arrange_without_package_as_uses_toc_basename() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("owner-repo-abc1234");
+    touch(&root.join("Cool_Vanilla.toc"));
+
+    let package = PkgMeta::default().arrange(&root, dir.path()).unwrap();
+    assert_eq!(package, dir.path().join("Cool"));
+}
@@ -2,19 +2,30 @@
 //!
 //! Requires a CurseForge API key, configured via `[providers.curseforge] api_key` in
 //! `config.toml` and carried through [`crate::providers::ProviderConfig`].
+//!
+//! Installed folders without an `X-Curse-Project-ID` can still be identified: [`fingerprint`]
+//! computes CurseForge's murmur2 folder fingerprint (the scheme the CurseForge app,
+//! Ajour and WowUp share) and [`CurseForgeProvider::match_fingerprints`] looks it up.
 
-use std::path::Path;
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Result,
-    manifest::ManifestAddon,
+    checksum::Digest,
+    events::Progress,
+    manifest::{ManifestAddon, Pin},
     model::{Channel, Flavor},
-    providers::{InstallContext, Provider, ResolvedArtifact},
+    providers::{InstallContext, Provider, ResolvedArtifact, version_matches},
 };
 
 const DEFAULT_BASE_URL: &str = "https://api.curseforge.com/v1";
+const PAGE_SIZE: &str = "50";
 
 pub struct CurseForgeProvider {
     client: reqwest::Client,
@@ -39,91 +50,215 @@ impl CurseForgeProvider {
             base_url,
         }
     }
-}
 
-#[async_trait::async_trait]
-impl Provider for CurseForgeProvider {
-    async fn resolve(
+    /// Newest file for the addon's channel that supports the install flavor.
+    async fn latest_file(
         &self,
         addon: &ManifestAddon,
+        project_id: u64,
         ctx: &InstallContext,
-    ) -> Result<ResolvedArtifact> {
-        let project_id = addon
-            .project_id
-            .ok_or_else(|| crate::Error::MissingProjectId {
-                name: addon.name.clone(),
-            })?;
-
+    ) -> Result<CfFile> {
         let channel = addon.channel.as_ref().unwrap_or(&ctx.channel);
         let release_type = channel_to_release_type(channel);
 
-        let url = format!("{}/mods/{}/files", self.base_url, project_id);
+        let page = self.files_page(project_id, Some(release_type), 0).await?;
+
+        page.data
+            .into_iter()
+            .find(|f| flavor_matches(&ctx.flavor, &f.game_versions))
+            .ok_or_else(|| crate::Error::NoRelease {
+                name: addon.name.clone(),
+            })
+    }
+
+    /// Exact file by id. A 404 means the file was deleted or never belonged to the project.
+    async fn pinned_file_id(
+        &self,
+        addon: &ManifestAddon,
+        project_id: u64,
+        file_id: u64,
+    ) -> Result<CfFile> {
+        let url = format!("{}/mods/{}/files/{}", self.base_url, project_id, file_id);
         let resp = self
             .client
             .get(&url)
             .header("x-api-key", &self.api_key)
-            .query(&[
-                ("gameId", "1"),
-                ("releaseType", release_type),
-                ("pageSize", "50"),
-            ])
             .send()
             .await
             .map_err(|e| crate::Error::Http(e.to_string()))?;
 
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(crate::Error::PinNotFound {
+                name: addon.name.clone(),
+                pin: Pin::FileId { file_id }.to_string(),
+            });
+        }
         if !resp.status().is_success() {
             return Err(crate::Error::Http(format!("{} {}", resp.status(), url)));
         }
 
-        let body: FilesResponse = resp
+        let body: FileResponse = resp
             .json()
             .await
             .map_err(|e| crate::Error::Http(e.to_string()))?;
+        Ok(body.data)
+    }
+
+    /// Walks every page of the project's files (all release types) looking for a
+    /// file whose name carries `version` and that supports `flavor`.
+    async fn pinned_version(
+        &self,
+        project_id: u64,
+        version: &str,
+        flavor: &Flavor,
+    ) -> Result<Option<CfFile>> {
+        let mut index = 0;
+        loop {
+            let page = self.files_page(project_id, None, index).await?;
+            let fetched = page.data.len() as u64;
+
+            if let Some(file) = page.data.into_iter().find(|f| {
+                (version_matches(version, &f.display_name)
+                    || version_matches(version, &f.file_name))
+                    && flavor_matches(flavor, &f.game_versions)
+            }) {
+                return Ok(Some(file));
+            }
+
+            index += fetched;
+            let total = page.pagination.map_or(0, |p| p.total_count);
+            if fetched == 0 || index >= total {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Looks `fingerprints` (from [`fingerprint`]) up in CurseForge's WoW catalog and
+    /// returns the files that match exactly. Unmatched fingerprints are left out.
+    pub async fn match_fingerprints(&self, fingerprints: &[u32]) -> Result<Vec<FingerprintMatch>> {
+        if fingerprints.is_empty() {
+            return Ok(Vec::new());
+        }
+        // WoW's game id is 1.
+        let url = format!("{}/fingerprints/1", self.base_url);
+        let resp = self
+            .client
+            .post(&url)
+            .header("x-api-key", &self.api_key)
+            .json(&FingerprintsRequest { fingerprints })
+            .send()
+            .await
+            .map_err(|e| crate::Error::Http(e.to_string()))?;
 
-        let file = body
+        if !resp.status().is_success() {
+            return Err(crate::Error::Http(format!("{} {}", resp.status(), url)));
+        }
+
+        let body: FingerprintsResponse = resp
+            .json()
+            .await
+            .map_err(|e| crate::Error::Http(e.to_string()))?;
+        Ok(body
             .data
+            .exact_matches
             .into_iter()
-            .find(|f| flavor_matches(&ctx.flavor, &f.game_versions))
-            .ok_or_else(|| crate::Error::NoRelease {
-                name: addon.name.clone(),
-            })?;
-
-        let sha256 = file
-            .hashes
-            .iter()
-            .find(|h| h.algo == 2)
-            .map(|h| h.value.clone());
-
-        Ok(ResolvedArtifact {
-            version: file.display_name,
-            id: file.id.to_string(),
-            url: file.download_url,
-            sha256,
-        })
+            .map(|m| FingerprintMatch {
+                project_id: m.id,
+                file_id: m.file.id,
+                version: m.file.display_name,
+                modules: m
+                    .file
+                    .modules
+                    .into_iter()
+                    .map(|module| (module.name, module.fingerprint))
+                    .collect(),
+            })
+            .collect())
     }
 
-    async fn download(&self, artifact: &ResolvedArtifact, dest: &Path) -> Result<()> {
+    async fn files_page(
+        &self,
+        project_id: u64,
+        release_type: Option<&str>,
+        index: u64,
+    ) -> Result<FilesResponse> {
+        let url = format!("{}/mods/{}/files", self.base_url, project_id);
+        let index = index.to_string();
+        let mut query = vec![("gameId", "1"), ("pageSize", PAGE_SIZE), ("index", &index)];
+        if let Some(release_type) = release_type {
+            query.push(("releaseType", release_type));
+        }
+
         let resp = self
             .client
-            .get(&artifact.url)
+            .get(&url)
+            .header("x-api-key", &self.api_key)
+            .query(&query)
             .send()
             .await
             .map_err(|e| crate::Error::Http(e.to_string()))?;
 
         if !resp.status().is_success() {
-            return Err(crate::Error::Http(format!(
-                "{} {}",
-                resp.status(),
-                artifact.url
-            )));
+            return Err(crate::Error::Http(format!("{} {}", resp.status(), url)));
         }
 
-        let bytes = resp
-            .bytes()
+        resp.json()
+            .await
+            .map_err(|e| crate::Error::Http(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl Provider for CurseForgeProvider {
+    async fn resolve(
+        &self,
+        addon: &ManifestAddon,
+        ctx: &InstallContext,
+    ) -> Result<ResolvedArtifact> {
+        let project_id = addon
+            .project_id
+            .ok_or_else(|| crate::Error::MissingProjectId {
+                name: addon.name.clone(),
+            })?;
+
+        let file = match &addon.pin {
+            None => self.latest_file(addon, project_id, ctx).await?,
+            Some(Pin::FileId { file_id }) => {
+                self.pinned_file_id(addon, project_id, *file_id).await?
+            }
+            Some(pin @ Pin::Version { version }) => self
+                .pinned_version(project_id, version, &ctx.flavor)
+                .await?
+                .ok_or_else(|| crate::Error::PinNotFound {
+                    name: addon.name.clone(),
+                    pin: pin.to_string(),
+                })?,
+            Some(pin) => {
+                return Err(crate::Error::UnsupportedPin {
+                    name: addon.name.clone(),
+                    provider: addon.provider.clone(),
+                    pin: pin.to_string(),
+                });
+            }
+        };
+
+        Ok(file.into_artifact())
+    }
+
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        max_bytes: Option<u64>,
+        progress: &Progress<'_>,
+    ) -> Result<()> {
+        let resp = self
+            .client
+            .get(&artifact.url)
+            .send()
             .await
             .map_err(|e| crate::Error::Http(e.to_string()))?;
-        std::fs::write(dest, bytes)?;
-        Ok(())
+        super::write_response(resp, &artifact.url, dest, max_bytes, progress).await
     }
 }
 
@@ -134,6 +269,18 @@ impl Provider for CurseForgeProvider {
 #[derive(Deserialize)]
 struct FilesResponse {
     data: Vec<CfFile>,
+    pagination: Option<CfPagination>,
+}
+
+#[derive(Deserialize)]
+struct FileResponse {
+    data: CfFile,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CfPagination {
+    total_count: u64,
 }
 
 #[derive(Deserialize)]
@@ -141,21 +288,94 @@ struct FilesResponse {
 struct CfFile {
     id: i64,
     display_name: String,
+    #[serde(default)]
+    file_name: String,
     download_url: String,
     game_versions: Vec<String>,
     hashes: Vec<CfHash>,
 }
 
+impl CfFile {
+    fn into_artifact(self) -> ResolvedArtifact {
+        let digest = preferred_digest(&self.hashes);
+        ResolvedArtifact {
+            version: self.display_name,
+            id: self.id.to_string(),
+            url: self.download_url,
+            digest,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct FingerprintsRequest<'a> {
+    fingerprints: &'a [u32],
+}
+
+#[derive(Deserialize)]
+struct FingerprintsResponse {
+    data: CfFingerprintMatches,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CfFingerprintMatches {
+    #[serde(default)]
+    exact_matches: Vec<CfFingerprintMatch>,
+}
+
+#[derive(Deserialize)]
+struct CfFingerprintMatch {
+    /// Project (mod) id.
+    id: u64,
+    file: CfFingerprintFile,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CfFingerprintFile {
+    id: u64,
+    display_name: String,
+    #[serde(default)]
+    modules: Vec<CfModule>,
+}
+
+#[derive(Deserialize)]
+struct CfModule {
+    name: String,
+    fingerprint: u32,
+}
+
+/// A CurseForge file whose folders match installed fingerprints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FingerprintMatch {
+    pub project_id: u64,
+    pub file_id: u64,
+    /// The file's display name, e.g. `Bagnon-10.2.5`.
+    pub version: String,
+    /// Every folder the file ships, with its fingerprint.
+    pub modules: Vec<(String, u32)>,
+}
+
 #[derive(Deserialize)]
-struct CfHash {
-    value: String,
-    algo: u8,
+pub(crate) struct CfHash {
+    pub(crate) value: String,
+    pub(crate) algo: u8,
 }
 
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Picks the strongest published hash. CurseForge's `HashAlgo` enum is
+/// `1 = Sha1`, `2 = Md5`; unknown ids are ignored.
+pub(crate) fn preferred_digest(hashes: &[CfHash]) -> Option<Digest> {
+    let by_algo = |algo: u8| hashes.iter().find(|h| h.algo == algo);
+    by_algo(1)
+        .map(|h| Digest::sha1(&h.value))
+        .or_else(|| by_algo(2).map(|h| Digest::md5(&h.value)))
+}
+
 pub(crate) fn channel_to_release_type(channel: &Channel) -> &'static str {
     match channel {
         Channel::Stable => "1",
@@ -192,3 +412,179 @@ fn flavor_version_prefix(flavor: &Flavor) -> &'static str {
         Flavor::Era => "1.",
     }
 }
+
+// ---------------------------------------------------------------------------
+// Fingerprints
+// ---------------------------------------------------------------------------
+
+/// Computes CurseForge's fingerprint of the installed addon folder `dir`.
+///
+/// The folder's own `.toc` files and `Bindings.xml` seed the walk; every `.lua` /
+/// `.xml` a `.toc` lists, and every `<Script file>` / `<Include file>` an XML file
+/// references, is followed (paths match case-insensitively, like the client). Each
+/// file is hashed with [`murmur2`] after dropping whitespace bytes; the sorted
+/// hashes, written out as decimal and concatenated, are hashed once more.
+pub fn fingerprint(dir: &Path) -> Result<u32> {
+    let folder = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut queue = VecDeque::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let seed = crate::toc::addon_name(&name).is_some_and(|n| n.eq_ignore_ascii_case(&folder))
+            || name.eq_ignore_ascii_case("bindings.xml");
+        if seed && entry.file_type()?.is_file() {
+            queue.push_back(entry.path());
+        }
+    }
+
+    let mut files = BTreeSet::new();
+    while let Some(path) = queue.pop_front() {
+        if !files.insert(path.clone()) {
+            continue;
+        }
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase());
+        let includes = match ext.as_deref() {
+            Some("toc") => toc_includes(&String::from_utf8_lossy(&fs::read(&path)?)),
+            Some("xml") => xml_includes(&String::from_utf8_lossy(&fs::read(&path)?)),
+            _ => continue,
+        };
+        let parent = path.parent().unwrap_or(dir);
+        queue.extend(includes.iter().filter_map(|rel| find_file(parent, rel)));
+    }
+
+    let mut hashes = Vec::with_capacity(files.len());
+    for path in &files {
+        let mut data = fs::read(path)?;
+        data.retain(|b| !matches!(b, b'\t' | b'\n' | b'\r' | b' '));
+        hashes.push(murmur2(&data, 1));
+    }
+    hashes.sort_unstable();
+    let joined: String = hashes.iter().map(u32::to_string).collect();
+    Ok(murmur2(joined.as_bytes(), 1))
+}
+
+/// 32-bit MurmurHash2 of `data`.
+pub fn murmur2(data: &[u8], seed: u32) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let mut h = seed ^ data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M) ^ k;
+    }
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, &b) in tail.iter().enumerate() {
+            h ^= u32::from(b) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^ (h >> 15)
+}
+
+/// File entries of a `.toc` that the fingerprint follows: `.lua` / `.xml` lines
+/// outside comments.
+pub(crate) fn toc_includes(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| {
+            let lower = line.to_ascii_lowercase();
+            (lower.ends_with(".lua") || lower.ends_with(".xml")) && !line.contains("..")
+        })
+        .map(str::to_owned)
+        .collect()
+}
+
+/// `file` attributes of `<Script>` / `<Include>` tags outside XML comments, in
+/// any of the spellings XML allows: self-closing or paired, spaces around `=`,
+/// single or double quotes.
+pub(crate) fn xml_includes(content: &str) -> Vec<String> {
+    let mut text = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("<!--") {
+        text.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("-->")
+            .map_or("", |end| &rest[start + end + 3..]);
+    }
+    text.push_str(rest);
+
+    let mut files = Vec::new();
+    for tag in text.split('<').skip(1) {
+        let name_len = tag
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(tag.len());
+        let (name, attrs) = tag.split_at(name_len);
+        if !(name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("include")) {
+            continue;
+        }
+        if let Some(file) = xml_attribute(attrs, "file")
+            && !file.contains("..")
+        {
+            files.push(file.to_owned());
+        }
+    }
+    files
+}
+
+/// Value of attribute `key` (matched case-insensitively) in `attrs`, the text
+/// after a tag name; stops at the end of the tag.
+fn xml_attribute<'a>(attrs: &'a str, key: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    loop {
+        rest = rest.trim_start();
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))
+            .unwrap_or(rest.len());
+        if name_len == 0 {
+            return None;
+        }
+        let (name, after) = rest.split_at(name_len);
+        let after = after.trim_start();
+        let Some(after) = after.strip_prefix('=') else {
+            // A bare attribute without a value.
+            rest = after;
+            continue;
+        };
+        let after = after.trim_start();
+        let (value, after) = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = after[1..].find(quote)?;
+                (&after[1..=end], &after[end + 2..])
+            }
+            _ => {
+                let end = after
+                    .find(|c: char| c.is_whitespace() || matches!(c, '>' | '/'))
+                    .unwrap_or(after.len());
+                after.split_at(end)
+            }
+        };
+        if name.eq_ignore_ascii_case(key) {
+            return Some(value);
+        }
+        rest = after;
+    }
+}
+
+/// Resolves `rel` (`/` or `\\` separated) under `base`, matching each component
+/// case-insensitively. Returns `None` if any component is missing.
+fn find_file(base: &Path, rel: &str) -> Option<PathBuf> {
+    let parts: Vec<&str> = rel.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
+    let path = crate::fs::join_ignore_case(base, &parts);
+    path.is_file().then_some(path)
+}
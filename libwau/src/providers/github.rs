@@ -5,6 +5,13 @@
 //!     file matching the regex, filtered by channel (stable excludes pre-releases).
 //!   - **Git-ref tip** (`git_ref` is set, `asset_regex` is absent): HEAD commit SHA of
 //!     the ref; the zipball URL is stored so the lock records the exact commit.
+//!     The zipball's `owner-repo-<sha>/` wrapper dir is unpacked by
+//!     [`crate::fs::extract_addon_zip`], which handles repo-root and multi-addon repos.
+//!
+//! Pins override both modes: `tag` selects that release (or, without `asset_regex`,
+//! the tag's commit snapshot) regardless of channel, and `commit` selects that exact
+//! snapshot. A `tag` + `sha256` pin carries the digest into the resolved artifact,
+//! taking precedence over the `sha256:` digest GitHub publishes for release assets.
 //!
 //! An optional `github_token` in [`crate::providers::ProviderConfig`] raises the
 //! unauthenticated rate limit from 60 to 5 000 requests / hour.
@@ -16,7 +23,9 @@ use serde::Deserialize;
 
 use crate::{
     Result,
-    manifest::ManifestAddon,
+    checksum::Digest,
+    events::Progress,
+    manifest::{ManifestAddon, Pin},
     model::Channel,
     providers::{InstallContext, Provider, ResolvedArtifact},
 };
@@ -76,10 +85,36 @@ impl Provider for GitHubProvider {
 
         let channel = addon.channel.as_ref().unwrap_or(&ctx.channel);
 
+        match &addon.pin {
+            None => {}
+            Some(pin @ (Pin::Tag { tag } | Pin::TagWithSha { tag, .. })) => {
+                let mut artifact = match &addon.asset_regex {
+                    Some(pattern) => {
+                        resolve_release_tag(self, addon, repo, pattern, tag, pin).await?
+                    }
+                    None => resolve_git_ref(self, addon, repo, tag, Some(pin)).await?,
+                };
+                if let Pin::TagWithSha { sha256, .. } = pin {
+                    artifact.digest = Some(Digest::sha256(sha256));
+                }
+                return Ok(artifact);
+            }
+            Some(pin @ Pin::Commit { commit }) => {
+                return resolve_git_ref(self, addon, repo, commit, Some(pin)).await;
+            }
+            Some(pin) => {
+                return Err(crate::Error::UnsupportedPin {
+                    name: addon.name.clone(),
+                    provider: addon.provider.clone(),
+                    pin: pin.to_string(),
+                });
+            }
+        }
+
         if let Some(pattern) = &addon.asset_regex {
             resolve_release_asset(self, addon, repo, pattern, channel).await
         } else if let Some(git_ref) = &addon.git_ref {
-            resolve_git_ref(self, addon, repo, git_ref).await
+            resolve_git_ref(self, addon, repo, git_ref, None).await
         } else {
             Err(crate::Error::NoRelease {
                 name: addon.name.clone(),
@@ -87,7 +122,13 @@ impl Provider for GitHubProvider {
         }
     }
 
-    async fn download(&self, artifact: &ResolvedArtifact, dest: &Path) -> Result<()> {
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        max_bytes: Option<u64>,
+        progress: &Progress<'_>,
+    ) -> Result<()> {
         let resp = self
             .client
             .get(&artifact.url)
@@ -95,21 +136,7 @@ impl Provider for GitHubProvider {
             .send()
             .await
             .map_err(|e| crate::Error::Http(e.to_string()))?;
-
-        if !resp.status().is_success() {
-            return Err(crate::Error::Http(format!(
-                "{} {}",
-                resp.status(),
-                artifact.url
-            )));
-        }
-
-        let bytes = resp
-            .bytes()
-            .await
-            .map_err(|e| crate::Error::Http(e.to_string()))?;
-        std::fs::write(dest, bytes)?;
-        Ok(())
+        super::write_response(resp, &artifact.url, dest, max_bytes, progress).await
     }
 }
 
@@ -156,7 +183,7 @@ async fn resolve_release_asset(
                 version: release.tag_name.clone(),
                 id: asset.id.to_string(),
                 url: asset.browser_download_url.clone(),
-                sha256: None,
+                digest: asset.sha256_digest(),
             });
         }
     }
@@ -172,11 +199,66 @@ async fn resolve_release_asset(
     })
 }
 
+/// Resolves the release tagged `tag` and its asset matching `pattern`, ignoring channel.
+async fn resolve_release_tag(
+    provider: &GitHubProvider,
+    addon: &ManifestAddon,
+    repo: &str,
+    pattern: &str,
+    tag: &str,
+    pin: &Pin,
+) -> Result<ResolvedArtifact> {
+    let re =
+        Regex::new(pattern).map_err(|e| crate::Error::Http(format!("invalid asset_regex: {e}")))?;
+
+    let url = format!("{}/repos/{}/releases/tags/{}", provider.base_url, repo, tag);
+    let resp = provider
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| crate::Error::Http(e.to_string()))?;
+
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(crate::Error::PinNotFound {
+            name: addon.name.clone(),
+            pin: pin.to_string(),
+        });
+    }
+    if !resp.status().is_success() {
+        return Err(crate::Error::Http(format!("{} {}", resp.status(), url)));
+    }
+
+    let release: GhRelease = resp
+        .json()
+        .await
+        .map_err(|e| crate::Error::Http(e.to_string()))?;
+
+    let asset = release
+        .assets
+        .iter()
+        .find(|a| re.is_match(&a.name))
+        .ok_or_else(|| crate::Error::NoMatchingAsset {
+            name: addon.name.clone(),
+            tag: release.tag_name.clone(),
+            pattern: pattern.to_owned(),
+        })?;
+
+    Ok(ResolvedArtifact {
+        version: release.tag_name.clone(),
+        id: asset.id.to_string(),
+        url: asset.browser_download_url.clone(),
+        digest: asset.sha256_digest(),
+    })
+}
+
+/// Resolves `git_ref` to a commit snapshot. When `pin` is set, a ref GitHub does not
+/// know (404, or 422 for a malformed sha) is reported as [`crate::Error::PinNotFound`].
 async fn resolve_git_ref(
     provider: &GitHubProvider,
-    _addon: &ManifestAddon,
+    addon: &ManifestAddon,
     repo: &str,
     git_ref: &str,
+    pin: Option<&Pin>,
 ) -> Result<ResolvedArtifact> {
     let url = format!("{}/repos/{}/commits/{}", provider.base_url, repo, git_ref);
     let resp = provider
@@ -185,6 +267,17 @@ async fn resolve_git_ref(
         .await
         .map_err(|e| crate::Error::Http(e.to_string()))?;
 
+    if let Some(pin) = pin
+        && matches!(
+            resp.status(),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::UNPROCESSABLE_ENTITY
+        )
+    {
+        return Err(crate::Error::PinNotFound {
+            name: addon.name.clone(),
+            pin: pin.to_string(),
+        });
+    }
     if !resp.status().is_success() {
         return Err(crate::Error::Http(format!("{} {}", resp.status(), url)));
     }
@@ -202,7 +295,7 @@ async fn resolve_git_ref(
         version: short,
         id: sha,
         url: zipball_url,
-        sha256: None,
+        digest: None,
     })
 }
 
@@ -223,6 +316,18 @@ struct GhAsset {
     id: u64,
     name: String,
     browser_download_url: String,
+    /// `"sha256:<hex>"`; absent on assets uploaded before GitHub started hashing them.
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+impl GhAsset {
+    fn sha256_digest(&self) -> Option<Digest> {
+        self.digest
+            .as_deref()
+            .and_then(|d| d.strip_prefix("sha256:"))
+            .map(Digest::sha256)
+    }
 }
 
 #[derive(Deserialize)]
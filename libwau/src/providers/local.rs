@@ -3,12 +3,17 @@
 //! Manifest rows must supply a `url` field with either an absolute path
 //! (`/path/to/addon.zip`) or a `file://` URL (`file:///path/to/addon.zip`).
 //! This provider is the reference implementation for end-to-end tests without
-//! external network access.
+//! external network access. The url already names one exact file, so pins are rejected.
+//!
+//! The resolved id embeds the zip's sha256 when the file is readable, so replacing
+//! the zip in place is seen as a new artifact by `sync --update`.
 
 use std::{fs, path::Path};
 
 use crate::{
     Result,
+    checksum::{Algorithm, Digest},
+    events::Progress,
     manifest::ManifestAddon,
     providers::{InstallContext, Provider, ResolvedArtifact},
 };
@@ -41,20 +46,43 @@ impl Provider for LocalProvider {
                 name: addon.name.clone(),
             })?;
 
+        if let Some(pin) = &addon.pin {
+            return Err(crate::Error::UnsupportedPin {
+                name: addon.name.clone(),
+                provider: addon.provider.clone(),
+                pin: pin.to_string(),
+            });
+        }
+
         let path = url_to_path(url);
-        let id = format!("local:{}", path.display());
+        // A missing file is reported by `download`; resolution stays path-only.
+        let (id, digest) = match crate::checksum::hash_file(&path, Algorithm::Sha256) {
+            Ok(sha256) => (
+                format!("local:{}@{}", path.display(), &sha256[..12]),
+                Some(Digest::sha256(sha256)),
+            ),
+            Err(_) => (format!("local:{}", path.display()), None),
+        };
 
         Ok(ResolvedArtifact {
             version: "local".into(),
             id,
             url: url.to_owned(),
-            sha256: None,
+            digest,
         })
     }
 
-    async fn download(&self, artifact: &ResolvedArtifact, dest: &Path) -> Result<()> {
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        max_bytes: Option<u64>,
+        progress: &Progress<'_>,
+    ) -> Result<()> {
         let src = url_to_path(&artifact.url);
-        fs::copy(&src, dest)?;
+        super::check_download_size(&artifact.url, fs::metadata(&src)?.len(), max_bytes)?;
+        let bytes = fs::copy(&src, dest)?;
+        progress.downloaded(bytes, Some(bytes));
         Ok(())
     }
 }
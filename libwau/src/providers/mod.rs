@@ -8,6 +8,8 @@ use std::path::{Path, PathBuf};
 
 use crate::{
     Result,
+    checksum::Digest,
+    events::Progress,
     manifest::ManifestAddon,
     model::{Channel, Flavor, Tag},
 };
@@ -37,6 +39,54 @@ pub struct InstallContext {
     pub addons_path: PathBuf,
     /// Cache root for staging areas and download buffers.
     pub cache_dir: PathBuf,
+    /// Size caps enforced while downloading and extracting.
+    pub limits: Limits,
+    /// What to do when an addon ships a folder another locked addon owns.
+    pub conflicts: ConflictPolicy,
+}
+
+/// How an install treats folders already owned by another lock entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Fail with [`crate::Error::FolderConflict`] before touching the addons dir.
+    #[default]
+    Refuse,
+    /// Install anyway; the folder becomes shared and is kept until its last owner goes.
+    Share,
+}
+
+/// Size caps that guard against oversized downloads and zip bombs.
+///
+/// `None` disables a cap. The defaults comfortably fit the largest real addon
+/// packs (ElvUI, DBM with voice packs) while stopping runaway archives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum bytes a single download may write to disk.
+    pub max_download_bytes: Option<u64>,
+    /// Maximum total bytes a single archive may expand to.
+    pub max_unpacked_bytes: Option<u64>,
+}
+
+impl Limits {
+    /// 512 MiB.
+    pub const DEFAULT_MAX_DOWNLOAD_BYTES: u64 = 512 * 1024 * 1024;
+    /// 2 GiB.
+    pub const DEFAULT_MAX_UNPACKED_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+    /// No caps at all.
+    pub const UNLIMITED: Self = Self {
+        max_download_bytes: None,
+        max_unpacked_bytes: None,
+    };
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_download_bytes: Some(Self::DEFAULT_MAX_DOWNLOAD_BYTES),
+            max_unpacked_bytes: Some(Self::DEFAULT_MAX_UNPACKED_BYTES),
+        }
+    }
 }
 
 /// A resolved artifact ready for download and extraction.
@@ -48,22 +98,39 @@ pub struct ResolvedArtifact {
     pub id: String,
     /// Download URL (or `file://` / filesystem path for local provider).
     pub url: String,
-    /// Optional sha256 hex digest for integrity verification.
-    pub sha256: Option<String>,
+    /// Digest published by the provider, verified after download when present.
+    pub digest: Option<Digest>,
 }
 
 /// Minimum interface every provider must implement.
 #[async_trait::async_trait]
 pub trait Provider: Send + Sync {
-    /// Resolve the manifest row to a single latest artifact given the install context.
+    /// Resolve the manifest row to one artifact given the install context: the
+    /// latest for the row's channel, or exactly the one its `pin` selects.
+    ///
+    /// A pin the provider can express but that matches nothing (an unknown tag,
+    /// version or file id) fails with [`crate::Error::PinNotFound`]; a pin kind the
+    /// provider cannot express at all (e.g. a commit on CurseForge, any pin on the
+    /// local provider) fails with [`crate::Error::UnsupportedPin`]. Neither falls
+    /// back to the latest artifact.
     async fn resolve(
         &self,
         addon: &ManifestAddon,
         ctx: &InstallContext,
     ) -> Result<ResolvedArtifact>;
 
-    /// Download (or copy) the artifact to `dest`, which should not exist yet.
-    async fn download(&self, artifact: &ResolvedArtifact, dest: &Path) -> Result<()>;
+    /// Download (or copy) the artifact to `dest`, which should not exist yet,
+    /// reporting bytes written through `progress`.
+    ///
+    /// Fails with [`crate::Error::DownloadTooLarge`] as soon as the artifact is
+    /// known to exceed `max_bytes`; the partial file is left for the caller to clean up.
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        max_bytes: Option<u64>,
+        progress: &Progress<'_>,
+    ) -> Result<()>;
 }
 
 /// Runtime credentials and options passed when constructing a provider.
@@ -110,3 +177,75 @@ pub fn for_provider(
         }),
     }
 }
+
+/// Streams the body of a successful download response to `dest` chunk by chunk,
+/// reporting progress as it goes. Shared by the HTTP providers.
+///
+/// A `Content-Length` above `max_bytes` is rejected before any body is read; a
+/// body that turns out longer than advertised is cut off once it crosses the cap.
+#[cfg(any(feature = "curseforge", feature = "wowinterface", feature = "github"))]
+pub(crate) async fn write_response(
+    mut resp: reqwest::Response,
+    url: &str,
+    dest: &Path,
+    max_bytes: Option<u64>,
+    progress: &Progress<'_>,
+) -> Result<()> {
+    use std::io::Write;
+
+    if !resp.status().is_success() {
+        return Err(crate::Error::Http(format!("{} {}", resp.status(), url)));
+    }
+
+    let total = resp.content_length();
+    check_download_size(url, total.unwrap_or(0), max_bytes)?;
+
+    let mut file = std::io::BufWriter::new(std::fs::File::create(dest)?);
+    let mut written = 0u64;
+    progress.downloaded(0, total);
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| crate::Error::Http(e.to_string()))?
+    {
+        written += chunk.len() as u64;
+        check_download_size(url, written, max_bytes)?;
+        file.write_all(&chunk)?;
+        progress.downloaded(written, total);
+    }
+    file.flush()?;
+    Ok(())
+}
+
+/// Returns [`crate::Error::DownloadTooLarge`] when `size` exceeds `max_bytes`.
+#[cfg(any(
+    feature = "local",
+    feature = "curseforge",
+    feature = "wowinterface",
+    feature = "github"
+))]
+pub(crate) fn check_download_size(url: &str, size: u64, max_bytes: Option<u64>) -> Result<()> {
+    match max_bytes {
+        Some(limit) if size > limit => Err(crate::Error::DownloadTooLarge {
+            url: url.to_owned(),
+            limit,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Returns `true` if `candidate` (a display or file name) names exactly `version`.
+///
+/// The name is split on whitespace, `-` and `_`; a trailing `.zip` and a leading `v`
+/// on either side are ignored, so `"Bagnon-v5.4.3.zip"` matches `"5.4.3"` and
+/// `"v5.4.3"` but `"5.4.31"` does not.
+pub(crate) fn version_matches(version: &str, candidate: &str) -> bool {
+    if candidate == version {
+        return true;
+    }
+    let bare = |s: &str| s.strip_prefix('v').unwrap_or(s).to_owned();
+    let version = bare(version);
+    let stem = candidate.strip_suffix(".zip").unwrap_or(candidate);
+    stem.split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .any(|token| bare(token) == version)
+}
@@ -1,5 +1,16 @@
 use super::*;
 
+/// Progress reporter that discards events, for download tests.
+#[cfg(any(
+    feature = "local",
+    feature = "curseforge",
+    feature = "wowinterface",
+    feature = "github"
+))]
+fn quiet() -> Progress<'static> {
+    Progress::new(&crate::events::NullSink, "test")
+}
+
 // ---------------------------------------------------------------------------
 // Provider trait types
 // ---------------------------------------------------------------------------
@@ -12,6 +23,8 @@ fn install_context_fields_accessible() {
         channel: Channel::Stable,
         addons_path: PathBuf::from("/wow/Interface/AddOns"),
         cache_dir: PathBuf::from("/cache/wau"),
+        limits: Default::default(),
+        conflicts: Default::default(),
     };
     assert_eq!(ctx.tag.as_str(), "test");
     assert_eq!(ctx.flavor, Flavor::Retail);
@@ -24,10 +37,20 @@ fn resolved_artifact_fields_accessible() {
         version: "1.0.0".into(),
         id: "local:/tmp/addon.zip".into(),
         url: "/tmp/addon.zip".into(),
-        sha256: None,
+        digest: None,
     };
     assert_eq!(a.version, "1.0.0");
-    assert!(a.sha256.is_none());
+    assert!(a.digest.is_none());
+}
+
+#[test]
+fn version_matches_exact_and_tokenised_names() {
+    assert!(version_matches("5.4.3", "5.4.3"));
+    assert!(version_matches("5.4.3", "Bagnon-5.4.3.zip"));
+    assert!(version_matches("5.4.3", "Bagnon v5.4.3"));
+    assert!(version_matches("v5.4.3", "Bagnon-5.4.3"));
+    assert!(!version_matches("5.4.3", "Bagnon-5.4.31.zip"));
+    assert!(!version_matches("5.4.3", "Bagnon-15.4.3"));
 }
 
 #[test]
@@ -49,11 +72,15 @@ mod curseforge_tests {
     use std::path::PathBuf;
 
     use crate::{
-        manifest::ManifestAddon,
+        checksum::Digest,
+        manifest::{ManifestAddon, Pin},
         model::{Channel, Flavor, Provider as ModelProvider, Tag},
         providers::{
             InstallContext, Provider, ResolvedArtifact,
-            curseforge::{CurseForgeProvider, channel_to_release_type, flavor_matches},
+            curseforge::{
+                CfHash, CurseForgeProvider, FingerprintMatch, channel_to_release_type, fingerprint,
+                flavor_matches, murmur2, preferred_digest, toc_includes, xml_includes,
+            },
         },
     };
 
@@ -64,6 +91,8 @@ mod curseforge_tests {
             channel: Channel::Stable,
             addons_path: PathBuf::from("/wow/Interface/AddOns"),
             cache_dir: PathBuf::from("/tmp/wau-cache"),
+            limits: Default::default(),
+            conflicts: Default::default(),
         }
     }
 
@@ -93,7 +122,7 @@ mod curseforge_tests {
                 "gameVersions": ["12.0.5"],
                 "hashes": [
                     {"value": "abc123sha1", "algo": 1},
-                    {"value": "def456sha256", "algo": 2}
+                    {"value": "def456md5", "algo": 2}
                 ]
             }],
             "pagination": {"index":0,"pageSize":50,"resultCount":1,"totalCount":1}
@@ -125,7 +154,7 @@ mod curseforge_tests {
             "https://edge.forgecdn.net/files/WeakAuras-4.5.0.zip"
         );
         assert_eq!(artifact.id, "4922788");
-        assert_eq!(artifact.sha256.as_deref(), Some("def456sha256"));
+        assert_eq!(artifact.digest, Some(Digest::sha1("abc123sha1")));
         mock.assert_async().await;
     }
 
@@ -254,6 +283,144 @@ mod curseforge_tests {
         mock.assert_async().await;
     }
 
+    // resolve — pins
+
+    #[tokio::test]
+    async fn resolve_file_id_pin_fetches_exact_file() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1/mods/90003/files/3000")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"data": {
+                    "id": 3000, "displayName": "WeakAuras-4.0.0.zip",
+                    "downloadUrl": "https://cdn/old.zip",
+                    "releaseType": 1, "gameVersions": ["12.0.1"], "hashes": []
+                }}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let mut addon = make_addon(Some(90003));
+        addon.pin = Some(Pin::FileId { file_id: 3000 });
+
+        let artifact = provider.resolve(&addon, &make_ctx()).await.unwrap();
+        assert_eq!(artifact.id, "3000");
+        assert_eq!(artifact.url, "https://cdn/old.zip");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_file_id_pin_error_when_file_gone() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1/mods/90003/files/3000")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let mut addon = make_addon(Some(90003));
+        addon.pin = Some(Pin::FileId { file_id: 3000 });
+
+        let result = provider.resolve(&addon, &make_ctx()).await;
+        assert!(matches!(result, Err(crate::Error::PinNotFound { .. })));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_version_pin_walks_pages() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("GET", "/v1/mods/90003/files")
+            .match_query(mockito::Matcher::UrlEncoded("index".into(), "0".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "data": [{
+                    "id": 2, "displayName": "WeakAuras-4.5.0.zip",
+                    "downloadUrl": "https://cdn/new.zip",
+                    "releaseType": 1, "gameVersions": ["12.0.5"], "hashes": []
+                }],
+                "pagination": {"index":0,"pageSize":1,"resultCount":1,"totalCount":2}
+            }"#,
+            )
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/v1/mods/90003/files")
+            .match_query(mockito::Matcher::UrlEncoded("index".into(), "1".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "data": [{
+                    "id": 1, "displayName": "WeakAuras-4.4.0.zip",
+                    "downloadUrl": "https://cdn/old.zip",
+                    "releaseType": 1, "gameVersions": ["12.0.1"], "hashes": []
+                }],
+                "pagination": {"index":1,"pageSize":1,"resultCount":1,"totalCount":2}
+            }"#,
+            )
+            .create_async()
+            .await;
+
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let mut addon = make_addon(Some(90003));
+        addon.pin = Some(Pin::Version {
+            version: "4.4.0".into(),
+        });
+
+        let artifact = provider.resolve(&addon, &make_ctx()).await.unwrap();
+        assert_eq!(artifact.id, "1");
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_version_pin_error_when_absent() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1/mods/90003/files")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(one_stable_retail_file())
+            .create_async()
+            .await;
+
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let mut addon = make_addon(Some(90003));
+        addon.pin = Some(Pin::Version {
+            version: "3.0.0".into(),
+        });
+
+        let result = provider.resolve(&addon, &make_ctx()).await;
+        assert!(matches!(result, Err(crate::Error::PinNotFound { .. })));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_commit_pin_is_unsupported() {
+        let server = mockito::Server::new_async().await;
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let mut addon = make_addon(Some(90003));
+        addon.pin = Some(Pin::Commit {
+            commit: "deadbeef".into(),
+        });
+
+        let result = provider.resolve(&addon, &make_ctx()).await;
+        assert!(matches!(result, Err(crate::Error::UnsupportedPin { .. })));
+    }
+
     // download
 
     #[tokio::test]
@@ -273,17 +440,75 @@ mod curseforge_tests {
             version: "4.5.0".into(),
             id: "4922788".into(),
             url: format!("{}/files/WeakAuras.zip", server.url()),
-            sha256: None,
+            digest: None,
         };
 
         let dir = tempfile::tempdir().unwrap();
         let dest = dir.path().join("out.zip");
-        provider.download(&artifact, &dest).await.unwrap();
+        provider
+            .download(&artifact, &dest, None, &super::quiet())
+            .await
+            .unwrap();
 
         assert_eq!(std::fs::read(&dest).unwrap(), fake_zip);
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn download_reports_progress_up_to_content_length() {
+        use crate::events::{Event, Progress};
+
+        let mut server = mockito::Server::new_async().await;
+        let body = vec![7u8; 64 * 1024];
+        let _mock = server
+            .mock("GET", "/files/Big.zip")
+            .with_status(200)
+            .with_body(&body)
+            .create_async()
+            .await;
+
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let artifact = ResolvedArtifact {
+            version: "1.0".into(),
+            id: "1".into(),
+            url: format!("{}/files/Big.zip", server.url()),
+            digest: None,
+        };
+
+        let seen = std::sync::Mutex::new(Vec::new());
+        let sink = |event: Event| seen.lock().unwrap().push(event);
+        let dir = tempfile::tempdir().unwrap();
+        provider
+            .download(
+                &artifact,
+                &dir.path().join("out.zip"),
+                None,
+                &Progress::new(&sink, "Big"),
+            )
+            .await
+            .unwrap();
+
+        let seen = seen.into_inner().unwrap();
+        let total = Some(body.len() as u64);
+        assert_eq!(
+            seen.first(),
+            Some(&Event::DownloadProgress {
+                addon: "Big".into(),
+                bytes: 0,
+                total,
+            })
+        );
+        assert_eq!(
+            seen.last(),
+            Some(&Event::DownloadProgress {
+                addon: "Big".into(),
+                bytes: body.len() as u64,
+                total,
+            })
+        );
+    }
+
     #[tokio::test]
     async fn download_error_on_http_failure() {
         let mut server = mockito::Server::new_async().await;
@@ -299,17 +524,56 @@ mod curseforge_tests {
             version: "1.0".into(),
             id: "1".into(),
             url: format!("{}/files/bad.zip", server.url()),
-            sha256: None,
+            digest: None,
         };
 
         let dir = tempfile::tempdir().unwrap();
         let result = provider
-            .download(&artifact, &dir.path().join("out.zip"))
+            .download(
+                &artifact,
+                &dir.path().join("out.zip"),
+                None,
+                &super::quiet(),
+            )
             .await;
         assert!(matches!(result, Err(crate::Error::Http(_))));
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn download_rejects_body_over_limit() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/files/Huge.zip")
+            .with_status(200)
+            .with_body(vec![0u8; 4096])
+            .create_async()
+            .await;
+
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let artifact = ResolvedArtifact {
+            version: "1.0".into(),
+            id: "1".into(),
+            url: format!("{}/files/Huge.zip", server.url()),
+            digest: None,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let result = provider
+            .download(
+                &artifact,
+                &dir.path().join("out.zip"),
+                Some(1024),
+                &super::quiet(),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::Error::DownloadTooLarge { limit: 1024, .. })
+        ));
+    }
+
     // flavor_matches
 
     #[test]
@@ -355,12 +619,187 @@ mod curseforge_tests {
 
     // channel_to_release_type
 
+    // preferred_digest
+
+    #[test]
+    fn preferred_digest_falls_back_to_md5() {
+        let hashes = vec![CfHash {
+            value: "00ff".into(),
+            algo: 2,
+        }];
+        assert_eq!(preferred_digest(&hashes), Some(Digest::md5("00ff")));
+    }
+
+    #[test]
+    fn preferred_digest_ignores_unknown_algo() {
+        let hashes = vec![CfHash {
+            value: "00ff".into(),
+            algo: 9,
+        }];
+        assert!(preferred_digest(&hashes).is_none());
+    }
+
     #[test]
     fn channel_release_type_values() {
         assert_eq!(channel_to_release_type(&Channel::Stable), "1");
         assert_eq!(channel_to_release_type(&Channel::Beta), "2");
         assert_eq!(channel_to_release_type(&Channel::Alpha), "3");
     }
+    // fingerprints
+
+    #[test]
+    fn murmur2_matches_reference_values() {
+        assert_eq!(murmur2(b"", 0), 0);
+        assert_eq!(murmur2(b"hello", 0), 0xe561_29cb);
+        assert_eq!(murmur2(b"hello", 1), 2_788_266_382);
+        assert_eq!(murmur2(b"abcd", 1), 3_376_380_438);
+    }
+
+    #[test]
+    fn toc_includes_lists_code_files_outside_comments() {
+        let toc = "## Title: Bagnon\n# Core.lua\nLibs\\embeds.xml\nCore.lua # main\nmedia/icon.tga\n../evil.lua\n";
+        assert_eq!(toc_includes(toc), vec!["Libs\\embeds.xml", "Core.lua"]);
+    }
+
+    #[test]
+    fn xml_includes_reads_script_and_include_tags() {
+        let xml = r#"<Ui>
+            <!-- <Script file="Old.lua"/> -->
+            <Script file="Core.lua"/>
+            <include  file='Libs\LibStub\LibStub.xml' />
+            <Frame name="X" file="NotAnInclude.lua"/>
+            <Scripts file="NotATag.lua"/>
+            <Include file="..\\Escape.xml"/>
+        </Ui>"#;
+        assert_eq!(
+            xml_includes(xml),
+            vec!["Core.lua", "Libs\\LibStub\\LibStub.xml"]
+        );
+    }
+
+    #[test]
+    fn xml_includes_tolerates_spacing_quotes_and_paired_tags() {
+        let xml = r#"<Ui xmlns="http://www.blizzard.com/wow/ui/">
+            <Script file = "Spaced.lua"></Script>
+            <Include file='Single.xml'/>
+            <SCRIPT
+                file="Multiline.lua"
+            />
+            <Script debug file=Unquoted.lua />
+        </Ui>"#;
+        assert_eq!(
+            xml_includes(xml),
+            vec!["Spaced.lua", "Single.xml", "Multiline.lua", "Unquoted.lua"]
+        );
+    }
+
+    fn write(path: &std::path::Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn fingerprint_follows_referenced_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let addon = dir.path().join("Bagnon");
+        write(
+            &addon.join("Bagnon.toc"),
+            "## Title: Bagnon\nlibs\\embeds.xml\nCore.lua\n",
+        );
+        write(&addon.join("Core.lua"), "print('hi')\n");
+        write(
+            &addon.join("Libs/embeds.xml"),
+            r#"<Ui><Script file="LibStub.lua"/></Ui>"#,
+        );
+        write(&addon.join("Libs/LibStub.lua"), "LibStub = {}\n");
+        let before = fingerprint(&addon).unwrap();
+
+        // Unreferenced files and whitespace changes do not affect the fingerprint.
+        write(&addon.join("README.md"), "notes");
+        write(&addon.join("Core.lua"), "print('hi')\r\n\t");
+        assert_eq!(fingerprint(&addon).unwrap(), before);
+
+        // A referenced file's content does.
+        write(&addon.join("Libs/LibStub.lua"), "LibStub = {v=2}\n");
+        assert_ne!(fingerprint(&addon).unwrap(), before);
+    }
+
+    #[test]
+    fn fingerprint_hashes_sorted_file_hashes() {
+        let dir = tempfile::tempdir().unwrap();
+        let addon = dir.path().join("Tiny");
+        write(&addon.join("Tiny.toc"), "Tiny.lua\n");
+        write(&addon.join("Tiny.lua"), "x = 1");
+
+        let mut hashes = [murmur2(b"Tiny.lua", 1), murmur2(b"x=1", 1)];
+        hashes.sort_unstable();
+        let joined = format!("{}{}", hashes[0], hashes[1]);
+        assert_eq!(fingerprint(&addon).unwrap(), murmur2(joined.as_bytes(), 1));
+    }
+
+    #[tokio::test]
+    async fn match_fingerprints_returns_exact_matches() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/fingerprints/1")
+            .match_header("x-api-key", "key")
+            .match_body(mockito::Matcher::JsonString(
+                r#"{"fingerprints": [111, 222]}"#.into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"data": {
+                    "isCacheBuilt": true,
+                    "exactMatches": [{
+                        "id": 1592,
+                        "file": {
+                            "id": 5001,
+                            "displayName": "Bagnon 10.2.5",
+                            "modules": [
+                                {"name": "Bagnon", "fingerprint": 111},
+                                {"name": "Bagnon_Config", "fingerprint": 333}
+                            ]
+                        },
+                        "latestFiles": []
+                    }],
+                    "exactFingerprints": [111],
+                    "partialMatches": [],
+                    "unmatchedFingerprints": [222]
+                }}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let matches = provider.match_fingerprints(&[111, 222]).await.unwrap();
+
+        assert_eq!(
+            matches,
+            vec![FingerprintMatch {
+                project_id: 1592,
+                file_id: 5001,
+                version: "Bagnon 10.2.5".into(),
+                modules: vec![("Bagnon".into(), 111), ("Bagnon_Config".into(), 333)],
+            }]
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn match_fingerprints_reports_http_errors() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/v1/fingerprints/1")
+            .with_status(403)
+            .create_async()
+            .await;
+        let provider =
+            CurseForgeProvider::with_base_url("key".into(), format!("{}/v1", server.url()));
+        let result = provider.match_fingerprints(&[1]).await;
+        assert!(matches!(result, Err(crate::Error::Http(_))));
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -372,7 +811,7 @@ mod local_tests {
     use std::path::PathBuf;
 
     use crate::{
-        manifest::ManifestAddon,
+        manifest::{ManifestAddon, Pin},
         model::{Channel, Flavor, Provider as ModelProvider, Tag},
         providers::{
             InstallContext, Provider, ResolvedArtifact,
@@ -387,6 +826,8 @@ mod local_tests {
             channel: Channel::Stable,
             addons_path: PathBuf::from("/wow/Interface/AddOns"),
             cache_dir: PathBuf::from("/cache/wau"),
+            limits: Default::default(),
+            conflicts: Default::default(),
         }
     }
 
@@ -422,7 +863,7 @@ mod local_tests {
         assert_eq!(artifact.version, "local");
         assert!(artifact.id.contains("/tmp/addon.zip"));
         assert_eq!(artifact.url, "/tmp/addon.zip");
-        assert!(artifact.sha256.is_none());
+        assert!(artifact.digest.is_none());
     }
 
     #[tokio::test]
@@ -434,6 +875,17 @@ mod local_tests {
         assert_eq!(artifact.url, "file:///tmp/addon.zip");
     }
 
+    #[tokio::test]
+    async fn resolve_rejects_pin() {
+        let provider = LocalProvider::new();
+        let mut addon = make_addon(Some("/tmp/addon.zip"));
+        addon.pin = Some(Pin::Version {
+            version: "1.0".into(),
+        });
+        let result = provider.resolve(&addon, &make_ctx()).await;
+        assert!(matches!(result, Err(crate::Error::UnsupportedPin { .. })));
+    }
+
     #[test]
     fn url_to_path_plain() {
         assert_eq!(url_to_path("/tmp/x.zip"), PathBuf::from("/tmp/x.zip"));
@@ -459,11 +911,37 @@ mod local_tests {
             version: "local".into(),
             id: "local:/tmp".into(),
             url: src.to_str().unwrap().to_owned(),
-            sha256: None,
+            digest: None,
         };
-        provider.download(&artifact, &dest).await.unwrap();
+        provider
+            .download(&artifact, &dest, None, &super::quiet())
+            .await
+            .unwrap();
         assert_eq!(std::fs::read(&dest).unwrap(), b"test data");
     }
+
+    #[tokio::test]
+    async fn download_rejects_file_over_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.zip");
+        std::fs::write(&src, b"test data").unwrap();
+
+        let dest = dir.path().join("dest.zip");
+        let artifact = ResolvedArtifact {
+            version: "local".into(),
+            id: "local:/tmp".into(),
+            url: src.to_str().unwrap().to_owned(),
+            digest: None,
+        };
+        let result = LocalProvider::new()
+            .download(&artifact, &dest, Some(4), &super::quiet())
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::Error::DownloadTooLarge { limit: 4, .. })
+        ));
+        assert!(!dest.exists());
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -475,7 +953,7 @@ mod wowinterface_tests {
     use std::path::PathBuf;
 
     use crate::{
-        manifest::ManifestAddon,
+        manifest::{ManifestAddon, Pin},
         model::{Channel, Flavor, Provider as ModelProvider, Tag},
         providers::{
             InstallContext, Provider, ResolvedArtifact, wowinterface::WoWInterfaceProvider,
@@ -489,6 +967,8 @@ mod wowinterface_tests {
             channel: Channel::Stable,
             addons_path: PathBuf::from("/wow/Interface/AddOns"),
             cache_dir: PathBuf::from("/tmp/wau-cache"),
+            limits: Default::default(),
+            conflicts: Default::default(),
         }
     }
 
@@ -544,7 +1024,51 @@ mod wowinterface_tests {
             artifact.url,
             "https://cdn.wowinterface.com/downloads/file12345/WeakAuras-4.5.0.zip"
         );
-        assert!(artifact.sha256.is_none());
+        assert!(artifact.digest.is_none());
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_version_pin_matches_current_file() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/filedetails/12345.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(file_details_response())
+            .create_async()
+            .await;
+
+        let provider = WoWInterfaceProvider::with_base_url(server.url());
+        let mut addon = make_addon(Some(12345));
+        addon.pin = Some(Pin::Version {
+            version: "4.5.0".into(),
+        });
+
+        let artifact = provider.resolve(&addon, &make_ctx()).await.unwrap();
+        assert_eq!(artifact.version, "4.5.0");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_version_pin_error_when_superseded() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/filedetails/12345.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(file_details_response())
+            .create_async()
+            .await;
+
+        let provider = WoWInterfaceProvider::with_base_url(server.url());
+        let mut addon = make_addon(Some(12345));
+        addon.pin = Some(Pin::Version {
+            version: "4.4.0".into(),
+        });
+
+        let result = provider.resolve(&addon, &make_ctx()).await;
+        assert!(matches!(result, Err(crate::Error::PinNotFound { .. })));
         mock.assert_async().await;
     }
 
@@ -610,12 +1134,15 @@ mod wowinterface_tests {
             version: "4.5.0".into(),
             id: "12345".into(),
             url: format!("{}/files/WeakAuras.zip", server.url()),
-            sha256: None,
+            digest: None,
         };
 
         let dir = tempfile::tempdir().unwrap();
         let dest = dir.path().join("out.zip");
-        provider.download(&artifact, &dest).await.unwrap();
+        provider
+            .download(&artifact, &dest, None, &super::quiet())
+            .await
+            .unwrap();
 
         assert_eq!(std::fs::read(&dest).unwrap(), fake_zip);
         mock.assert_async().await;
@@ -635,12 +1162,17 @@ mod wowinterface_tests {
             version: "1.0".into(),
             id: "1".into(),
             url: format!("{}/files/bad.zip", server.url()),
-            sha256: None,
+            digest: None,
         };
 
         let dir = tempfile::tempdir().unwrap();
         let result = provider
-            .download(&artifact, &dir.path().join("out.zip"))
+            .download(
+                &artifact,
+                &dir.path().join("out.zip"),
+                None,
+                &super::quiet(),
+            )
             .await;
         assert!(matches!(result, Err(crate::Error::Http(_))));
         mock.assert_async().await;
@@ -656,7 +1188,8 @@ mod github_tests {
     use std::path::PathBuf;
 
     use crate::{
-        manifest::ManifestAddon,
+        checksum::Digest,
+        manifest::{ManifestAddon, Pin},
         model::{Channel, Flavor, Provider as ModelProvider, Tag},
         providers::{InstallContext, Provider, ResolvedArtifact, github::GitHubProvider},
     };
@@ -668,6 +1201,8 @@ mod github_tests {
             channel: Channel::Stable,
             addons_path: PathBuf::from("/wow/Interface/AddOns"),
             cache_dir: PathBuf::from("/tmp/wau-cache"),
+            limits: Default::default(),
+            conflicts: Default::default(),
         }
     }
 
@@ -729,7 +1264,7 @@ mod github_tests {
             artifact.url,
             "https://github.com/owner/repo/releases/download/v4.5.0/TestAddon-4.5.0.zip"
         );
-        assert!(artifact.sha256.is_none());
+        assert!(artifact.digest.is_none());
         mock.assert_async().await;
     }
 
@@ -798,6 +1333,30 @@ mod github_tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn resolve_release_asset_carries_published_digest() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/repos/owner/repo/releases")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[{"tag_name":"v4.5.0","prerelease":false,"draft":false,"assets":[
+                    {"id":1,"name":"TestAddon-4.5.0.zip","browser_download_url":"https://cdn/a.zip",
+                     "digest":"sha256:0011"}
+                ]}]"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = GitHubProvider::with_base_url(None, server.url());
+        let addon = make_addon(Some("owner/repo"), Some(r"TestAddon-.*\.zip"), None);
+        let artifact = provider.resolve(&addon, &make_ctx()).await.unwrap();
+
+        assert_eq!(artifact.digest, Some(Digest::sha256("0011")));
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn resolve_release_asset_error_on_http_failure() {
         let mut server = mockito::Server::new_async().await;
@@ -874,6 +1433,110 @@ mod github_tests {
         mock.assert_async().await;
     }
 
+    // resolve — pins
+
+    #[tokio::test]
+    async fn resolve_tag_pin_fetches_release_by_tag() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/repos/owner/repo/releases/tags/v4.0.0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"tag_name":"v4.0.0","prerelease":true,"draft":false,"assets":[
+                    {"id":7,"name":"TestAddon-4.0.0.zip","browser_download_url":"https://cdn/4.0.0.zip"}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = GitHubProvider::with_base_url(None, server.url());
+        let mut addon = make_addon(Some("owner/repo"), Some(r"TestAddon-.*\.zip"), None);
+        addon.pin = Some(Pin::TagWithSha {
+            tag: "v4.0.0".into(),
+            sha256: "ab".repeat(32),
+        });
+
+        // Pinned prerelease is accepted even on the stable channel.
+        let artifact = provider.resolve(&addon, &make_ctx()).await.unwrap();
+        assert_eq!(artifact.version, "v4.0.0");
+        assert_eq!(artifact.id, "7");
+        assert_eq!(artifact.digest, Some(Digest::sha256("ab".repeat(32))));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_tag_pin_error_when_tag_gone() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/repos/owner/repo/releases/tags/v4.0.0")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let provider = GitHubProvider::with_base_url(None, server.url());
+        let mut addon = make_addon(Some("owner/repo"), Some(r"TestAddon-.*\.zip"), None);
+        addon.pin = Some(Pin::Tag {
+            tag: "v4.0.0".into(),
+        });
+
+        let result = provider.resolve(&addon, &make_ctx()).await;
+        assert!(matches!(result, Err(crate::Error::PinNotFound { .. })));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_commit_pin_overrides_git_ref() {
+        let mut server = mockito::Server::new_async().await;
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let mock = server
+            .mock("GET", format!("/repos/owner/repo/commits/{sha}").as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"sha":"{sha}"}}"#))
+            .create_async()
+            .await;
+
+        let provider = GitHubProvider::with_base_url(None, server.url());
+        let mut addon = make_addon(Some("owner/repo"), None, Some("main"));
+        addon.pin = Some(Pin::Commit { commit: sha.into() });
+
+        let artifact = provider.resolve(&addon, &make_ctx()).await.unwrap();
+        assert_eq!(artifact.id, sha);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_commit_pin_error_when_commit_gone() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/repos/owner/repo/commits/deadbeef")
+            .with_status(422)
+            .create_async()
+            .await;
+
+        let provider = GitHubProvider::with_base_url(None, server.url());
+        let mut addon = make_addon(Some("owner/repo"), None, Some("main"));
+        addon.pin = Some(Pin::Commit {
+            commit: "deadbeef".into(),
+        });
+
+        let result = provider.resolve(&addon, &make_ctx()).await;
+        assert!(matches!(result, Err(crate::Error::PinNotFound { .. })));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_file_id_pin_is_unsupported() {
+        let server = mockito::Server::new_async().await;
+        let provider = GitHubProvider::with_base_url(None, server.url());
+        let mut addon = make_addon(Some("owner/repo"), Some(r"TestAddon-.*\.zip"), None);
+        addon.pin = Some(Pin::FileId { file_id: 1 });
+
+        let result = provider.resolve(&addon, &make_ctx()).await;
+        assert!(matches!(result, Err(crate::Error::UnsupportedPin { .. })));
+    }
+
     // download
 
     #[tokio::test]
@@ -892,12 +1555,15 @@ mod github_tests {
             version: "v4.5.0".into(),
             id: "11111".into(),
             url: format!("{}/files/TestAddon.zip", server.url()),
-            sha256: None,
+            digest: None,
         };
 
         let dir = tempfile::tempdir().unwrap();
         let dest = dir.path().join("out.zip");
-        provider.download(&artifact, &dest).await.unwrap();
+        provider
+            .download(&artifact, &dest, None, &super::quiet())
+            .await
+            .unwrap();
 
         assert_eq!(std::fs::read(&dest).unwrap(), fake_zip);
         mock.assert_async().await;
@@ -917,12 +1583,17 @@ mod github_tests {
             version: "v1.0".into(),
             id: "1".into(),
             url: format!("{}/files/bad.zip", server.url()),
-            sha256: None,
+            digest: None,
         };
 
         let dir = tempfile::tempdir().unwrap();
         let result = provider
-            .download(&artifact, &dir.path().join("out.zip"))
+            .download(
+                &artifact,
+                &dir.path().join("out.zip"),
+                None,
+                &super::quiet(),
+            )
             .await;
         assert!(matches!(result, Err(crate::Error::Http(_))));
         mock.assert_async().await;
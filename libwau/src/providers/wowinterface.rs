@@ -1,6 +1,9 @@
 //! WoWInterface provider: resolves and downloads addon files via the MMOUI v4 API.
 //!
 //! No authentication is required; the MMOUI API is publicly accessible.
+//!
+//! The API only exposes the current file, so a `version` pin can be honoured only
+//! while that version is still the one served; otherwise resolution fails.
 
 use std::path::Path;
 
@@ -8,7 +11,8 @@ use serde::Deserialize;
 
 use crate::{
     Result,
-    manifest::ManifestAddon,
+    events::Progress,
+    manifest::{ManifestAddon, Pin},
     providers::{InstallContext, Provider, ResolvedArtifact},
 };
 
@@ -77,36 +81,46 @@ impl Provider for WoWInterfaceProvider {
                 name: addon.name.clone(),
             })?;
 
+        match &addon.pin {
+            None => {}
+            Some(Pin::Version { version }) if *version == file.ui_version => {}
+            Some(pin @ Pin::Version { .. }) => {
+                return Err(crate::Error::PinNotFound {
+                    name: addon.name.clone(),
+                    pin: pin.to_string(),
+                });
+            }
+            Some(pin) => {
+                return Err(crate::Error::UnsupportedPin {
+                    name: addon.name.clone(),
+                    provider: addon.provider.clone(),
+                    pin: pin.to_string(),
+                });
+            }
+        }
+
         Ok(ResolvedArtifact {
             version: file.ui_version,
             id: file.uid,
             url: file.ui_download,
-            sha256: None,
+            digest: None,
         })
     }
 
-    async fn download(&self, artifact: &ResolvedArtifact, dest: &Path) -> Result<()> {
+    async fn download(
+        &self,
+        artifact: &ResolvedArtifact,
+        dest: &Path,
+        max_bytes: Option<u64>,
+        progress: &Progress<'_>,
+    ) -> Result<()> {
         let resp = self
             .client
             .get(&artifact.url)
             .send()
             .await
             .map_err(|e| crate::Error::Http(e.to_string()))?;
-
-        if !resp.status().is_success() {
-            return Err(crate::Error::Http(format!(
-                "{} {}",
-                resp.status(),
-                artifact.url
-            )));
-        }
-
-        let bytes = resp
-            .bytes()
-            .await
-            .map_err(|e| crate::Error::Http(e.to_string()))?;
-        std::fs::write(dest, bytes)?;
-        Ok(())
+        super::write_response(resp, &artifact.url, dest, max_bytes, progress).await
     }
 }
 
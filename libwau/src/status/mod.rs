@@ -0,0 +1,257 @@
+//! Cross-references the AddOns folder with the lock and manifest.
+//!
+//! [`report`] produces one [`AddonReport`] per lock entry, per manifest row that
+//! was never installed, and per [`AddonGroup`] no lock entry owns. `wau list`
+//! filters and sorts the result with [`ReportFilter`] and [`sort`].
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    fs::{Compatibility, InstalledAddon},
+    inventory::AddonGroup,
+    lock::Lock,
+    manifest::Manifest,
+    model::{Channel, Flavor, Provider, parse_slug},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// How an addon relates to the lock and the AddOns folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AddonStatus {
+    /// Locked, and every locked folder is on disk.
+    Managed,
+    /// In the lock or manifest, but some or all of its folders are not on disk.
+    Missing,
+    /// On disk, but no lock entry owns the folder.
+    Unmanaged,
+}
+
+impl AddonStatus {
+    pub const ALL: &[Self] = &[Self::Managed, Self::Missing, Self::Unmanaged];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Managed => "managed",
+            Self::Missing => "missing",
+            Self::Unmanaged => "unmanaged",
+        }
+    }
+}
+
+impl std::fmt::Display for AddonStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for AddonStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_slug(Self::ALL, Self::as_str, "status", s)
+    }
+}
+
+/// One row of the cross-referenced view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddonReport {
+    /// Lock/manifest name; the main folder's name for unmanaged rows.
+    pub name: String,
+    pub status: AddonStatus,
+    /// `None` for unmanaged folders.
+    pub provider: Option<Provider>,
+    /// The locked channel, or the manifest row's channel when it sets one.
+    pub channel: Option<Channel>,
+    /// `None` for unmanaged folders.
+    pub flavor: Option<Flavor>,
+    /// Folders the addon owns (locked `installed_dirs`, or the unmanaged group's).
+    pub folders: Vec<String>,
+    /// Locked folders that are not on disk.
+    pub missing_folders: Vec<String>,
+    pub locked_version: Option<String>,
+    /// `## Version` of the first (main, for unmanaged rows) folder on disk, read
+    /// from the `.toc` the flavor's client loads.
+    pub toc_version: Option<String>,
+    /// How the same folder's `## Interface` suits the flavor; `None` when no
+    /// folder is on disk.
+    pub compatibility: Option<Compatibility>,
+    /// Whether a manifest row for this addon applies to the flavor.
+    pub in_manifest: bool,
+}
+
+/// Builds the cross-referenced view of `groups` (from [`crate::inventory::group`]),
+/// the lock and the manifest rows that apply to `flavor`. `client_interface` is
+/// the detected client's interface number, when known.
+pub fn report(
+    groups: &[AddonGroup],
+    lock: &Lock,
+    manifest: &Manifest,
+    flavor: &Flavor,
+    client_interface: Option<u32>,
+) -> Vec<AddonReport> {
+    let on_disk: HashMap<&str, &InstalledAddon> = groups
+        .iter()
+        .flat_map(|g| &g.addons)
+        .map(|a| (a.folder.as_str(), a))
+        .collect();
+    let in_manifest = |name: &str, f: &Flavor| {
+        manifest
+            .addon
+            .iter()
+            .any(|m| m.name == name && m.flavors.as_ref().is_none_or(|flavors| flavors.contains(f)))
+    };
+
+    let mut reports = Vec::new();
+    for entry in &lock.addon {
+        let (present, missing): (Vec<&String>, Vec<&String>) = entry
+            .installed_dirs
+            .iter()
+            .partition(|d| on_disk.contains_key(d.as_str()));
+        reports.push(AddonReport {
+            name: entry.name.clone(),
+            status: if present.is_empty() || !missing.is_empty() {
+                AddonStatus::Missing
+            } else {
+                AddonStatus::Managed
+            },
+            provider: Some(entry.provider.clone()),
+            channel: Some(entry.channel.clone()),
+            flavor: Some(entry.flavor.clone()),
+            folders: entry.installed_dirs.clone(),
+            missing_folders: missing.into_iter().cloned().collect(),
+            locked_version: Some(entry.resolved_version.clone()),
+            toc_version: present
+                .first()
+                .and_then(|d| on_disk[d.as_str()].version_for(&entry.flavor))
+                .map(str::to_owned),
+            compatibility: present
+                .first()
+                .map(|d| on_disk[d.as_str()].compatibility(&entry.flavor, client_interface)),
+            in_manifest: in_manifest(&entry.name, &entry.flavor),
+        });
+    }
+
+    for addon in &manifest.addon {
+        if !in_manifest(&addon.name, flavor) || lock.find(&addon.name, flavor).is_some() {
+            continue;
+        }
+        reports.push(AddonReport {
+            name: addon.name.clone(),
+            status: AddonStatus::Missing,
+            provider: Some(addon.provider.clone()),
+            channel: addon.channel.clone(),
+            flavor: Some(flavor.clone()),
+            folders: Vec::new(),
+            missing_folders: Vec::new(),
+            locked_version: None,
+            toc_version: None,
+            compatibility: None,
+            in_manifest: true,
+        });
+    }
+
+    for group in groups.iter().filter(|g| g.locked_by.is_none()) {
+        reports.push(AddonReport {
+            name: group.name.clone(),
+            status: AddonStatus::Unmanaged,
+            provider: None,
+            channel: None,
+            flavor: None,
+            folders: group.folders().map(str::to_owned).collect(),
+            missing_folders: Vec::new(),
+            locked_version: None,
+            toc_version: group.main().version_for(flavor).map(str::to_owned),
+            compatibility: Some(group.main().compatibility(flavor, client_interface)),
+            in_manifest: false,
+        });
+    }
+
+    reports
+}
+
+/// Row filter for [`report`] output; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct ReportFilter {
+    pub provider: Option<Provider>,
+    pub status: Option<AddonStatus>,
+    /// Unmanaged folders have no flavor and never match a flavor filter.
+    pub flavor: Option<Flavor>,
+}
+
+impl ReportFilter {
+    pub fn matches(&self, report: &AddonReport) -> bool {
+        self.provider
+            .as_ref()
+            .is_none_or(|p| report.provider.as_ref() == Some(p))
+            && self.status.is_none_or(|s| report.status == s)
+            && self
+                .flavor
+                .as_ref()
+                .is_none_or(|f| report.flavor.as_ref() == Some(f))
+    }
+}
+
+/// Column [`sort`] orders rows by; ties fall back to the name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Name,
+    Status,
+    Provider,
+    Flavor,
+}
+
+impl SortKey {
+    pub const ALL: &[Self] = &[Self::Name, Self::Status, Self::Provider, Self::Flavor];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Status => "status",
+            Self::Provider => "provider",
+            Self::Flavor => "flavor",
+        }
+    }
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_slug(Self::ALL, Self::as_str, "sort key", s)
+    }
+}
+
+/// Sorts rows by `key`, then case-insensitively by name. Rows without a
+/// provider or flavor sort last for those keys.
+pub fn sort(reports: &mut [AddonReport], key: SortKey) {
+    fn by_name(a: &AddonReport, b: &AddonReport) -> Ordering {
+        a.name.to_lowercase().cmp(&b.name.to_lowercase())
+    }
+    fn last_if_none(a: Option<&'static str>, b: Option<&'static str>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    reports.sort_by(|a, b| {
+        let primary = match key {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Status => a.status.cmp(&b.status),
+            SortKey::Provider => last_if_none(
+                a.provider.as_ref().map(Provider::as_str),
+                b.provider.as_ref().map(Provider::as_str),
+            ),
+            SortKey::Flavor => last_if_none(
+                a.flavor.as_ref().map(Flavor::as_str),
+                b.flavor.as_ref().map(Flavor::as_str),
+            ),
+        };
+        primary.then_with(|| by_name(a, b))
+    });
+}
@@ -0,0 +1,207 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+
+use super::*;
+use crate::{
+    inventory::group, lock::LockedAddon, manifest::ManifestAddon, model::Tag, toc::TocFile,
+};
+
+fn on_disk(folder: &str, version: Option<&str>) -> InstalledAddon {
+    InstalledAddon {
+        folder: folder.to_owned(),
+        toc_files: vec![TocFile {
+            path: PathBuf::from(format!("{folder}/{folder}.toc")),
+            version: version.map(str::to_owned),
+            interface: vec![120001],
+            ..Default::default()
+        }],
+    }
+}
+
+fn row(name: &str, provider: Provider, flavors: Option<Vec<Flavor>>) -> ManifestAddon {
+    ManifestAddon {
+        name: name.into(),
+        provider,
+        channel: None,
+        flavors,
+        pin: None,
+        project_id: None,
+        wowi_id: None,
+        repo: None,
+        asset_regex: None,
+        git_ref: None,
+        url: None,
+    }
+}
+
+fn locked(name: &str, version: &str, dirs: &[&str]) -> LockedAddon {
+    LockedAddon {
+        name: name.into(),
+        provider: Provider::CurseForge,
+        flavor: Flavor::Retail,
+        channel: Channel::Beta,
+        project_id: Some(1),
+        resolved_version: version.into(),
+        resolved_id: "1".into(),
+        download_url: String::new(),
+        sha256: None,
+        installed_dirs: dirs.iter().map(|d| (*d).to_owned()).collect(),
+        installed_at: Utc::now(),
+    }
+}
+
+fn fixture() -> Vec<AddonReport> {
+    let scanned = [
+        on_disk("Bagnon", Some("10.2.4")),
+        on_disk("Bagnon_Config", None),
+        on_disk("MyHack", Some("0.1")),
+        on_disk("MyHack_Options", None),
+    ];
+    let mut lock = Lock::new(Tag::new("test"));
+    lock.upsert(locked("Bagnon", "10.2.5", &["Bagnon", "Bagnon_Config"]));
+    lock.upsert(locked("Details", "1.0", &["Details"]));
+    let manifest = Manifest {
+        schema: 1,
+        addon: vec![
+            row("Bagnon", Provider::CurseForge, None),
+            row("Plater", Provider::WoWInterface, None),
+            row("Questie", Provider::GitHub, Some(vec![Flavor::Era])),
+        ],
+    };
+    report(
+        &group(&scanned, &lock, &Flavor::Retail),
+        &lock,
+        &manifest,
+        &Flavor::Retail,
+        None,
+    )
+}
+
+fn find<'a>(reports: &'a [AddonReport], name: &str) -> &'a AddonReport {
+    reports.iter().find(|r| r.name == name).unwrap()
+}
+
+#[test]
+fn report_marks_locked_addon_on_disk_as_managed() {
+    let reports = fixture();
+    let bagnon = find(&reports, "Bagnon");
+    assert_eq!(bagnon.status, AddonStatus::Managed);
+    assert_eq!(bagnon.provider, Some(Provider::CurseForge));
+    assert_eq!(bagnon.channel, Some(Channel::Beta));
+    assert_eq!(bagnon.locked_version.as_deref(), Some("10.2.5"));
+    assert_eq!(bagnon.toc_version.as_deref(), Some("10.2.4"));
+    assert!(bagnon.in_manifest);
+}
+
+#[test]
+fn report_marks_locked_and_manifest_addons_absent_from_disk_as_missing() {
+    let reports = fixture();
+    let details = find(&reports, "Details");
+    assert_eq!(details.status, AddonStatus::Missing);
+    assert_eq!(details.missing_folders, vec!["Details"]);
+    assert!(!details.in_manifest);
+    assert!(details.compatibility.is_none());
+
+    let plater = find(&reports, "Plater");
+    assert_eq!(plater.status, AddonStatus::Missing);
+    assert_eq!(plater.provider, Some(Provider::WoWInterface));
+    assert!(plater.locked_version.is_none());
+}
+
+#[test]
+fn report_lists_unowned_folders_as_unmanaged() {
+    let reports = fixture();
+    let hack = find(&reports, "MyHack");
+    assert_eq!(hack.status, AddonStatus::Unmanaged);
+    assert_eq!(hack.toc_version.as_deref(), Some("0.1"));
+    assert!(hack.provider.is_none());
+    assert_eq!(hack.folders, vec!["MyHack", "MyHack_Options"]);
+    assert_eq!(hack.compatibility, Some(Compatibility::Compatible));
+    // Bagnon_Config is owned by the Bagnon entry, not listed on its own.
+    assert!(reports.iter().all(|r| r.name != "Bagnon_Config"));
+}
+
+#[test]
+fn report_checks_unmanaged_folders_against_the_requested_flavor() {
+    let scanned = [on_disk("MyHack", Some("0.1"))];
+    let lock = Lock::new(Tag::new("test"));
+    let manifest = Manifest {
+        schema: 1,
+        addon: vec![],
+    };
+    let reports = report(
+        &group(&scanned, &lock, &Flavor::Era),
+        &lock,
+        &manifest,
+        &Flavor::Era,
+        None,
+    );
+    assert_eq!(reports[0].compatibility, Some(Compatibility::Incompatible));
+}
+
+#[test]
+fn report_skips_manifest_rows_for_other_flavors() {
+    assert!(fixture().iter().all(|r| r.name != "Questie"));
+}
+
+#[test]
+fn filter_matches_on_every_set_field() {
+    let reports = fixture();
+    let names = |filter: ReportFilter| -> Vec<&str> {
+        let mut names: Vec<&str> = reports
+            .iter()
+            .filter(|r| filter.matches(r))
+            .map(|r| r.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names
+    };
+
+    assert_eq!(
+        names(ReportFilter {
+            status: Some(AddonStatus::Missing),
+            ..Default::default()
+        }),
+        vec!["Details", "Plater"]
+    );
+    assert_eq!(
+        names(ReportFilter {
+            provider: Some(Provider::CurseForge),
+            status: Some(AddonStatus::Managed),
+            ..Default::default()
+        }),
+        vec!["Bagnon"]
+    );
+    assert_eq!(
+        names(ReportFilter {
+            flavor: Some(Flavor::Retail),
+            ..Default::default()
+        }),
+        vec!["Bagnon", "Details", "Plater"]
+    );
+}
+
+#[test]
+fn sort_orders_by_key_then_name() {
+    let mut reports = fixture();
+
+    sort(&mut reports, SortKey::Name);
+    let names: Vec<&str> = reports.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["Bagnon", "Details", "MyHack", "Plater"]);
+
+    sort(&mut reports, SortKey::Status);
+    let names: Vec<&str> = reports.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["Bagnon", "Details", "Plater", "MyHack"]);
+
+    sort(&mut reports, SortKey::Provider);
+    let names: Vec<&str> = reports.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["Bagnon", "Details", "Plater", "MyHack"]);
+}
+
+#[test]
+fn status_and_sort_key_parse_from_slugs() {
+    assert_eq!("missing".parse::<AddonStatus>(), Ok(AddonStatus::Missing));
+    assert_eq!("provider".parse::<SortKey>(), Ok(SortKey::Provider));
+    assert!("broken".parse::<AddonStatus>().is_err());
+}
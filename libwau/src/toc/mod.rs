@@ -25,6 +25,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::model::Flavor;
+
 #[cfg(test)]
 mod tests;
 
@@ -148,6 +150,32 @@ pub struct TocFile {
     pub files: Vec<String>,
 }
 
+impl TocFile {
+    /// Returns the `## X-*` field `key` (e.g. `"X-Curse-Project-ID"`), matched
+    /// case-insensitively like every other directive.
+    pub fn x_field(&self, key: &str) -> Option<&str> {
+        self.x_fields
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The interface numbers a `flavor` client reads: `## Interface-<Suffix>` for
+    /// the first of [`Flavor::client_suffixes`] the file sets, else `## Interface`.
+    pub fn interface_for(&self, flavor: &Flavor) -> &[u32] {
+        flavor
+            .client_suffixes()
+            .iter()
+            .find_map(|suffix| {
+                self.interface_flavor
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(suffix))
+                    .map(|(_, v)| v.as_slice())
+            })
+            .unwrap_or(&self.interface)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -169,6 +197,60 @@ pub fn parse(path: &Path) -> Option<TocFile> {
     Some(toc)
 }
 
+/// Client-specific `.toc` filename suffixes, e.g. `MyAddon_Vanilla.toc` or the older
+/// `MyAddon-Classic.toc`. Compared case-insensitively.
+const FLAVOR_SUFFIXES: &[&str] = &[
+    "_mainline",
+    "_classic",
+    "_vanilla",
+    "_tbc",
+    "_bcc",
+    "_wrath",
+    "_wotlkc",
+    "_cata",
+    "_mists",
+    "-mainline",
+    "-classic",
+    "-bcc",
+    "-wotlkc",
+];
+
+/// Returns the addon folder name a `.toc` filename belongs to, i.e. the basename
+/// with the `.toc` extension and any client suffix removed.
+///
+/// The WoW client only loads `Folder/Folder.toc` (or a suffixed variant), so this is
+/// the name the containing directory must have. Returns `None` if `file_name` is not
+/// a `.toc` file.
+pub fn addon_name(file_name: &str) -> Option<&str> {
+    split_file_name(file_name).map(|(base, _)| base)
+}
+
+/// Returns the client suffix of a `.toc` filename without its separator, e.g.
+/// `Vanilla` for `MyAddon_Vanilla.toc`; `None` for an unsuffixed or non-`.toc` file.
+pub fn client_suffix(file_name: &str) -> Option<&str> {
+    split_file_name(file_name).and_then(|(_, suffix)| suffix)
+}
+
+fn split_file_name(file_name: &str) -> Option<(&str, Option<&str>)> {
+    let stem = file_name
+        .len()
+        .checked_sub(4)
+        .filter(|&i| file_name.is_char_boundary(i) && file_name[i..].eq_ignore_ascii_case(".toc"))
+        .map(|i| &file_name[..i])
+        .filter(|s| !s.is_empty())?;
+
+    let lower = stem.to_ascii_lowercase();
+    Some(
+        FLAVOR_SUFFIXES
+            .iter()
+            .find(|suffix| lower.len() > suffix.len() && lower.ends_with(*suffix))
+            .map_or((stem, None), |suffix| {
+                let at = stem.len() - suffix.len();
+                (&stem[..at], Some(&stem[at + 1..]))
+            }),
+    )
+}
+
 /// Parses `.toc` content from a string slice.
 ///
 /// Best-effort and infallible: unknown directives are ignored, malformed lines are
@@ -340,6 +340,13 @@ fn x_field_case_preserved() {
     );
 }
 
+#[test]
+fn x_field_lookup_ignores_case() {
+    let toc = parse_str("## x-curse-project-id: 12345\n");
+    assert_eq!(toc.x_field("X-Curse-Project-ID"), Some("12345"));
+    assert_eq!(toc.x_field("X-WoWI-ID"), None);
+}
+
 #[test]
 fn multiple_x_fields_all_stored() {
     let content = "## X-Curse-Project-ID: 274066\n## X-Wago-ID: nQN5aoNB\n## X-DBM-Mod: 1\n";
@@ -671,3 +678,41 @@ fn fixture_deps_saved_vars_per_char() {
     let toc = parse_str(FIXTURE_DEPS);
     assert_eq!(toc.saved_variables_per_character, vec!["DepCharDB"]);
 }
+
+// ---------------------------------------------------------------------------
+// addon_name
+// ---------------------------------------------------------------------------
+
+#[test]
+fn addon_name_strips_extension() {
+    assert_eq!(addon_name("WeakAuras.toc"), Some("WeakAuras"));
+    assert_eq!(addon_name("WeakAuras.TOC"), Some("WeakAuras"));
+}
+
+#[test]
+fn addon_name_strips_client_suffixes() {
+    assert_eq!(addon_name("Details_Vanilla.toc"), Some("Details"));
+    assert_eq!(addon_name("Details_Mainline.toc"), Some("Details"));
+    assert_eq!(addon_name("Questie-Classic.toc"), Some("Questie"));
+    assert_eq!(addon_name("Questie-WOTLKC.toc"), Some("Questie"));
+}
+
+#[test]
+fn addon_name_keeps_non_client_suffixes() {
+    assert_eq!(addon_name("DBM-Core.toc"), Some("DBM-Core"));
+    assert_eq!(addon_name("Plater_Options.toc"), Some("Plater_Options"));
+}
+
+#[test]
+fn client_suffix_returns_suffix_without_separator() {
+    assert_eq!(client_suffix("Details_Vanilla.toc"), Some("Vanilla"));
+    assert_eq!(client_suffix("Questie-WOTLKC.TOC"), Some("WOTLKC"));
+    assert_eq!(client_suffix("DBM-Core.toc"), None);
+    assert_eq!(client_suffix("README.md"), None);
+}
+
+#[test]
+fn addon_name_rejects_non_toc() {
+    assert_eq!(addon_name("README.md"), None);
+    assert_eq!(addon_name(".toc"), None);
+}
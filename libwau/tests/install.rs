@@ -5,6 +5,7 @@
 use std::{fs, io::Write as _, path::PathBuf};
 
 use libwau::{
+    events::NullSink,
     lock::{self, Lock},
     manifest::ManifestAddon,
     model::{Channel, Flavor, Provider, Tag},
@@ -42,6 +43,8 @@ fn make_ctx(addons_path: PathBuf, cache_dir: PathBuf) -> InstallContext {
         channel: Channel::Stable,
         addons_path,
         cache_dir,
+        limits: Default::default(),
+        conflicts: Default::default(),
     }
 }
 
@@ -99,7 +102,7 @@ async fn install_and_remove_single_addon() {
     let mut lock = Lock::new(Tag::new("test"));
 
     // Install
-    ops::install(&provider, &addon, &ctx, &mut lock)
+    ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
         .await
         .unwrap();
 
@@ -117,7 +120,9 @@ async fn install_and_remove_single_addon() {
     assert_eq!(reloaded.addon[0].name, "WeakAuras");
 
     // Remove
-    ops::remove("WeakAuras", &ctx, &mut lock).await.unwrap();
+    ops::remove("WeakAuras", &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
 
     assert!(lock.addon.is_empty());
     assert!(!addons_dir.join("WeakAuras").exists());
@@ -160,7 +165,7 @@ async fn install_multi_folder_addon() {
     let ctx = make_ctx(addons_dir.clone(), cache_dir);
     let mut lock = Lock::new(Tag::new("test"));
 
-    ops::install(&provider, &addon, &ctx, &mut lock)
+    ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
         .await
         .unwrap();
 
@@ -197,10 +202,10 @@ async fn reinstall_replaces_existing_dirs_and_lock_entry() {
     let ctx = make_ctx(addons_dir.clone(), cache_dir);
     let mut lock = Lock::new(Tag::new("test"));
 
-    ops::install(&provider, &addon, &ctx, &mut lock)
+    ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
         .await
         .unwrap();
-    ops::install(&provider, &addon, &ctx, &mut lock)
+    ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
         .await
         .unwrap();
 
@@ -208,6 +213,51 @@ async fn reinstall_replaces_existing_dirs_and_lock_entry() {
     assert!(addons_dir.join("MyAddon").exists());
 }
 
+#[tokio::test]
+async fn reinstall_detects_zip_replaced_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let entries_v1: &[(&str, &[u8])] = &[
+        ("MyAddon/", &[]),
+        (
+            "MyAddon/MyAddon.toc",
+            b"## Interface: 110200\n## Version: 1\n",
+        ),
+    ];
+    let zip = write_zip(dir.path(), "MyAddon.zip", entries_v1);
+
+    let addons_dir = dir.path().join("AddOns");
+    let provider = LocalProvider::new();
+    let addon = make_addon("MyAddon", &zip);
+    let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
+    let mut lock = Lock::new(Tag::new("test"));
+
+    ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    let unchanged = ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(matches!(unchanged, ops::InstallOutcome::UpToDate { .. }));
+
+    write_zip(
+        dir.path(),
+        "MyAddon.zip",
+        &[
+            ("MyAddon/", &[]),
+            (
+                "MyAddon/MyAddon.toc",
+                b"## Interface: 110200\n## Version: 2\n",
+            ),
+        ],
+    );
+    let changed = ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
+        .await
+        .unwrap();
+    assert!(matches!(changed, ops::InstallOutcome::Updated { .. }));
+    let toc = fs::read_to_string(addons_dir.join("MyAddon").join("MyAddon.toc")).unwrap();
+    assert!(toc.contains("Version: 2"));
+}
+
 // ---------------------------------------------------------------------------
 // file:// URL support
 // ---------------------------------------------------------------------------
@@ -233,7 +283,7 @@ async fn install_via_file_url() {
     let ctx = make_ctx(addons_dir.clone(), dir.path().join("cache"));
     let mut lock = Lock::new(Tag::new("test"));
 
-    ops::install(&provider, &addon, &ctx, &mut lock)
+    ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
         .await
         .unwrap();
     assert!(addons_dir.join("TestAddon").exists());
@@ -253,7 +303,7 @@ async fn install_fails_when_url_missing() {
     let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
     let mut lock = Lock::new(Tag::new("test"));
 
-    let result = ops::install(&provider, &addon, &ctx, &mut lock).await;
+    let result = ops::install(&provider, &addon, &ctx, &mut lock, &NullSink).await;
     assert!(matches!(result, Err(libwau::Error::LocalMissingUrl { .. })));
 }
 
@@ -266,7 +316,7 @@ async fn install_fails_when_zip_not_found() {
     let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
     let mut lock = Lock::new(Tag::new("test"));
 
-    let result = ops::install(&provider, &addon, &ctx, &mut lock).await;
+    let result = ops::install(&provider, &addon, &ctx, &mut lock, &NullSink).await;
     assert!(result.is_err());
 }
 
@@ -276,7 +326,7 @@ async fn remove_fails_when_addon_not_in_lock() {
     let ctx = make_ctx(dir.path().join("AddOns"), dir.path().join("cache"));
     let mut lock = Lock::new(Tag::new("test"));
 
-    let result = ops::remove("NotInstalled", &ctx, &mut lock).await;
+    let result = ops::remove("NotInstalled", &ctx, &mut lock, &NullSink).await;
     assert!(matches!(result, Err(libwau::Error::AddonNotInLock { .. })));
 }
 
@@ -312,10 +362,12 @@ async fn lock_round_trip_after_install() {
         channel: Channel::Stable,
         addons_path: addons_dir,
         cache_dir: dir.path().join("cache"),
+        limits: Default::default(),
+        conflicts: Default::default(),
     };
     let mut lock = Lock::new(Tag::new("era"));
 
-    ops::install(&provider, &addon, &ctx, &mut lock)
+    ops::install(&provider, &addon, &ctx, &mut lock, &NullSink)
         .await
         .unwrap();
     lock::save(&lock, &lock_path).unwrap();
@@ -1,14 +1,31 @@
 //! Top-level command dispatch. `main` calls `run`; all logic lives here or in `libwau`.
 
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, IsTerminal, Write},
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
+
 use libwau::{
-    lock::{self, Lock},
-    manifest, ops, providers,
+    client::{Client, Product},
+    journal::{self, Journal, JournalEntry, Recovery},
+    lock::{self, Lock, LockedAddon},
+    manifest,
+    model::{Flavor, Tag},
+    ops::{self, InstallOutcome, SyncJob, UpdateStatus},
+    providers::{self, ConflictPolicy, InstallContext, ProviderConfig},
+    status::{self, AddonReport},
 };
 
 use crate::{
     cli::{Cli, Command},
+    config::{self, FoundInstall, Install},
     output,
-    settings::{ListSettings, RemoveSettings, SettingsError, SyncSettings},
+    settings::{
+        AdoptSettings, FilesSettings, InitSettings, ListSettings, OutdatedSettings, OwnsSettings,
+        RecoverSettings, RemoveSettings, SettingsError, SyncSettings,
+    },
 };
 
 #[cfg(test)]
@@ -21,14 +38,45 @@ pub enum AppError {
 
     #[error("{0}")]
     Libwau(#[from] libwau::Error),
+
+    #[error("{failed} addon(s) failed to sync; rerun with --retry-failed after fixing them")]
+    SyncFailed { failed: usize },
+
+    #[error("{failed} addon(s) could not be checked for updates")]
+    CheckFailed { failed: usize },
+
+    #[error("no folder '{folder}' in {}", addons_path.display())]
+    FolderNotFound {
+        folder: String,
+        addons_path: PathBuf,
+    },
+
+    #[error("config already exists at {}; rerun with --force to overwrite it", path.display())]
+    ConfigExists { path: PathBuf },
+
+    #[error("no WoW installs to write; pass --root <wow root or wine prefix>")]
+    NoInstallsFound,
 }
 
+/// Exit status of a command that completed without error.
+pub const EXIT_OK: i32 = 0;
+
+/// Exit status of `wau outdated` when at least one addon has an update available.
+/// Matches `dnf check-update` so existing prompt/cron snippets carry over.
+pub const EXIT_UPDATES_AVAILABLE: i32 = 100;
+
 /// Dispatches the parsed CLI command and returns an exit code (0 = success).
-pub async fn run(cli: &Cli) -> Result<(), AppError> {
+pub async fn run(cli: &Cli) -> Result<i32, AppError> {
     match &cli.command {
-        Command::List(_) => list(cli),
-        Command::Sync(_) => sync(cli).await,
-        Command::Remove(_) => remove(cli).await,
+        Command::List(_) => list(cli).map(|()| EXIT_OK),
+        Command::Sync(_) => sync(cli).await.map(|()| EXIT_OK),
+        Command::Remove(_) => remove(cli).await.map(|()| EXIT_OK),
+        Command::Outdated(_) => outdated(cli).await,
+        Command::Recover(_) => recover(cli).await.map(|()| EXIT_OK),
+        Command::Owns(_) => owns(cli).map(|()| EXIT_OK),
+        Command::Files(_) => files(cli).map(|()| EXIT_OK),
+        Command::Adopt(_) => adopt(cli).await.map(|()| EXIT_OK),
+        Command::Init(_) => init(cli).map(|()| EXIT_OK),
     }
 }
 
@@ -37,10 +85,373 @@ fn list(cli: &Cli) -> Result<(), AppError> {
     tracing::debug!(tag = %settings.tag, path = %settings.addons_path.display(), "scanning addons");
 
     let addons = libwau::fs::scan(&settings.addons_path)?;
-    output::print_addon_list(&addons);
+    let lock = match lock::load(&settings.lock_path) {
+        Ok(l) => l,
+        Err(libwau::Error::LockNotFound { .. }) => Lock::new(settings.tag.clone()),
+        Err(e) => return Err(e.into()),
+    };
+    let manifest = match manifest::load(&settings.manifest_path) {
+        Ok(m) => m,
+        Err(libwau::Error::ManifestNotFound { .. }) => manifest::Manifest {
+            schema: manifest::SUPPORTED_SCHEMA,
+            addon: Vec::new(),
+        },
+        Err(e) => return Err(e.into()),
+    };
+
+    let groups = libwau::inventory::group(&addons, &lock, &settings.flavor);
+    let mut reports: Vec<AddonReport> = status::report(
+        &groups,
+        &lock,
+        &manifest,
+        &settings.flavor,
+        settings.client_interface,
+    )
+    .into_iter()
+    .filter(|r| settings.filter.matches(r))
+    .collect();
+    status::sort(&mut reports, settings.sort);
+    output::print_addon_report(&reports);
+    Ok(())
+}
+
+fn owns(cli: &Cli) -> Result<(), AppError> {
+    let settings = OwnsSettings::for_owns(cli)?;
+    tracing::debug!(tag = %settings.tag, folder = %settings.folder, "looking up folder owner");
+
+    let lock = match lock::load(&settings.lock_path) {
+        Ok(l) => l,
+        Err(libwau::Error::LockNotFound { .. }) => Lock::new(settings.tag.clone()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let present = settings.addons_path.join(&settings.folder).is_dir();
+    let owners = lock.owners(&settings.folder);
+    if !owners.is_empty() {
+        output::print_owners(&settings.folder, &owners, present);
+        return Ok(());
+    }
+    if !present {
+        return Err(AppError::FolderNotFound {
+            folder: settings.folder,
+            addons_path: settings.addons_path,
+        });
+    }
+
+    let scanned = libwau::fs::scan(&settings.addons_path)?
+        .into_iter()
+        .find(|a| a.folder == settings.folder);
+    output::print_unmanaged(&settings.folder, scanned.as_ref(), &settings.flavor);
+    Ok(())
+}
+
+fn files(cli: &Cli) -> Result<(), AppError> {
+    let settings = FilesSettings::for_files(cli)?;
+    tracing::debug!(tag = %settings.tag, addon = %settings.addon, "listing addon files");
+
+    let lock = match lock::load(&settings.lock_path) {
+        Ok(l) => l,
+        Err(libwau::Error::LockNotFound { .. }) => Lock::new(settings.tag.clone()),
+        Err(e) => return Err(e.into()),
+    };
+    let entry = lock
+        .find(&settings.addon, &settings.flavor)
+        .ok_or_else(|| libwau::Error::AddonNotInLock {
+            name: settings.addon.clone(),
+        })?;
+
+    let mut folders = Vec::with_capacity(entry.installed_dirs.len());
+    for dir in &entry.installed_dirs {
+        let files = match libwau::fs::addon_files(&settings.addons_path, dir) {
+            Ok(files) => Some(files),
+            Err(libwau::Error::Io(e)) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        folders.push((dir.as_str(), files));
+    }
+    output::print_files(&entry.name, &folders);
+    Ok(())
+}
+
+async fn adopt(cli: &Cli) -> Result<(), AppError> {
+    let settings = AdoptSettings::for_adopt(cli)?;
+    tracing::debug!(tag = %settings.tag, path = %settings.addons_path.display(), "adopting addons");
+
+    let addons = libwau::fs::scan(&settings.addons_path)?;
+    let mut lock = match lock::load(&settings.lock_path) {
+        Ok(l) => l,
+        Err(libwau::Error::LockNotFound { .. }) => Lock::new(settings.tag.clone()),
+        Err(e) => return Err(e.into()),
+    };
+    let manifest = match manifest::load(&settings.manifest_path) {
+        Ok(m) => m,
+        Err(libwau::Error::ManifestNotFound { .. }) => manifest::Manifest {
+            schema: manifest::SUPPORTED_SCHEMA,
+            addon: Vec::new(),
+        },
+        Err(e) => return Err(e.into()),
+    };
+
+    let groups = libwau::inventory::group(&addons, &lock, &settings.flavor);
+    let adoption =
+        libwau::adopt::plan(&groups, &lock, &manifest, &settings.flavor, &HashMap::new());
+    #[cfg(feature = "curseforge")]
+    let adoption = match &settings.provider_config.curseforge_api_key {
+        Some(api_key) => {
+            let fingerprinted =
+                fingerprint_unidentified(api_key, &settings.addons_path, &adoption).await;
+            if fingerprinted.is_empty() {
+                adoption
+            } else {
+                tracing::info!(
+                    count = fingerprinted.len(),
+                    "identified folders by fingerprint"
+                );
+                libwau::adopt::plan(&groups, &lock, &manifest, &settings.flavor, &fingerprinted)
+            }
+        }
+        None => adoption,
+    };
+    output::print_adoption(&adoption, settings.dry_run);
+    if settings.dry_run || adoption.addons.is_empty() {
+        return Ok(());
+    }
+
+    let rows: Vec<manifest::ManifestAddon> = adoption
+        .addons
+        .iter()
+        .filter(|a| a.new_row)
+        .map(|a| a.row.clone())
+        .collect();
+    manifest::append(&settings.manifest_path, &rows)?;
+    libwau::adopt::apply(&adoption, &mut lock, &settings.flavor, &settings.channel);
+    lock::save(&lock, &settings.lock_path)?;
+    Ok(())
+}
+
+/// Matches the folders `adoption` could not identify from their `.toc` against
+/// CurseForge fingerprints. A failed lookup only loses the fallback, so it is
+/// logged rather than returned.
+#[cfg(feature = "curseforge")]
+async fn fingerprint_unidentified(
+    api_key: &str,
+    addons_path: &Path,
+    adoption: &libwau::adopt::Adoption,
+) -> HashMap<String, libwau::adopt::Fingerprinted> {
+    use libwau::{
+        adopt::{Fingerprinted, SkipReason},
+        providers::curseforge::{CurseForgeProvider, fingerprint},
+    };
+
+    let mut folders = HashMap::new();
+    for skipped in &adoption.skipped {
+        if !matches!(
+            skipped.reason,
+            SkipReason::NoProviderId | SkipReason::WagoOnly(_)
+        ) {
+            continue;
+        }
+        for folder in &skipped.folders {
+            match fingerprint(&addons_path.join(folder)) {
+                Ok(print) => {
+                    folders.insert(print, folder.as_str());
+                }
+                Err(e) => {
+                    tracing::warn!(folder = %folder, error = %e, "cannot fingerprint folder")
+                }
+            }
+        }
+    }
+
+    let prints: Vec<u32> = folders.keys().copied().collect();
+    let matches = match CurseForgeProvider::new(api_key.to_owned())
+        .match_fingerprints(&prints)
+        .await
+    {
+        Ok(m) => m,
+        Err(e) => {
+            tracing::warn!(error = %e, "CurseForge fingerprint lookup failed");
+            return HashMap::new();
+        }
+    };
+
+    let mut found = HashMap::new();
+    for m in matches {
+        for (name, print) in &m.modules {
+            if folders.get(print) == Some(&name.as_str()) {
+                found.insert(
+                    name.clone(),
+                    Fingerprinted {
+                        project_id: m.project_id,
+                        file_id: m.file_id,
+                        version: m.version.clone(),
+                    },
+                );
+            }
+        }
+    }
+    found
+}
+
+/// Manifest `wau init` writes when none exists yet.
+const EMPTY_MANIFEST: &str = "\
+# Addons to install; see examples/manifest.toml for every provider and option.
+
+# Manifest format version for forward-compatible parsing.
+schema = 1
+
+# [[addon]]
+# name = \"Details\"
+# provider = \"curseforge\"
+# project_id = 61284
+";
+
+fn init(cli: &Cli) -> Result<(), AppError> {
+    let settings = InitSettings::for_init(cli);
+    tracing::debug!(config = %settings.config_path.display(), roots = ?settings.roots, "initialising config");
+    let interactive = settings.interactive && io::stdin().is_terminal();
+
+    if settings.config_path.exists() && !settings.force {
+        let overwrite = interactive
+            && ask_yes_no(
+                &format!("Overwrite {}?", settings.config_path.display()),
+                false,
+            )?;
+        if !overwrite {
+            return Err(AppError::ConfigExists {
+                path: settings.config_path,
+            });
+        }
+    }
+
+    let clients = libwau::client::discover(&settings.roots);
+    let mut installs: Vec<FoundInstall> = Vec::with_capacity(clients.len());
+    for client in &clients {
+        let taken: Vec<&str> = installs.iter().map(|f| f.install.tag.as_str()).collect();
+        let guess = client.flavor().or_else(|| client.flavor_hint());
+        let mut found = guess.clone().map(|f| found_install(client, f, &taken));
+        output::print_found_install(found.as_ref(), client);
+        if interactive {
+            if !ask_yes_no("Add it?", true)? {
+                continue;
+            }
+            if client.flavor().is_none() {
+                let flavor = ask_flavor(guess.as_ref())?;
+                found = Some(found_install(client, flavor, &taken));
+            }
+        }
+        match found {
+            Some(found) => installs.push(found),
+            None => output::print_undetected_client(client),
+        }
+    }
+    if installs.is_empty() {
+        return Err(AppError::NoInstallsFound);
+    }
+
+    if let Some(dir) = settings.config_path.parent() {
+        std::fs::create_dir_all(dir).map_err(libwau::Error::from)?;
+    }
+    std::fs::write(&settings.config_path, config::render(&installs))
+        .map_err(libwau::Error::from)?;
+    let manifest_written = !settings.manifest_path.exists();
+    if manifest_written {
+        std::fs::write(&settings.manifest_path, EMPTY_MANIFEST).map_err(libwau::Error::from)?;
+    }
+    output::print_init_done(
+        &settings.config_path,
+        installs.len(),
+        manifest_written.then_some(settings.manifest_path.as_path()),
+    );
     Ok(())
 }
 
+/// Describes `client` as a `flavor` config install with a tag not in `taken`:
+/// the product slug for Battle.net clients, else the flavor slug, numbered when
+/// repeated.
+fn found_install(client: &Client, flavor: Flavor, taken: &[&str]) -> FoundInstall {
+    let in_product_folder = client
+        .dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(Product::from_folder);
+    let (wow_root, product) = match (in_product_folder, client.dir.parent()) {
+        (Some(product), Some(parent)) => (parent.to_path_buf(), Some(product)),
+        _ => (client.dir.clone(), None),
+    };
+    let base = client
+        .product
+        .map_or(flavor.as_str(), |p| p.as_str())
+        .to_owned();
+    let tag = (1..)
+        .map(|n| match n {
+            1 => base.clone(),
+            n => format!("{base}-{n}"),
+        })
+        .find(|t| !taken.contains(&t.as_str()))
+        .unwrap_or(base);
+    let note = match client.flavor() {
+        Some(_) => format!("Detected: {client}"),
+        None => "Flavor not detected; check it matches the client".to_owned(),
+    };
+
+    FoundInstall {
+        install: Install {
+            tag: Tag::new(tag),
+            flavor,
+            wow_root,
+            product,
+            channel: None,
+        },
+        note,
+    }
+}
+
+/// Asks a yes/no question; an empty answer picks `default`.
+fn ask_yes_no(question: &str, default: bool) -> Result<bool, AppError> {
+    eprint!("{question} {} ", if default { "[Y/n]" } else { "[y/N]" });
+    io::stderr().flush().map_err(libwau::Error::from)?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .map_err(libwau::Error::from)?;
+    Ok(match answer.trim().to_ascii_lowercase().as_str() {
+        "" => default,
+        a => matches!(a, "y" | "yes"),
+    })
+}
+
+/// Prompts for the flavor of a client wau could not identify; an empty answer
+/// picks `default`, or asks again without one.
+fn ask_flavor(default: Option<&Flavor>) -> Result<Flavor, AppError> {
+    loop {
+        match default {
+            Some(default) => eprint!("Flavor (retail, classic-era, classic-tbc, …) [{default}]: "),
+            None => eprint!("Flavor (retail, classic-era, classic-tbc, …): "),
+        }
+        io::stderr().flush().map_err(libwau::Error::from)?;
+
+        let mut answer = String::new();
+        io::stdin()
+            .lock()
+            .read_line(&mut answer)
+            .map_err(libwau::Error::from)?;
+        match answer.trim() {
+            "" => {
+                if let Some(default) = default {
+                    return Ok(default.clone());
+                }
+            }
+            a => match a.parse() {
+                Ok(flavor) => return Ok(flavor),
+                Err(e) => eprintln!("{e}"),
+            },
+        }
+    }
+}
+
 async fn sync(cli: &Cli) -> Result<(), AppError> {
     let settings = SyncSettings::for_sync(cli)?;
     tracing::debug!(
@@ -67,23 +478,314 @@ async fn sync(cli: &Cli) -> Result<(), AppError> {
         channel: settings.channel.clone(),
         addons_path: settings.addons_path.clone(),
         cache_dir: settings.cache_dir.clone(),
+        limits: settings.limits,
+        conflicts: settings.conflicts,
     };
 
-    let plan = libwau::resolve::plan(&manifest, &lock, &ctx.flavor, settings.update);
+    let journal_path = journal::path(&settings.cache_dir, &settings.tag);
+    resolve_interrupted(
+        &journal_path,
+        &ctx,
+        &mut lock,
+        &settings.lock_path,
+        &settings.provider_config,
+    )
+    .await?;
 
-    let mut installed = 0u32;
-    for addon in plan.to_install {
-        let provider = providers::for_provider(&addon.provider, &settings.provider_config)?;
-        ops::install(provider.as_ref(), addon, &ctx, &mut lock).await?;
-        output::print_installed(&addon.name);
-        installed += 1;
+    let failed_path = journal::failed_path(&settings.cache_dir, &settings.tag);
+    let mut plan = libwau::resolve::plan(
+        &manifest,
+        &lock,
+        &ctx.flavor,
+        settings.update || settings.retry_failed,
+    );
+    if settings.retry_failed {
+        let retry = journal::load_failed(&failed_path)?;
+        if retry.is_empty() {
+            output::print_nothing_to_retry();
+            return Ok(());
+        }
+        plan.to_install.retain(|a| retry.contains(&a.name));
     }
 
-    lock::save(&lock, &settings.lock_path)?;
-    output::print_sync_summary(installed, plan.skipped as u32);
+    let mut journal = Journal::begin(&journal_path, &settings.tag, "sync")?;
+    let mut tally = Tally::default();
+    // Ordinary errors close the journal; only a crash leaves it for `wau recover`.
+    let run = async {
+        let mut failures: Vec<(String, libwau::Error)> = Vec::new();
+
+        let mut jobs = Vec::with_capacity(plan.to_install.len());
+        for addon in plan.to_install {
+            match providers::for_provider(&addon.provider, &settings.provider_config) {
+                Ok(provider) => jobs.push(SyncJob { addon, provider }),
+                Err(e) => {
+                    output::print_failed(&addon.name);
+                    failures.push((addon.name.clone(), e));
+                }
+            }
+        }
+        if settings.fail_fast && !failures.is_empty() {
+            jobs.clear();
+        }
+
+        let progress = output::ProgressLine::new();
+        ops::sync(
+            jobs,
+            &ctx,
+            &mut lock,
+            &settings.concurrency,
+            &progress,
+            |result, lock| {
+                progress.clear();
+                let name = &result.addon.name;
+                match result.outcome {
+                    Ok(outcome) => {
+                        tally.add(&outcome);
+                        record_outcome(
+                            result.addon,
+                            &outcome,
+                            result.previous,
+                            &ctx,
+                            lock,
+                            &mut journal,
+                            &settings.lock_path,
+                        )?;
+                        Ok(ControlFlow::Continue(()))
+                    }
+                    Err(e) => {
+                        tracing::debug!(name = %name, error = %e, "addon failed");
+                        output::print_failed(name);
+                        failures.push((name.clone(), e));
+                        Ok(if settings.fail_fast {
+                            ControlFlow::Break(())
+                        } else {
+                            ControlFlow::Continue(())
+                        })
+                    }
+                }
+            },
+        )
+        .await?;
+
+        if settings.conflicts == ConflictPolicy::Refuse && io::stdin().is_terminal() {
+            let mut remaining = Vec::with_capacity(failures.len());
+            for (name, error) in failures {
+                let libwau::Error::FolderConflict { claims, .. } = &error else {
+                    remaining.push((name, error));
+                    continue;
+                };
+                let Some(addon) = manifest.addon.iter().find(|a| a.name == name) else {
+                    remaining.push((name, error));
+                    continue;
+                };
+                output::print_folder_conflict(&name, claims);
+                if !ask_share()? {
+                    remaining.push((name, error));
+                    continue;
+                }
+
+                let shared = InstallContext {
+                    conflicts: ConflictPolicy::Share,
+                    ..ctx.clone()
+                };
+                let previous = lock.find(&name, &ctx.flavor).cloned();
+                let provider = providers::for_provider(&addon.provider, &settings.provider_config)?;
+                let result =
+                    ops::install(provider.as_ref(), addon, &shared, &mut lock, &progress).await;
+                progress.clear();
+                match result {
+                    Ok(outcome) => {
+                        tally.add(&outcome);
+                        record_outcome(
+                            addon,
+                            &outcome,
+                            previous,
+                            &ctx,
+                            &lock,
+                            &mut journal,
+                            &settings.lock_path,
+                        )?;
+                    }
+                    Err(e) => {
+                        output::print_failed(&name);
+                        remaining.push((name, e));
+                    }
+                }
+            }
+            failures = remaining;
+        }
+
+        lock::save(&lock, &settings.lock_path)?;
+        Ok::<_, AppError>(failures)
+    }
+    .await;
+    let failures = match run {
+        Ok(failures) => failures,
+        Err(e) => return Err(close_journal(journal, &mut lock, &settings.lock_path, e)),
+    };
+    journal.finish()?;
+
+    let failed_names: Vec<String> = failures.iter().map(|(name, _)| name.clone()).collect();
+    journal::save_failed(&failed_path, &failed_names)?;
+
+    output::print_sync_summary(
+        tally.installed,
+        tally.updated,
+        tally.up_to_date,
+        plan.skipped as u32,
+        failures.len() as u32,
+    );
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        output::print_failures(&failures);
+        if failures
+            .iter()
+            .any(|(_, e)| matches!(e, libwau::Error::FolderConflict { .. }))
+        {
+            output::print_allow_shared_hint();
+        }
+        Err(AppError::SyncFailed {
+            failed: failures.len(),
+        })
+    }
+}
+
+/// Per-outcome counters for the sync summary.
+#[derive(Debug, Default)]
+struct Tally {
+    installed: u32,
+    updated: u32,
+    up_to_date: u32,
+}
+
+impl Tally {
+    fn add(&mut self, outcome: &InstallOutcome) {
+        match outcome {
+            InstallOutcome::Installed { .. } => self.installed += 1,
+            InstallOutcome::Updated { .. } => self.updated += 1,
+            InstallOutcome::UpToDate { .. } => self.up_to_date += 1,
+        }
+    }
+}
+
+/// Journals and persists one completed sync job (unless nothing changed), then
+/// prints its outcome.
+fn record_outcome(
+    addon: &manifest::ManifestAddon,
+    outcome: &InstallOutcome,
+    previous: Option<LockedAddon>,
+    ctx: &InstallContext,
+    lock: &Lock,
+    journal: &mut Journal,
+    lock_path: &Path,
+) -> libwau::Result<()> {
+    if !matches!(outcome, InstallOutcome::UpToDate { .. }) {
+        let current = lock.find(&addon.name, &ctx.flavor).cloned();
+        journal.record(
+            JournalEntry::new(&addon.name, &ctx.flavor, previous, current).with_source(addon),
+        )?;
+        lock::save(lock, lock_path)?;
+    }
+    output::print_outcome(&addon.name, outcome);
     Ok(())
 }
 
+/// Asks whether to install an addon anyway, sharing its conflicting folders.
+fn ask_share() -> Result<bool, AppError> {
+    ask_yes_no("Install anyway and share these folders?", false)
+}
+
+async fn outdated(cli: &Cli) -> Result<i32, AppError> {
+    let settings = OutdatedSettings::for_outdated(cli)?;
+    tracing::debug!(
+        tag = %settings.tag,
+        manifest = %settings.manifest_path.display(),
+        lock = %settings.lock_path.display(),
+        "checking for updates"
+    );
+
+    let manifest = manifest::load(&settings.manifest_path)?;
+    let lock = match lock::load(&settings.lock_path) {
+        Ok(l) => l,
+        Err(libwau::Error::LockNotFound { .. }) => Lock::new(settings.tag.clone()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let ctx = providers::InstallContext {
+        tag: settings.tag.clone(),
+        flavor: settings.flavor.clone(),
+        channel: settings.channel.clone(),
+        addons_path: settings.addons_path.clone(),
+        cache_dir: settings.cache_dir.clone(),
+        limits: settings.limits,
+        conflicts: ConflictPolicy::Refuse,
+    };
+
+    let plan = libwau::resolve::plan(&manifest, &lock, &ctx.flavor, true);
+
+    let progress = output::ProgressLine::new();
+    let (statuses, failures) = check_all(
+        &plan.to_install,
+        &ctx,
+        &lock,
+        &settings.provider_config,
+        &progress,
+    )
+    .await;
+
+    progress.clear();
+    output::print_outdated(&statuses);
+    if !failures.is_empty() {
+        output::print_failures(&failures);
+    }
+    outdated_exit(&statuses, &failures)
+}
+
+/// Checks every addon, collecting per-addon failures instead of stopping at the
+/// first, so one bad row or network error leaves the rest of the report intact.
+async fn check_all(
+    addons: &[&manifest::ManifestAddon],
+    ctx: &InstallContext,
+    lock: &Lock,
+    provider_config: &ProviderConfig,
+    events: &dyn libwau::events::EventSink,
+) -> (Vec<UpdateStatus>, Vec<(String, libwau::Error)>) {
+    let mut statuses = Vec::with_capacity(addons.len());
+    let mut failures = Vec::new();
+    for addon in addons {
+        let status = match providers::for_provider(&addon.provider, provider_config) {
+            Ok(provider) => ops::check(provider.as_ref(), addon, ctx, lock, events).await,
+            Err(e) => Err(e),
+        };
+        match status {
+            Ok(status) => statuses.push(status),
+            Err(e) => {
+                tracing::debug!(name = %addon.name, error = %e, "check failed");
+                failures.push((addon.name.clone(), e));
+            }
+        }
+    }
+    (statuses, failures)
+}
+
+/// Exit status of `wau outdated`: updates available wins over failures, so a
+/// prompt or cron job still hears about the addons that did resolve.
+fn outdated_exit(
+    statuses: &[UpdateStatus],
+    failures: &[(String, libwau::Error)],
+) -> Result<i32, AppError> {
+    if statuses.iter().any(|s| s.outdated) {
+        Ok(EXIT_UPDATES_AVAILABLE)
+    } else if !failures.is_empty() {
+        Err(AppError::CheckFailed {
+            failed: failures.len(),
+        })
+    } else {
+        Ok(EXIT_OK)
+    }
+}
+
 async fn remove(cli: &Cli) -> Result<(), AppError> {
     let settings = RemoveSettings::for_remove(cli)?;
     tracing::debug!(
@@ -98,16 +800,202 @@ async fn remove(cli: &Cli) -> Result<(), AppError> {
     let ctx = providers::InstallContext {
         tag: settings.tag.clone(),
         flavor: settings.flavor.clone(),
-        channel: libwau::model::Channel::Stable,
+        channel: settings.channel.clone(),
+        addons_path: settings.addons_path.clone(),
+        cache_dir: settings.cache_dir.clone(),
+        limits: settings.limits,
+        conflicts: ConflictPolicy::Refuse,
+    };
+
+    let journal_path = journal::path(&settings.cache_dir, &settings.tag);
+    resolve_interrupted(
+        &journal_path,
+        &ctx,
+        &mut lock,
+        &settings.lock_path,
+        &settings.provider_config,
+    )
+    .await?;
+
+    // Catch typos before the journal opens, so they never look like a crash.
+    if let Some(name) = settings
+        .addons
+        .iter()
+        .find(|name| lock.find(name, &ctx.flavor).is_none())
+    {
+        return Err(libwau::Error::AddonNotInLock { name: name.clone() }.into());
+    }
+
+    let progress = output::ProgressLine::new();
+    let mut journal = Journal::begin(&journal_path, &settings.tag, "remove")?;
+    let run = async {
+        for addon_name in &settings.addons {
+            let previous = lock.find(addon_name, &ctx.flavor).cloned();
+            ops::remove(addon_name, &ctx, &mut lock, &progress).await?;
+            progress.clear();
+            journal.record(JournalEntry::new(addon_name, &ctx.flavor, previous, None))?;
+            lock::save(&lock, &settings.lock_path)?;
+            output::print_removed(addon_name);
+        }
+        lock::save(&lock, &settings.lock_path)?;
+        Ok::<_, AppError>(())
+    }
+    .await;
+    if let Err(e) = run {
+        return Err(close_journal(journal, &mut lock, &settings.lock_path, e));
+    }
+    journal.finish()?;
+    Ok(())
+}
+
+/// Closes the journal of a command that stopped on an ordinary error and returns
+/// `error`. Recorded changes are kept in the lock; the journal is only left behind
+/// when that lock cannot be saved, as the sole record of those changes.
+fn close_journal(journal: Journal, lock: &mut Lock, lock_path: &Path, error: AppError) -> AppError {
+    if let Err(e) = journal.close(lock, lock_path) {
+        tracing::warn!(error = %e, "keeping the journal of the failed run");
+    }
+    error
+}
+
+async fn recover(cli: &Cli) -> Result<(), AppError> {
+    let settings = RecoverSettings::for_recover(cli)?;
+    let journal_path = journal::path(&settings.cache_dir, &settings.tag);
+    tracing::debug!(
+        tag = %settings.tag,
+        journal = %journal_path.display(),
+        recovery = %settings.recovery,
+        "recovering interrupted run"
+    );
+
+    let Some(journal) = journal::load(&journal_path)? else {
+        output::print_nothing_to_recover(&settings.tag);
+        return Ok(());
+    };
+
+    let mut lock = match lock::load(&settings.lock_path) {
+        Ok(l) => l,
+        Err(libwau::Error::LockNotFound { .. }) => Lock::new(settings.tag.clone()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let ctx = providers::InstallContext {
+        tag: settings.tag.clone(),
+        flavor: settings.flavor.clone(),
+        channel: settings.channel.clone(),
         addons_path: settings.addons_path.clone(),
-        cache_dir: std::path::PathBuf::new(),
+        cache_dir: settings.cache_dir.clone(),
+        limits: settings.limits,
+        conflicts: ConflictPolicy::Refuse,
     };
 
-    for addon_name in &settings.addons {
-        ops::remove(addon_name, &ctx, &mut lock).await?;
-        output::print_removed(addon_name);
+    output::print_interrupted_run(&journal);
+    apply_recovery(
+        journal,
+        settings.recovery,
+        &ctx,
+        &mut lock,
+        &settings.lock_path,
+        &settings.provider_config,
+    )
+    .await
+}
+
+// ---------------------------------------------------------------------------
+// Interrupted-run recovery
+// ---------------------------------------------------------------------------
+
+/// Checks for a journal left by an interrupted run and resolves it before a new
+/// command changes the addons dir. Asks on a terminal; otherwise fails with
+/// [`libwau::Error::InterruptedRun`] so scripts never pick a direction silently.
+async fn resolve_interrupted(
+    journal_path: &Path,
+    ctx: &InstallContext,
+    lock: &mut Lock,
+    lock_path: &Path,
+    provider_config: &ProviderConfig,
+) -> Result<(), AppError> {
+    let Some(journal) = journal::load(journal_path)? else {
+        return Ok(());
+    };
+    if journal.entry.is_empty() {
+        // The run died before changing anything: nothing to decide.
+        tracing::debug!(path = %journal_path.display(), "clearing empty journal");
+        journal.finish()?;
+        return Ok(());
     }
 
-    lock::save(&lock, &settings.lock_path)?;
+    output::print_interrupted_run(&journal);
+    let interrupted = || libwau::Error::InterruptedRun {
+        tag: ctx.tag.clone(),
+        path: journal_path.to_path_buf(),
+    };
+    let recovery = if io::stdin().is_terminal() {
+        ask_recovery()?
+    } else {
+        None
+    };
+    let Some(recovery) = recovery else {
+        output::print_recover_hint(&ctx.tag);
+        return Err(interrupted().into());
+    };
+    apply_recovery(journal, recovery, ctx, lock, lock_path, provider_config).await
+}
+
+/// Prompts for a recovery direction; `None` means the user chose to abort.
+fn ask_recovery() -> Result<Option<Recovery>, AppError> {
+    loop {
+        eprint!("Roll [f]orward, roll [b]ack, or [a]bort? ");
+        io::stderr().flush().map_err(libwau::Error::from)?;
+
+        let mut answer = String::new();
+        io::stdin()
+            .lock()
+            .read_line(&mut answer)
+            .map_err(libwau::Error::from)?;
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "f" | "forward" => return Ok(Some(Recovery::RollForward)),
+            "b" | "back" => return Ok(Some(Recovery::RollBack)),
+            "" | "a" | "abort" => return Ok(None),
+            _ => continue,
+        }
+    }
+}
+
+/// Brings `lock` up to date with every journalled change, optionally reverts them
+/// newest first, then saves the lock and deletes the journal.
+async fn apply_recovery(
+    journal: Journal,
+    recovery: Recovery,
+    ctx: &InstallContext,
+    lock: &mut Lock,
+    lock_path: &Path,
+    provider_config: &ProviderConfig,
+) -> Result<(), AppError> {
+    if recovery == Recovery::RollBack {
+        for entry in &journal.entry {
+            ops::check_revert(entry)?;
+        }
+    }
+    journal.roll_forward(lock);
+    lock::save(lock, lock_path)?;
+
+    if recovery == Recovery::RollBack {
+        for entry in journal.entry.iter().rev() {
+            let Some(provider_id) = entry.provider() else {
+                continue;
+            };
+            let provider = providers::for_provider(provider_id, provider_config)?;
+            let progress = output::ProgressLine::new();
+            ops::revert(provider.as_ref(), entry, ctx, lock, &progress).await?;
+            progress.clear();
+            lock::save(lock, lock_path)?;
+            output::print_reverted(entry);
+        }
+    }
+
+    let changes = journal.entry.len();
+    journal.finish()?;
+    output::print_recovered(recovery, changes);
     Ok(())
 }
@@ -1,5 +1,8 @@
 use super::*;
-use crate::cli::{Cli, Command, ListArgs, RemoveArgs, SyncArgs};
+use crate::cli::{
+    AdoptArgs, Cli, Command, FilesArgs, InitArgs, ListArgs, OutdatedArgs, OwnsArgs, RecoverArgs,
+    RemoveArgs, SyncArgs,
+};
 
 // Integration-level tests require a real config file + addons directory on disk,
 // so they live in libwau/tests/ once the full pipeline is wired (Phase 3+).
@@ -14,7 +17,13 @@ fn missing_config_cli(command: Command) -> Cli {
 
 #[tokio::test]
 async fn list_returns_settings_error_when_config_missing() {
-    let cli = missing_config_cli(Command::List(ListArgs { tag: None }));
+    let cli = missing_config_cli(Command::List(ListArgs {
+        tag: None,
+        provider: None,
+        status: None,
+        flavor: None,
+        sort: Default::default(),
+    }));
     assert!(run(&cli).await.is_err());
 }
 
@@ -24,6 +33,11 @@ async fn sync_returns_settings_error_when_config_missing() {
         tag: None,
         manifest: None,
         update: false,
+        retry_failed: false,
+        fail_fast: false,
+        allow_shared: false,
+        flavor: None,
+        channel: None,
     }));
     assert!(run(&cli).await.is_err());
 }
@@ -36,3 +50,155 @@ async fn remove_returns_settings_error_when_config_missing() {
     }));
     assert!(run(&cli).await.is_err());
 }
+
+#[tokio::test]
+async fn outdated_returns_settings_error_when_config_missing() {
+    let cli = missing_config_cli(Command::Outdated(OutdatedArgs {
+        tag: None,
+        manifest: None,
+    }));
+    assert!(run(&cli).await.is_err());
+}
+
+#[tokio::test]
+async fn recover_returns_settings_error_when_config_missing() {
+    let cli = missing_config_cli(Command::Recover(RecoverArgs {
+        tag: None,
+        forward: true,
+        back: false,
+    }));
+    assert!(run(&cli).await.is_err());
+}
+
+#[tokio::test]
+async fn owns_fails_on_missing_config() {
+    let cli = missing_config_cli(Command::Owns(OwnsArgs {
+        tag: None,
+        folder: "WeakAuras".into(),
+    }));
+    assert!(run(&cli).await.is_err());
+}
+
+#[tokio::test]
+async fn files_fails_on_missing_config() {
+    let cli = missing_config_cli(Command::Files(FilesArgs {
+        tag: None,
+        addon: "WeakAuras".into(),
+    }));
+    assert!(run(&cli).await.is_err());
+}
+
+#[tokio::test]
+async fn adopt_fails_on_missing_config() {
+    let cli = missing_config_cli(Command::Adopt(AdoptArgs {
+        tag: None,
+        dry_run: true,
+    }));
+    assert!(run(&cli).await.is_err());
+}
+
+#[tokio::test]
+async fn init_finds_nothing_without_installs() {
+    let cli = missing_config_cli(Command::Init(InitArgs {
+        roots: vec![PathBuf::from("/nonexistent/wow")],
+        non_interactive: true,
+        force: false,
+    }));
+    assert!(matches!(run(&cli).await, Err(AppError::NoInstallsFound)));
+}
+
+#[cfg(feature = "local")]
+#[tokio::test]
+async fn outdated_reports_the_addons_that_resolved_past_a_failing_one() {
+    let dir = tempfile::tempdir().unwrap();
+    let row = |name: &str, url: Option<&str>| manifest::ManifestAddon {
+        name: name.into(),
+        provider: libwau::model::Provider::Local,
+        channel: None,
+        flavors: None,
+        pin: None,
+        project_id: None,
+        wowi_id: None,
+        repo: None,
+        asset_regex: None,
+        git_ref: None,
+        url: url.map(str::to_owned),
+    };
+    let zip = dir.path().join("Fresh.zip");
+    // No `url`: the local provider cannot resolve it.
+    let broken = row("Broken", None);
+    let fresh = row("Fresh", Some(zip.to_str().unwrap()));
+    let ctx = InstallContext {
+        tag: Tag::new("test"),
+        flavor: Flavor::Retail,
+        channel: libwau::model::Channel::Stable,
+        addons_path: dir.path().join("AddOns"),
+        cache_dir: dir.path().join("cache"),
+        limits: Default::default(),
+        conflicts: Default::default(),
+    };
+    let lock = Lock::new(Tag::new("test"));
+
+    let (statuses, failures) = check_all(
+        &[&broken, &fresh],
+        &ctx,
+        &lock,
+        &ProviderConfig::default(),
+        &libwau::events::NullSink,
+    )
+    .await;
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].name, "Fresh");
+    assert!(statuses[0].outdated);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, "Broken");
+    assert!(matches!(
+        failures[0].1,
+        libwau::Error::LocalMissingUrl { .. }
+    ));
+
+    // Updates still get their exit status; failures alone are an error.
+    assert_eq!(
+        outdated_exit(&statuses, &failures).unwrap(),
+        EXIT_UPDATES_AVAILABLE
+    );
+    assert!(matches!(
+        outdated_exit(&[], &failures),
+        Err(AppError::CheckFailed { failed: 1 })
+    ));
+    assert_eq!(outdated_exit(&[], &[]).unwrap(), EXIT_OK);
+}
+
+#[test]
+fn found_install_tags_by_product_and_keeps_the_battle_net_root() {
+    let client = |dir: &str, product: Option<Product>| Client {
+        product,
+        dir: PathBuf::from(dir),
+        version: None,
+    };
+
+    let retail = found_install(
+        &client("/wow/_retail_", Some(Product::Retail)),
+        Flavor::Retail,
+        &[],
+    );
+    assert_eq!(retail.install.tag, Tag::new("retail"));
+    assert_eq!(retail.install.flavor, Flavor::Retail);
+    assert_eq!(retail.install.wow_root, PathBuf::from("/wow"));
+    assert_eq!(retail.install.product, Some(Product::Retail));
+
+    // A second retail client gets a numbered tag.
+    let second = found_install(
+        &client("/other/_retail_", Some(Product::Retail)),
+        Flavor::Retail,
+        &["retail"],
+    );
+    assert_eq!(second.install.tag, Tag::new("retail-2"));
+
+    // Flat installs keep their root and no product, and are tagged by flavor.
+    let flat = found_install(&client("/games/Turtle", None), Flavor::Era, &[]);
+    assert_eq!(flat.install.wow_root, PathBuf::from("/games/Turtle"));
+    assert_eq!(flat.install.product, None);
+    assert_eq!(flat.install.tag, Tag::new("classic-era"));
+    assert!(flat.note.contains("not detected"));
+}
@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
+use libwau::{
+    model::{Channel, Flavor, Provider},
+    status::{AddonStatus, SortKey},
+};
 
 #[cfg(test)]
 mod tests;
@@ -24,6 +28,21 @@ pub enum Command {
     Sync(SyncArgs),
     /// Remove installed addons recorded in the lock.
     Remove(RemoveArgs),
+    /// Check manifest addons for updates without installing anything.
+    ///
+    /// Exits with status 100 when at least one addon has an update available.
+    Outdated(OutdatedArgs),
+    /// Resolve a sync or remove that was interrupted before it finished.
+    Recover(RecoverArgs),
+    /// Show which locked addon installed a folder under `Interface/AddOns`.
+    Owns(OwnsArgs),
+    /// List the folders and files a locked addon installed.
+    Files(FilesArgs),
+    /// Add addons installed by another manager to the manifest and lock,
+    /// using the provider IDs in their `.toc` files.
+    Adopt(AdoptArgs),
+    /// Find WoW installs on this machine and write a starter config and manifest.
+    Init(InitArgs),
 }
 
 #[derive(Debug, clap::Args)]
@@ -31,6 +50,22 @@ pub struct ListArgs {
     /// Install tag to use (default: config `defaults.install_tag`).
     #[arg(short, long, value_name = "TAG")]
     pub tag: Option<String>,
+
+    /// Only show addons from this provider (curseforge, wowinterface, github, local).
+    #[arg(short, long, value_name = "PROVIDER")]
+    pub provider: Option<Provider>,
+
+    /// Only show addons with this status (managed, missing, unmanaged).
+    #[arg(short, long, value_name = "STATUS")]
+    pub status: Option<AddonStatus>,
+
+    /// Only show addons locked or listed for this flavor.
+    #[arg(short, long, value_name = "FLAVOR")]
+    pub flavor: Option<Flavor>,
+
+    /// Sort by name, status, provider or flavor.
+    #[arg(long, value_name = "KEY", default_value = "name")]
+    pub sort: SortKey,
 }
 
 #[derive(Debug, clap::Args)]
@@ -46,6 +81,26 @@ pub struct SyncArgs {
     /// Re-install addons already present in the lock (apply updates).
     #[arg(long)]
     pub update: bool,
+
+    /// Only re-run the addons that failed during the previous sync of this tag.
+    #[arg(long)]
+    pub retry_failed: bool,
+
+    /// Stop at the first addon that fails instead of continuing with the rest.
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Install addons that ship folders another addon already owns, sharing them.
+    #[arg(long)]
+    pub allow_shared: bool,
+
+    /// Resolve files for this flavor instead of the install's.
+    #[arg(long, value_name = "FLAVOR")]
+    pub flavor: Option<Flavor>,
+
+    /// Resolve releases from this channel instead of the install's (stable, beta, alpha).
+    #[arg(long, value_name = "CHANNEL")]
+    pub channel: Option<Channel>,
 }
 
 #[derive(Debug, clap::Args)]
@@ -58,3 +113,78 @@ pub struct RemoveArgs {
     #[arg(required = true, value_name = "ADDON")]
     pub addons: Vec<String>,
 }
+
+#[derive(Debug, clap::Args)]
+pub struct OutdatedArgs {
+    /// Install tag to use (default: config `defaults.install_tag`).
+    #[arg(short, long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Path to manifest file (default: `$XDG_CONFIG_HOME/wau/manifest.toml`).
+    #[arg(short, long, value_name = "PATH")]
+    pub manifest: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct OwnsArgs {
+    /// Install tag to use (default: config `defaults.install_tag`).
+    #[arg(short, long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Folder name, or a path to it or to a file inside it.
+    #[arg(value_name = "FOLDER")]
+    pub folder: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct FilesArgs {
+    /// Install tag to use (default: config `defaults.install_tag`).
+    #[arg(short, long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Addon name (as listed in the manifest/lock).
+    #[arg(value_name = "ADDON")]
+    pub addon: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct AdoptArgs {
+    /// Install tag to use (default: config `defaults.install_tag`).
+    #[arg(short, long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Show what would be adopted without writing the manifest or lock.
+    #[arg(short = 'n', long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, clap::Args)]
+#[command(group(clap::ArgGroup::new("direction").required(true).args(["forward", "back"])))]
+pub struct RecoverArgs {
+    /// Install tag to use (default: config `defaults.install_tag`).
+    #[arg(short, long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Keep the changes the interrupted run completed and update the lock to match.
+    #[arg(long)]
+    pub forward: bool,
+
+    /// Undo the changes the interrupted run completed, newest first.
+    #[arg(long)]
+    pub back: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct InitArgs {
+    /// Also search this WoW root, Wine prefix, or folder of prefixes (repeatable).
+    #[arg(short, long = "root", value_name = "PATH")]
+    pub roots: Vec<PathBuf>,
+
+    /// Search only the `--root` paths, add every install found, and never prompt.
+    #[arg(long, requires = "roots")]
+    pub non_interactive: bool,
+
+    /// Overwrite an existing config without asking.
+    #[arg(short, long)]
+    pub force: bool,
+}
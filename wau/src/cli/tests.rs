@@ -20,6 +20,35 @@ fn list_with_tag() {
     assert_eq!(args.tag.as_deref(), Some("retail-main"));
 }
 
+#[test]
+fn list_with_filters_and_sort() {
+    let cli = Cli::try_parse_from([
+        "wau",
+        "list",
+        "--provider",
+        "curseforge",
+        "--status",
+        "unmanaged",
+        "--flavor",
+        "classic-era",
+        "--sort",
+        "status",
+    ])
+    .unwrap();
+    let Command::List(args) = cli.command else {
+        panic!()
+    };
+    assert_eq!(args.provider, Some(libwau::model::Provider::CurseForge));
+    assert_eq!(args.status, Some(libwau::status::AddonStatus::Unmanaged));
+    assert_eq!(args.flavor, Some(libwau::model::Flavor::Era));
+    assert_eq!(args.sort, libwau::status::SortKey::Status);
+}
+
+#[test]
+fn list_rejects_unknown_status() {
+    assert!(Cli::try_parse_from(["wau", "list", "--status", "broken"]).is_err());
+}
+
 #[test]
 fn list_with_config_override() {
     let cli = Cli::try_parse_from(["wau", "--config", "/tmp/test.toml", "list"]).unwrap();
@@ -102,3 +131,166 @@ fn remove_with_tag() {
     assert_eq!(args.tag.as_deref(), Some("classic-era"));
     assert_eq!(args.addons, vec!["Questie"]);
 }
+
+#[test]
+fn outdated_defaults() {
+    let cli = Cli::try_parse_from(["wau", "outdated"]).unwrap();
+    let Command::Outdated(args) = cli.command else {
+        panic!()
+    };
+    assert!(args.tag.is_none());
+    assert!(args.manifest.is_none());
+}
+
+#[test]
+fn outdated_with_tag_and_manifest() {
+    let cli = Cli::try_parse_from([
+        "wau",
+        "outdated",
+        "-t",
+        "classic-era",
+        "-m",
+        "/tmp/manifest.toml",
+    ])
+    .unwrap();
+    let Command::Outdated(args) = cli.command else {
+        panic!()
+    };
+    assert_eq!(args.tag.as_deref(), Some("classic-era"));
+    assert_eq!(
+        args.manifest.as_deref(),
+        Some(std::path::Path::new("/tmp/manifest.toml"))
+    );
+}
+
+#[test]
+fn recover_requires_a_direction() {
+    assert!(Cli::try_parse_from(["wau", "recover"]).is_err());
+    assert!(Cli::try_parse_from(["wau", "recover", "--forward", "--back"]).is_err());
+}
+
+#[test]
+fn recover_back_with_tag() {
+    let cli = Cli::try_parse_from(["wau", "recover", "--back", "-t", "classic-era"]).unwrap();
+    let Command::Recover(args) = cli.command else {
+        panic!()
+    };
+    assert!(args.back);
+    assert!(!args.forward);
+    assert_eq!(args.tag.as_deref(), Some("classic-era"));
+}
+
+#[test]
+fn sync_retry_failed_and_fail_fast() {
+    let cli = Cli::try_parse_from(["wau", "sync"]).unwrap();
+    let Command::Sync(args) = cli.command else {
+        panic!()
+    };
+    assert!(!args.retry_failed);
+    assert!(!args.fail_fast);
+
+    let cli = Cli::try_parse_from(["wau", "sync", "--retry-failed", "--fail-fast"]).unwrap();
+    let Command::Sync(args) = cli.command else {
+        panic!()
+    };
+    assert!(args.retry_failed);
+    assert!(args.fail_fast);
+}
+
+#[test]
+fn sync_allow_shared() {
+    let cli = Cli::try_parse_from(["wau", "sync"]).unwrap();
+    let Command::Sync(args) = cli.command else {
+        panic!()
+    };
+    assert!(!args.allow_shared);
+
+    let cli = Cli::try_parse_from(["wau", "sync", "--allow-shared"]).unwrap();
+    let Command::Sync(args) = cli.command else {
+        panic!()
+    };
+    assert!(args.allow_shared);
+}
+
+#[test]
+fn sync_flavor_and_channel_overrides() {
+    let cli = Cli::try_parse_from([
+        "wau",
+        "sync",
+        "--flavor",
+        "classic-era",
+        "--channel",
+        "beta",
+    ])
+    .unwrap();
+    let Command::Sync(args) = cli.command else {
+        panic!()
+    };
+    assert_eq!(args.flavor, Some(Flavor::Era));
+    assert_eq!(args.channel, Some(Channel::Beta));
+    assert!(Cli::try_parse_from(["wau", "sync", "--channel", "nightly"]).is_err());
+}
+
+#[test]
+fn owns_takes_folder_path() {
+    let cli = Cli::try_parse_from(["wau", "owns", "Interface/AddOns/WeakAuras"]).unwrap();
+    let Command::Owns(args) = cli.command else {
+        panic!()
+    };
+    assert_eq!(args.folder, PathBuf::from("Interface/AddOns/WeakAuras"));
+    assert!(args.tag.is_none());
+}
+
+#[test]
+fn files_requires_addon() {
+    assert!(Cli::try_parse_from(["wau", "files"]).is_err());
+
+    let cli = Cli::try_parse_from(["wau", "files", "-t", "era", "Questie"]).unwrap();
+    let Command::Files(args) = cli.command else {
+        panic!()
+    };
+    assert_eq!(args.addon, "Questie");
+    assert_eq!(args.tag.as_deref(), Some("era"));
+}
+
+#[test]
+fn adopt_dry_run_flag() {
+    let cli = Cli::try_parse_from(["wau", "adopt", "-n", "--tag", "retail"]).unwrap();
+    let Command::Adopt(args) = cli.command else {
+        panic!()
+    };
+    assert!(args.dry_run);
+    assert_eq!(args.tag.as_deref(), Some("retail"));
+}
+
+#[test]
+fn init_roots_and_non_interactive() {
+    let cli = Cli::try_parse_from(["wau", "init"]).unwrap();
+    let Command::Init(args) = cli.command else {
+        panic!()
+    };
+    assert!(args.roots.is_empty());
+    assert!(!args.non_interactive);
+
+    let cli = Cli::try_parse_from([
+        "wau",
+        "init",
+        "--non-interactive",
+        "--root",
+        "/games/a",
+        "-r",
+        "/games/b",
+    ])
+    .unwrap();
+    let Command::Init(args) = cli.command else {
+        panic!()
+    };
+    assert_eq!(
+        args.roots,
+        vec![PathBuf::from("/games/a"), PathBuf::from("/games/b")]
+    );
+    assert!(args.non_interactive);
+
+    // Non-interactive runs only search the roots they are given.
+    assert!(Cli::try_parse_from(["wau", "init", "--non-interactive"]).is_err());
+}
@@ -2,13 +2,17 @@
 #![allow(dead_code)]
 
 use std::{
+    collections::HashMap,
     io,
     path::{Path, PathBuf},
 };
 
 use serde::{Deserialize, Serialize};
 
-use libwau::model::{Channel, Flavor, LogLevel, Tag};
+use libwau::{
+    client::Product,
+    model::{Channel, Flavor, LogLevel, Provider, Tag},
+};
 
 #[cfg(test)]
 mod tests;
@@ -22,6 +26,10 @@ pub struct Config {
     pub paths: Paths,
     #[serde(default)]
     pub providers: Providers,
+    #[serde(default)]
+    pub network: Network,
+    #[serde(default)]
+    pub limits: Limits,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +57,82 @@ pub struct Install {
     pub tag: Tag,
     pub flavor: Flavor,
     pub wow_root: PathBuf,
+    /// Battle.net product folder under `wow_root` (`_retail_`, `_classic_era_`, …);
+    /// inferred from `flavor` when omitted.
+    #[serde(default)]
+    pub product: Option<Product>,
+    /// Release channel for this install; `defaults.channel` when omitted.
+    #[serde(default)]
+    pub channel: Option<Channel>,
+}
+
+impl Install {
+    /// The directory holding this install's `Interface` and `WTF` folders.
+    pub fn client_dir(&self) -> PathBuf {
+        libwau::client::client_dir(&self.wow_root, self.product, &self.flavor)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Network {
+    /// Concurrent resolve + download jobs per provider during `wau sync`.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// Per-provider overrides of `concurrency`, e.g. `github = 2`.
+    #[serde(default)]
+    pub provider_concurrency: HashMap<Provider, usize>,
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Self {
+            concurrency: default_concurrency(),
+            provider_concurrency: HashMap::new(),
+        }
+    }
+}
+
+fn default_concurrency() -> usize {
+    libwau::ops::DEFAULT_CONCURRENCY
+}
+
+/// Size caps for downloads and extraction, in MiB; `0` disables a cap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Limits {
+    #[serde(default = "default_max_download_mib")]
+    pub max_download_mib: u64,
+    #[serde(default = "default_max_unpacked_mib")]
+    pub max_unpacked_mib: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_download_mib: default_max_download_mib(),
+            max_unpacked_mib: default_max_unpacked_mib(),
+        }
+    }
+}
+
+fn default_max_download_mib() -> u64 {
+    libwau::providers::Limits::DEFAULT_MAX_DOWNLOAD_BYTES / MIB
+}
+
+fn default_max_unpacked_mib() -> u64 {
+    libwau::providers::Limits::DEFAULT_MAX_UNPACKED_BYTES / MIB
+}
+
+const MIB: u64 = 1024 * 1024;
+
+impl Limits {
+    /// Converts to byte caps, mapping `0` to "no cap".
+    pub fn to_bytes(&self) -> libwau::providers::Limits {
+        let cap = |mib: u64| (mib > 0).then(|| mib.saturating_mul(MIB));
+        libwau::providers::Limits {
+            max_download_bytes: cap(self.max_download_mib),
+            max_unpacked_bytes: cap(self.max_unpacked_mib),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -122,13 +206,38 @@ pub fn parse(s: &str) -> Result<Config, ConfigError> {
 }
 
 impl Config {
+    /// Returns the `[[paths.installs]]` entry for `tag`, if configured.
+    pub fn install(&self, tag: &Tag) -> Option<&Install> {
+        self.paths.installs.iter().find(|i| &i.tag == tag)
+    }
+
+    /// The flavor of install `tag`, falling back to `defaults.flavor` for unknown tags.
+    pub fn flavor_for(&self, tag: &Tag) -> Flavor {
+        self.install(tag)
+            .map_or_else(|| self.defaults.flavor.clone(), |i| i.flavor.clone())
+    }
+
+    /// The channel of install `tag`, falling back to `defaults.channel`.
+    pub fn channel_for(&self, tag: &Tag) -> Channel {
+        self.install(tag)
+            .and_then(|i| i.channel.clone())
+            .unwrap_or_else(|| self.defaults.channel.clone())
+    }
+
     /// Returns the `Interface/AddOns` path for `tag`, if that tag is configured.
+    ///
+    /// Matches an existing `interface/addons` (or any other casing) so wau never
+    /// creates a second tree beside the one a Wine client reads.
     pub fn addons_path(&self, tag: &Tag) -> Option<PathBuf> {
-        self.paths
-            .installs
-            .iter()
-            .find(|i| &i.tag == tag)
-            .map(|i| i.wow_root.join("Interface").join("AddOns"))
+        self.install(tag)
+            .map(|i| libwau::fs::join_ignore_case(&i.client_dir(), &["Interface", "AddOns"]))
+    }
+
+    /// Returns the `WTF` path for `tag`, matched case-insensitively like
+    /// [`Config::addons_path`].
+    pub fn wtf_path(&self, tag: &Tag) -> Option<PathBuf> {
+        self.install(tag)
+            .map(|i| libwau::fs::join_ignore_case(&i.client_dir(), &["WTF"]))
     }
 
     fn expand_paths(&mut self) {
@@ -139,6 +248,71 @@ impl Config {
     }
 }
 
+/// An install `wau init` found, with a note on how it was detected.
+#[derive(Debug, Clone)]
+pub struct FoundInstall {
+    pub install: Install,
+    /// One-line comment written above the entry.
+    pub note: String,
+}
+
+/// Renders the commented `config.toml` `wau init` writes: one
+/// `[[paths.installs]]` entry per install, the first of them as the default.
+pub fn render(installs: &[FoundInstall]) -> String {
+    let quote = |s: &str| toml::Value::String(s.to_owned()).to_string();
+    let (flavor, tag) = installs.first().map_or((Flavor::Retail, "retail"), |f| {
+        (f.install.flavor.clone(), f.install.tag.as_str())
+    });
+
+    let mut out = format!(
+        r#"# wau config, written by `wau init`. See examples/config.toml for every option.
+
+# Config format version for forward-compatible parsing.
+schema = 1
+
+# Install used when a command omits `--tag`.
+[defaults]
+flavor = {flavor}
+channel = "stable"
+install_tag = {tag}
+
+[logging]
+level = "info"
+
+# Downloads, staging and provider metadata cache.
+[paths]
+cache = "~/.cache/wau"
+
+# One entry per client; an entry's `flavor` (and optional `channel`) wins over
+# `[defaults]` whenever its tag is used.
+"#,
+        flavor = quote(flavor.as_str()),
+        tag = quote(tag),
+    );
+    for found in installs {
+        let install = &found.install;
+        out.push_str(&format!(
+            "[[paths.installs]]\n# {}\ntag = {}\nflavor = {}\nwow_root = {}\n",
+            found.note,
+            quote(install.tag.as_str()),
+            quote(install.flavor.as_str()),
+            quote(&install.wow_root.to_string_lossy()),
+        ));
+        if let Some(product) = install.product {
+            out.push_str(&format!("product = {}\n", quote(product.as_str())));
+        }
+        out.push('\n');
+    }
+    out.push_str(
+        r#"# CurseForge addons need an API key from https://console.curseforge.com ("API Keys").
+# Keep this file out of version control once the key is in it.
+# [providers.curseforge]
+# api_key = "your-curseforge-api-key"
+"#,
+    );
+    out
+}
+
 fn expand_tilde(path: PathBuf) -> PathBuf {
     let s = path.to_string_lossy().into_owned();
     if s == "~" {
@@ -23,6 +23,8 @@ wow_root = "/games/World of Warcraft"
 tag = "classic-official"
 flavor = "classic-era"
 wow_root = "/games/World of Warcraft"
+product = "classic-era"
+channel = "beta"
 
 [[paths.installs]]
 tag = "classic-turtle"
@@ -31,6 +33,16 @@ wow_root = "/games/TurtleWoW/World of Warcraft"
 
 [providers.curseforge]
 api_key = "test-token"
+
+[network]
+concurrency = 6
+
+[network.provider_concurrency]
+github = 2
+
+[limits]
+max_download_mib = 100
+max_unpacked_mib = 0
 "#;
 
 const MINIMAL_CONFIG: &str = r#"
@@ -45,6 +57,36 @@ install_tag = "retail-main"
 cache = "/tmp/wau-test-cache"
 "#;
 
+#[test]
+fn network_section_parses_overrides() {
+    let cfg = parse(FULL_CONFIG).unwrap();
+    assert_eq!(cfg.network.concurrency, 6);
+    assert_eq!(
+        cfg.network.provider_concurrency.get(&Provider::GitHub),
+        Some(&2)
+    );
+}
+
+#[test]
+fn network_section_defaults_when_absent() {
+    let cfg = parse(MINIMAL_CONFIG).unwrap();
+    assert_eq!(cfg.network.concurrency, 4);
+    assert!(cfg.network.provider_concurrency.is_empty());
+}
+
+#[test]
+fn limits_section_converts_mib_and_zero_disables() {
+    let limits = parse(FULL_CONFIG).unwrap().limits.to_bytes();
+    assert_eq!(limits.max_download_bytes, Some(100 * 1024 * 1024));
+    assert_eq!(limits.max_unpacked_bytes, None);
+}
+
+#[test]
+fn limits_section_defaults_when_absent() {
+    let cfg = parse(MINIMAL_CONFIG).unwrap();
+    assert_eq!(cfg.limits.to_bytes(), libwau::providers::Limits::default());
+}
+
 #[test]
 fn parse_full_config() {
     let cfg = parse(FULL_CONFIG).unwrap();
@@ -90,6 +132,56 @@ fn addons_path_returns_correct_subpath() {
     assert!(path.ends_with("Interface/AddOns"));
 }
 
+#[test]
+fn addons_path_joins_the_configured_product_folder() {
+    let cfg = parse(FULL_CONFIG).unwrap();
+    assert_eq!(cfg.paths.installs[1].product, Some(Product::ClassicEra));
+    assert_eq!(
+        cfg.addons_path(&Tag::new("classic-official")).unwrap(),
+        Path::new("/games/World of Warcraft/_classic_era_/Interface/AddOns")
+    );
+    // No product and no product folder on disk: the flat layout.
+    assert_eq!(
+        cfg.addons_path(&Tag::new("classic-turtle")).unwrap(),
+        Path::new("/games/TurtleWoW/World of Warcraft/Interface/AddOns")
+    );
+}
+
+#[test]
+fn install_flavor_and_channel_override_defaults() {
+    let cfg = parse(FULL_CONFIG).unwrap();
+    let classic = Tag::new("classic-official");
+    assert_eq!(cfg.flavor_for(&classic), Flavor::Era);
+    assert_eq!(cfg.channel_for(&classic), Channel::Beta);
+    // No per-install channel: the default one.
+    assert_eq!(
+        cfg.channel_for(&Tag::new("classic-turtle")),
+        Channel::Stable
+    );
+    // Unknown tags fall back to `[defaults]`.
+    assert_eq!(cfg.flavor_for(&Tag::new("nope")), Flavor::Retail);
+}
+
+#[test]
+fn addons_and_wtf_paths_reuse_lowercase_folders_on_disk() {
+    let root = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(root.path().join("interface/addons")).unwrap();
+    std::fs::create_dir_all(root.path().join("wtf")).unwrap();
+    let cfg = parse(&format!(
+        "{MINIMAL_CONFIG}\n[[paths.installs]]\ntag = \"wine\"\nflavor = \"classic-era\"\nwow_root = {}\n",
+        toml::Value::String(root.path().to_string_lossy().into_owned())
+    ))
+    .unwrap();
+
+    let tag = Tag::new("wine");
+    assert_eq!(
+        cfg.addons_path(&tag).unwrap(),
+        root.path().join("interface/addons")
+    );
+    assert_eq!(cfg.wtf_path(&tag).unwrap(), root.path().join("wtf"));
+    assert!(cfg.wtf_path(&Tag::new("nonexistent")).is_none());
+}
+
 #[test]
 fn addons_path_returns_none_for_unknown_tag() {
     let cfg = parse(FULL_CONFIG).unwrap();
@@ -202,3 +294,43 @@ fn example_config_curseforge_provider_present() {
         "expected [providers.curseforge] in examples/config.toml"
     );
 }
+
+#[test]
+fn render_writes_a_config_that_parses_back() {
+    let found = |tag: &str, flavor: Flavor, root: &str, product: Option<Product>| FoundInstall {
+        install: Install {
+            tag: Tag::new(tag),
+            flavor,
+            wow_root: PathBuf::from(root),
+            product,
+            channel: None,
+        },
+        note: format!("Detected: {tag}"),
+    };
+    let text = render(&[
+        found(
+            "classic-era",
+            Flavor::Era,
+            "/p/drive_c/Program Files (x86)/World of Warcraft",
+            Some(Product::ClassicEra),
+        ),
+        found("retail", Flavor::Retail, "/games/\"odd\" wow", None),
+    ]);
+    assert!(text.contains("# Detected: classic-era\n"));
+
+    let cfg = parse(&text).unwrap();
+    assert_eq!(cfg.defaults.install_tag, Tag::new("classic-era"));
+    assert_eq!(cfg.defaults.flavor, Flavor::Era);
+    assert_eq!(cfg.paths.installs.len(), 2);
+    assert_eq!(
+        cfg.addons_path(&Tag::new("classic-era")).unwrap(),
+        Path::new(
+            "/p/drive_c/Program Files (x86)/World of Warcraft/_classic_era_/Interface/AddOns"
+        )
+    );
+    assert_eq!(
+        cfg.paths.installs[1].wow_root,
+        Path::new("/games/\"odd\" wow")
+    );
+    assert!(cfg.providers.curseforge.is_none());
+}
@@ -12,8 +12,12 @@ async fn main() {
 
     let cli = cli::Cli::parse();
 
-    if let Err(e) = app::run(&cli).await {
-        eprintln!("error: {e}");
-        std::process::exit(1);
+    match app::run(&cli).await {
+        Ok(app::EXIT_OK) => {}
+        Ok(code) => std::process::exit(code),
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
     }
 }
@@ -3,33 +3,189 @@
 //! All output goes through this module so that formatting decisions are
 //! centralised rather than scattered across `app`.
 
-use libwau::fs::InstalledAddon;
+use std::{
+    io::{self, IsTerminal, Write},
+    path::Path,
+};
+
+use libwau::{
+    adopt::{AdoptedAddon, Adoption, SkippedGroup},
+    client::Client,
+    events::{Event, EventSink},
+    fs::{AddonFile, Compatibility, InstalledAddon},
+    journal::{Journal, JournalEntry, Recovery},
+    lock::{FolderClaim, LockedAddon},
+    model::{Flavor, Tag},
+    ops::{InstallOutcome, UpdateStatus},
+    status::{AddonReport, AddonStatus},
+};
+
+use crate::config::FoundInstall;
 
 #[cfg(test)]
 mod tests;
 
 const COL_NAME: usize = 42;
+const COL_VERSION: usize = 24;
+const COL_PROVIDER: usize = 13;
+const COL_STATUS: usize = 9;
+const COL_SOURCE: usize = 20;
 
-/// Prints the installed-addon list to stdout.
-pub fn print_addon_list(addons: &[InstalledAddon]) {
-    if addons.is_empty() {
+/// Prints the `wau list` table, then a count per status.
+pub fn print_addon_report(reports: &[AddonReport]) {
+    if reports.is_empty() {
         println!("No addons found.");
         return;
     }
 
-    println!("{:<col$}  Version", "Name", col = COL_NAME);
-    println!("{}", "-".repeat(COL_NAME + 12));
+    println!(
+        "{:<name$}  {:<status$}  {:<source$}  {:<ver$}  TOC version",
+        "Name",
+        "Status",
+        "Source",
+        "Locked",
+        name = COL_NAME,
+        status = COL_STATUS,
+        source = COL_SOURCE,
+        ver = COL_VERSION,
+    );
+    println!(
+        "{}",
+        "-".repeat(COL_NAME + COL_STATUS + COL_SOURCE + COL_VERSION + 19)
+    );
+    for report in reports {
+        println!("{}", format_report_row(report));
+    }
 
-    for addon in addons {
-        let title = addon.display_title();
-        let version = addon.version().unwrap_or("-");
-        println!("{:<col$}  {}", title, version, col = COL_NAME);
+    let count = |status: AddonStatus| reports.iter().filter(|r| r.status == status).count();
+    println!(
+        "{} managed, {} missing, {} unmanaged",
+        count(AddonStatus::Managed),
+        count(AddonStatus::Missing),
+        count(AddonStatus::Unmanaged)
+    );
+}
+
+/// Formats one `wau list` row. The source is `provider/channel`; missing rows
+/// note which folders are absent, or that the addon was never installed, and
+/// addons the client would not load are tagged `[out of date]` / `[incompatible]`.
+pub fn format_report_row(report: &AddonReport) -> String {
+    let source = match (&report.provider, &report.channel) {
+        (Some(p), Some(c)) => format!("{p}/{c}"),
+        (Some(p), None) => p.to_string(),
+        _ => "-".to_owned(),
+    };
+    let mut row = format!(
+        "{:<name$}  {:<status$}  {:<source$}  {:<ver$}  {}",
+        report.name,
+        report.status.as_str(),
+        source,
+        report.locked_version.as_deref().unwrap_or("-"),
+        report.toc_version.as_deref().unwrap_or("-"),
+        name = COL_NAME,
+        status = COL_STATUS,
+        source = COL_SOURCE,
+        ver = COL_VERSION,
+    );
+    if report.status == AddonStatus::Missing {
+        if report.locked_version.is_none() {
+            row.push_str("  (not installed)");
+        } else if !report.missing_folders.is_empty() {
+            row.push_str(&format!(
+                "  (missing: {})",
+                report.missing_folders.join(", ")
+            ));
+        }
+    } else if report.status == AddonStatus::Unmanaged && report.folders.len() > 1 {
+        row.push_str(&format!("  (+{} folders)", report.folders.len() - 1));
+    }
+    if let Some(c @ (Compatibility::OutOfDate | Compatibility::Incompatible)) = report.compatibility
+    {
+        row.push_str(&format!("  [{}]", c.as_str()));
     }
+    row
 }
 
-/// Prints a confirmation that an addon was installed.
-pub fn print_installed(name: &str) {
-    println!("installed  {name}");
+/// Prints the `wau outdated` table: only rows with an update, then a summary line.
+pub fn print_outdated(statuses: &[UpdateStatus]) {
+    let outdated: Vec<&UpdateStatus> = statuses.iter().filter(|s| s.outdated).collect();
+    if outdated.is_empty() {
+        println!("All {} addons are up to date.", statuses.len());
+        return;
+    }
+
+    println!(
+        "{:<name$}  {:<ver$}  {:<ver$}  {:<prov$}  Channel",
+        "Name",
+        "Installed",
+        "Available",
+        "Provider",
+        name = COL_NAME,
+        ver = COL_VERSION,
+        prov = COL_PROVIDER,
+    );
+    println!(
+        "{}",
+        "-".repeat(COL_NAME + 2 * COL_VERSION + COL_PROVIDER + 15)
+    );
+    for status in &outdated {
+        println!("{}", format_outdated_row(status));
+    }
+    println!(
+        "{} of {} addons can be updated",
+        outdated.len(),
+        statuses.len()
+    );
+}
+
+/// Formats one `wau outdated` row; a never-installed addon shows `-` as installed.
+pub fn format_outdated_row(status: &UpdateStatus) -> String {
+    format!(
+        "{:<name$}  {:<ver$}  {:<ver$}  {:<prov$}  {}",
+        status.name,
+        status.installed.as_deref().unwrap_or("-"),
+        status.available,
+        status.provider,
+        status.channel,
+        name = COL_NAME,
+        ver = COL_VERSION,
+        prov = COL_PROVIDER,
+    )
+}
+
+/// Prints what a sync did with one addon.
+pub fn print_outcome(name: &str, outcome: &InstallOutcome) {
+    println!("{}", format_outcome(name, outcome));
+}
+
+/// Formats one sync outcome line: `installed`, `updated X -> Y`, or `up to date`,
+/// followed by one line per `.pkgmeta` external the install is missing.
+pub fn format_outcome(name: &str, outcome: &InstallOutcome) -> String {
+    let (mut line, skipped) = match outcome {
+        InstallOutcome::Installed {
+            version,
+            skipped_externals,
+        } => (
+            format!("installed  {name} ({version})"),
+            skipped_externals.as_slice(),
+        ),
+        InstallOutcome::Updated {
+            from,
+            to,
+            skipped_externals,
+        } => (
+            format!("updated    {name} ({from} -> {to})"),
+            skipped_externals.as_slice(),
+        ),
+        InstallOutcome::UpToDate { version } => (format!("up to date {name} ({version})"), &[][..]),
+    };
+    for external in skipped {
+        line.push_str(&format!(
+            "\n  missing external {}: {} is not on GitHub; install that library separately",
+            external.path, external.url
+        ));
+    }
+    line
 }
 
 /// Prints a confirmation that an addon was removed.
@@ -37,9 +193,349 @@ pub fn print_removed(name: &str) {
     println!("removed    {name}");
 }
 
+/// Prints the progress line for an addon whose sync failed; the cause comes later
+/// in [`print_failures`].
+pub fn print_failed(name: &str) {
+    println!("failed     {name}");
+}
+
 /// Prints the sync operation summary.
-pub fn print_sync_summary(installed: u32, skipped: u32) {
-    println!("sync done: {installed} installed, {skipped} skipped");
+pub fn print_sync_summary(
+    installed: u32,
+    updated: u32,
+    up_to_date: u32,
+    skipped: u32,
+    failed: u32,
+) {
+    println!(
+        "sync done: {installed} installed, {updated} updated, {up_to_date} up to date, {skipped} skipped, {failed} failed"
+    );
+}
+
+/// Prints the table of addons that failed during a sync or update check, with
+/// each error's cause.
+pub fn print_failures(failures: &[(String, libwau::Error)]) {
+    eprintln!();
+    eprintln!("{:<col$}  Error", "Failed addon", col = COL_NAME);
+    eprintln!("{}", "-".repeat(COL_NAME + 40));
+    for (name, error) in failures {
+        eprintln!("{}", format_failure_row(name, error));
+    }
+}
+
+/// Formats one failure row: addon name, then the `libwau::Error` message.
+pub fn format_failure_row(name: &str, error: &libwau::Error) -> String {
+    format!("{:<col$}  {}", name, error, col = COL_NAME)
+}
+
+/// Prints which folders of `name` other locked addons already own.
+pub fn print_folder_conflict(name: &str, claims: &[FolderClaim]) {
+    eprintln!("{name} ships folders other addons already own:");
+    for claim in claims {
+        eprintln!("  {claim}");
+    }
+}
+
+/// Prints how to install addons refused for folder conflicts.
+pub fn print_allow_shared_hint() {
+    eprintln!("rerun with --allow-shared to install them anyway, sharing the conflicting folders");
+}
+
+/// Prints that `--retry-failed` found nothing recorded from the previous sync.
+pub fn print_nothing_to_retry() {
+    println!("No failed addons recorded for the previous sync.");
+}
+
+/// Prints what an interrupted run left behind: the command and each completed change.
+pub fn print_interrupted_run(journal: &Journal) {
+    eprintln!(
+        "interrupted `wau {}` on '{}' (started {}, pid {}) completed {} change(s):",
+        journal.command,
+        journal.install_tag,
+        journal.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        journal.pid,
+        journal.entry.len()
+    );
+    for entry in &journal.entry {
+        eprintln!("  {}", format_journal_entry(entry));
+    }
+}
+
+/// Formats one journalled change, e.g. `updated    Bagnon (1.0 -> 1.1)`.
+pub fn format_journal_entry(entry: &JournalEntry) -> String {
+    let name = &entry.name;
+    match (&entry.previous, &entry.current) {
+        (None, Some(cur)) => format!("installed  {name} ({})", cur.resolved_version),
+        (Some(prev), Some(cur)) => format!(
+            "updated    {name} ({} -> {})",
+            prev.resolved_version, cur.resolved_version
+        ),
+        (Some(prev), None) => format!("removed    {name} ({})", prev.resolved_version),
+        (None, None) => format!("unknown    {name}"),
+    }
+}
+
+/// Tells the user how to resolve an interrupted run without a prompt.
+pub fn print_recover_hint(tag: &Tag) {
+    eprintln!(
+        "run `wau recover --tag {tag} --forward` to keep these changes or `--back` to undo them"
+    );
+}
+
+/// Prints one change undone while rolling back.
+pub fn print_reverted(entry: &JournalEntry) {
+    println!("reverted   {}", entry.name);
+}
+
+/// Prints the outcome of resolving an interrupted run.
+pub fn print_recovered(recovery: Recovery, changes: usize) {
+    let verb = match recovery {
+        Recovery::RollForward => "rolled forward",
+        Recovery::RollBack => "rolled back",
+    };
+    println!("recovery done: {verb} {changes} change(s)");
+}
+
+/// Prints that `wau recover` found no interrupted run.
+pub fn print_nothing_to_recover(tag: &Tag) {
+    println!("No interrupted run for '{tag}'.");
+}
+
+/// Renders `libwau` events as one status line on stderr, rewritten in place.
+///
+/// Does nothing when stderr is not a terminal, so piped output stays clean.
+/// Call [`ProgressLine::clear`] before printing a regular line.
+pub struct ProgressLine {
+    enabled: bool,
+}
+
+impl ProgressLine {
+    pub fn new() -> Self {
+        Self {
+            enabled: io::stderr().is_terminal(),
+        }
+    }
+
+    /// Erases the status line.
+    pub fn clear(&self) {
+        if self.enabled {
+            eprint!("\r\x1b[2K");
+            let _ = io::stderr().flush();
+        }
+    }
+}
+
+impl EventSink for ProgressLine {
+    fn emit(&self, event: Event) {
+        tracing::trace!(%event, "progress");
+        if self.enabled {
+            eprint!("\r\x1b[2K{}", format_progress(&event));
+            let _ = io::stderr().flush();
+        }
+    }
+}
+
+/// Formats one event for the status line; downloads get a bar when the size is known.
+pub fn format_progress(event: &Event) -> String {
+    const BAR: usize = 20;
+    match event {
+        Event::DownloadProgress {
+            addon,
+            bytes,
+            total: Some(total),
+        } if *total > 0 => {
+            let filled = (*bytes * BAR as u64 / total).min(BAR as u64) as usize;
+            format!(
+                "{addon} [{}{}] {:>3}% {} / {}",
+                "#".repeat(filled),
+                "-".repeat(BAR - filled),
+                bytes * 100 / total,
+                format_bytes(*bytes),
+                format_bytes(*total)
+            )
+        }
+        Event::DownloadProgress { addon, bytes, .. } => {
+            format!("{addon} {}", format_bytes(*bytes))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Prints every lock entry that owns `folder`, one per line.
+pub fn print_owners(folder: &str, owners: &[&LockedAddon], present: bool) {
+    for owner in owners {
+        println!("{}", format_owner(folder, owner));
+    }
+    if !present {
+        println!("{folder} is missing from disk; `wau sync` reinstalls it");
+    }
+}
+
+/// Formats one owner, e.g. `WeakAuras is owned by WeakAuras 5.12.0 (curseforge)`.
+pub fn format_owner(folder: &str, owner: &LockedAddon) -> String {
+    format!(
+        "{folder} is owned by {} {} ({})",
+        owner.name, owner.resolved_version, owner.provider
+    )
+}
+
+/// Prints that no lock entry owns `folder`, with the title and version from the
+/// `.toc` a `flavor` client loads, if it has one.
+pub fn print_unmanaged(folder: &str, addon: Option<&InstalledAddon>, flavor: &Flavor) {
+    println!("{}", format_unmanaged(folder, addon, flavor));
+}
+
+/// Formats the `wau owns` line for a folder no lock entry owns.
+pub fn format_unmanaged(folder: &str, addon: Option<&InstalledAddon>, flavor: &Flavor) -> String {
+    match addon {
+        Some(a) => format!(
+            "{folder} is not owned by any locked addon (unmanaged: {} {})",
+            a.display_title_for(flavor),
+            a.version_for(flavor).unwrap_or("-")
+        ),
+        None => format!("{folder} is not owned by any locked addon (unmanaged)"),
+    }
+}
+
+/// Prints each folder of addon `name` and every file under it with its size, like
+/// `pacman -Ql`. A `None` file list marks a folder missing from disk.
+pub fn print_files(name: &str, folders: &[(&str, Option<Vec<AddonFile>>)]) {
+    let (mut count, mut total) = (0usize, 0u64);
+    for (folder, files) in folders {
+        match files {
+            Some(files) => {
+                println!("{name} {folder}/");
+                for file in files {
+                    println!("{}", format_file_row(name, file));
+                    count += 1;
+                    total += file.size;
+                }
+            }
+            None => println!("{name} {folder}/ (missing)"),
+        }
+    }
+    println!(
+        "{count} files, {} in {} folder(s)",
+        format_bytes(total),
+        folders.len()
+    );
+}
+
+/// Formats one file row, e.g. `WeakAuras WeakAuras/WeakAuras.toc (1.2 KiB)`.
+pub fn format_file_row(name: &str, file: &AddonFile) -> String {
+    format!(
+        "{name} {} ({})",
+        file.path.display(),
+        format_bytes(file.size)
+    )
+}
+
+/// Prints the addons `wau adopt` found (or would adopt, for a dry run), then the
+/// folders it left unmanaged and why.
+pub fn print_adoption(adoption: &Adoption, dry_run: bool) {
+    if adoption.addons.is_empty() && adoption.skipped.is_empty() {
+        println!("No unmanaged addons to adopt.");
+        return;
+    }
+    for addon in &adoption.addons {
+        println!("{}", format_adopted(addon));
+    }
+    for skipped in &adoption.skipped {
+        println!("{}", format_skipped(skipped));
+    }
+    println!(
+        "{} {} addon(s), {} left unmanaged",
+        if dry_run { "Would adopt" } else { "Adopted" },
+        adoption.addons.len(),
+        adoption.skipped.len()
+    );
+}
+
+/// Formats one adopted addon, e.g.
+/// `adopt  Bagnon 10.2.5 (curseforge 1592): Bagnon, Bagnon_Config`.
+pub fn format_adopted(addon: &AdoptedAddon) -> String {
+    let row = &addon.row;
+    let id = row
+        .project_id
+        .or(row.wowi_id)
+        .map_or_else(String::new, |id| format!(" {id}"));
+    format!(
+        "adopt  {} {} ({}{id}){}: {}",
+        row.name,
+        addon.version.as_deref().unwrap_or("-"),
+        row.provider,
+        if addon.new_row { "" } else { " [in manifest]" },
+        addon.folders.join(", ")
+    )
+}
+
+/// Formats one group `wau adopt` leaves unmanaged, e.g.
+/// `skip   MyHack (MyHack, MyHack_Options): no provider ID in its .toc`.
+pub fn format_skipped(skipped: &SkippedGroup) -> String {
+    let folders = if skipped.folders.len() > 1 {
+        format!(" ({})", skipped.folders.join(", "))
+    } else {
+        String::new()
+    };
+    format!("skip   {}{folders}: {}", skipped.name, skipped.reason)
+}
+
+/// Prints one client `wau init` found and the install it would write for it;
+/// `None` when its flavor is not known yet.
+pub fn print_found_install(found: Option<&FoundInstall>, client: &Client) {
+    println!("{}", format_found_install(found, client));
+}
+
+/// Formats one client `wau init` found, e.g.
+/// `found  retail (retail 11.2.5.64270): /games/World of Warcraft/_retail_`.
+pub fn format_found_install(found: Option<&FoundInstall>, client: &Client) -> String {
+    let detected = match (found, client.flavor()) {
+        (_, Some(_)) => client.to_string(),
+        (Some(f), None) => format!("{}, guessed from installed addons", f.install.flavor),
+        (None, None) => "flavor not detected".to_owned(),
+    };
+    format!(
+        "found  {} ({detected}): {}",
+        found.map_or("?", |f| f.install.tag.as_str()),
+        client.dir.display()
+    )
+}
+
+/// Prints that `wau init --non-interactive` left out a client of unknown flavor.
+pub fn print_undetected_client(client: &Client) {
+    println!("{}", format_undetected_client(client));
+}
+
+/// Formats the line for a client left out because its flavor is unknown.
+pub fn format_undetected_client(client: &Client) -> String {
+    format!(
+        "skip   {}: flavor not detected; run `wau init` in a terminal to pick it, or add the install to the config by hand",
+        client.dir.display()
+    )
+}
+
+/// Prints where `wau init` wrote its files.
+pub fn print_init_done(config_path: &Path, installs: usize, manifest_path: Option<&Path>) {
+    println!("Wrote {} with {installs} install(s)", config_path.display());
+    if let Some(path) = manifest_path {
+        println!("Wrote empty manifest {}", path.display());
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 MiB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
 }
 
 // ---------------------------------------------------------------------------
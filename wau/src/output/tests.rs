@@ -39,3 +39,333 @@ fn format_row_no_title_uses_folder() {
     let row = format_addon_row(&addon);
     assert!(row.starts_with("FolderName"));
 }
+
+#[test]
+fn format_outcome_updated_shows_both_versions() {
+    let outcome = InstallOutcome::Updated {
+        from: "1.0".into(),
+        to: "1.1".into(),
+        skipped_externals: Vec::new(),
+    };
+    assert_eq!(
+        format_outcome("Bagnon", &outcome),
+        "updated    Bagnon (1.0 -> 1.1)"
+    );
+}
+
+#[test]
+fn format_outcome_lists_skipped_externals() {
+    let outcome = InstallOutcome::Installed {
+        version: "abc1234".into(),
+        skipped_externals: vec![libwau::ops::SkippedExternal {
+            path: "Libs/LibStub".into(),
+            url: "https://repos.wowace.com/wow/libstub/trunk".into(),
+        }],
+    };
+    assert_eq!(
+        format_outcome("MyAddon", &outcome),
+        "installed  MyAddon (abc1234)\n  missing external Libs/LibStub: \
+         https://repos.wowace.com/wow/libstub/trunk is not on GitHub; install that library separately"
+    );
+}
+
+#[test]
+fn format_outcome_up_to_date() {
+    let outcome = InstallOutcome::UpToDate {
+        version: "1.0".into(),
+    };
+    assert!(format_outcome("Bagnon", &outcome).starts_with("up to date Bagnon"));
+}
+
+fn make_status(installed: Option<&str>, available: &str) -> UpdateStatus {
+    UpdateStatus {
+        name: "Bagnon".into(),
+        provider: libwau::model::Provider::CurseForge,
+        channel: libwau::model::Channel::Stable,
+        installed: installed.map(str::to_owned),
+        available: available.into(),
+        outdated: installed != Some(available),
+    }
+}
+
+#[test]
+fn format_outdated_row_shows_versions_provider_and_channel() {
+    let row = format_outdated_row(&make_status(Some("1.0"), "1.1"));
+    assert!(row.starts_with("Bagnon"));
+    assert!(row.contains("1.0"));
+    assert!(row.contains("1.1"));
+    assert!(row.contains("curseforge"));
+    assert!(row.ends_with("stable"));
+}
+
+#[test]
+fn format_outdated_row_not_installed_shows_dash() {
+    let row = format_outdated_row(&make_status(None, "1.1"));
+    let installed = row[COL_NAME + 2..COL_NAME + 2 + COL_VERSION].trim();
+    assert_eq!(installed, "-");
+}
+
+fn make_locked(version: &str) -> libwau::lock::LockedAddon {
+    libwau::lock::LockedAddon {
+        name: "Bagnon".into(),
+        provider: libwau::model::Provider::CurseForge,
+        flavor: libwau::model::Flavor::Retail,
+        channel: libwau::model::Channel::Stable,
+        project_id: Some(1),
+        resolved_version: version.into(),
+        resolved_id: format!("cf-{version}"),
+        download_url: String::new(),
+        sha256: None,
+        installed_dirs: vec!["Bagnon".into()],
+        installed_at: "2026-04-22T00:00:00Z".parse().unwrap(),
+    }
+}
+
+#[test]
+fn format_journal_entry_names_each_action() {
+    let flavor = libwau::model::Flavor::Retail;
+    let installed = JournalEntry::new("Bagnon", &flavor, None, Some(make_locked("1.0")));
+    let updated = JournalEntry::new(
+        "Bagnon",
+        &flavor,
+        Some(make_locked("1.0")),
+        Some(make_locked("1.1")),
+    );
+    let removed = JournalEntry::new("Bagnon", &flavor, Some(make_locked("1.1")), None);
+
+    assert_eq!(format_journal_entry(&installed), "installed  Bagnon (1.0)");
+    assert_eq!(
+        format_journal_entry(&updated),
+        "updated    Bagnon (1.0 -> 1.1)"
+    );
+    assert_eq!(format_journal_entry(&removed), "removed    Bagnon (1.1)");
+}
+
+#[test]
+fn format_failure_row_shows_error_cause() {
+    let error = libwau::Error::MissingRepo {
+        name: "SomeAddon".into(),
+    };
+    let row = format_failure_row("SomeAddon", &error);
+    assert!(row.starts_with("SomeAddon"));
+    assert!(row.ends_with(&error.to_string()));
+}
+
+#[test]
+fn format_progress_draws_bar_for_known_size() {
+    let event = Event::DownloadProgress {
+        addon: "Bagnon".into(),
+        bytes: 512 * 1024,
+        total: Some(1024 * 1024),
+    };
+    assert_eq!(
+        format_progress(&event),
+        "Bagnon [##########----------]  50% 512.0 KiB / 1.0 MiB"
+    );
+}
+
+#[test]
+fn format_progress_unknown_size_shows_bytes_only() {
+    let event = Event::DownloadProgress {
+        addon: "Bagnon".into(),
+        bytes: 100,
+        total: None,
+    };
+    assert_eq!(format_progress(&event), "Bagnon 100 B");
+}
+
+#[test]
+fn format_owner_names_version_and_provider() {
+    assert_eq!(
+        format_owner("Bagnon", &make_locked("10.2.5")),
+        "Bagnon is owned by Bagnon 10.2.5 (curseforge)"
+    );
+}
+
+#[test]
+fn format_unmanaged_shows_toc_details_when_present() {
+    let addon = make_addon("MyHack", Some("My Hack"), Some("1.0"));
+    assert_eq!(
+        format_unmanaged("MyHack", Some(&addon), &Flavor::Retail),
+        "MyHack is not owned by any locked addon (unmanaged: My Hack 1.0)"
+    );
+    assert_eq!(
+        format_unmanaged("Empty", None, &Flavor::Retail),
+        "Empty is not owned by any locked addon (unmanaged)"
+    );
+}
+
+#[test]
+fn format_unmanaged_reads_the_toc_the_flavor_loads() {
+    let mut addon = make_addon("MyHack", Some("My Hack"), Some("1.0"));
+    addon.toc_files.push(TocFile {
+        path: PathBuf::from("MyHack/MyHack_Vanilla.toc"),
+        title: Some("My Hack Classic".to_owned()),
+        version: Some("1.0-era".to_owned()),
+        interface: vec![11507],
+        ..Default::default()
+    });
+    assert_eq!(
+        format_unmanaged("MyHack", Some(&addon), &Flavor::Era),
+        "MyHack is not owned by any locked addon (unmanaged: My Hack Classic 1.0-era)"
+    );
+    assert_eq!(
+        format_unmanaged("MyHack", Some(&addon), &Flavor::Retail),
+        "MyHack is not owned by any locked addon (unmanaged: My Hack 1.0)"
+    );
+}
+
+#[test]
+fn format_file_row_shows_path_and_size() {
+    let file = libwau::fs::AddonFile {
+        path: PathBuf::from("Bagnon/Bagnon.toc"),
+        size: 2048,
+    };
+    assert_eq!(
+        format_file_row("Bagnon", &file),
+        "Bagnon Bagnon/Bagnon.toc (2.0 KiB)"
+    );
+}
+
+fn make_report(status: AddonStatus, locked: Option<&str>, missing: &[&str]) -> AddonReport {
+    AddonReport {
+        name: "Bagnon".into(),
+        status,
+        provider: Some(libwau::model::Provider::CurseForge),
+        channel: Some(libwau::model::Channel::Beta),
+        flavor: Some(libwau::model::Flavor::Retail),
+        folders: vec!["Bagnon".into(), "Bagnon_Config".into()],
+        missing_folders: missing.iter().map(|d| (*d).to_owned()).collect(),
+        locked_version: locked.map(str::to_owned),
+        toc_version: Some("10.2.4".into()),
+        compatibility: Some(libwau::fs::Compatibility::Compatible),
+        in_manifest: true,
+    }
+}
+
+#[test]
+fn format_report_row_shows_status_source_and_both_versions() {
+    let row = format_report_row(&make_report(AddonStatus::Managed, Some("10.2.5"), &[]));
+    assert!(row.starts_with("Bagnon"));
+    assert!(row.contains("managed"));
+    assert!(row.contains("curseforge/beta"));
+    assert!(row.contains("10.2.5"));
+    assert!(row.ends_with("10.2.4"));
+}
+
+#[test]
+fn format_report_row_notes_missing_folders() {
+    let row = format_report_row(&make_report(
+        AddonStatus::Missing,
+        Some("10.2.5"),
+        &["Bagnon_Config"],
+    ));
+    assert!(row.ends_with("(missing: Bagnon_Config)"));
+
+    let row = format_report_row(&make_report(AddonStatus::Missing, None, &[]));
+    assert!(row.ends_with("(not installed)"));
+}
+
+#[test]
+fn format_report_row_counts_extra_folders_of_unmanaged_groups() {
+    let row = format_report_row(&make_report(AddonStatus::Unmanaged, None, &[]));
+    assert!(row.ends_with("10.2.4  (+1 folders)"));
+}
+
+#[test]
+fn format_report_row_tags_addons_the_client_would_not_load() {
+    let mut report = make_report(AddonStatus::Managed, Some("10.2.5"), &[]);
+    report.compatibility = Some(libwau::fs::Compatibility::OutOfDate);
+    assert!(format_report_row(&report).ends_with("10.2.4  [out of date]"));
+
+    report.compatibility = Some(libwau::fs::Compatibility::Incompatible);
+    assert!(format_report_row(&report).ends_with("[incompatible]"));
+}
+
+#[test]
+fn format_adopted_shows_provider_id_and_folders() {
+    let addon = libwau::adopt::AdoptedAddon {
+        row: libwau::manifest::ManifestAddon {
+            name: "Bagnon".into(),
+            provider: libwau::model::Provider::CurseForge,
+            channel: None,
+            flavors: None,
+            pin: None,
+            project_id: Some(1592),
+            wowi_id: None,
+            repo: None,
+            asset_regex: None,
+            git_ref: None,
+            url: None,
+        },
+        new_row: true,
+        version: Some("10.2.5".into()),
+        folders: vec!["Bagnon".into(), "Bagnon_Config".into()],
+        file_id: None,
+    };
+    assert_eq!(
+        format_adopted(&addon),
+        "adopt  Bagnon 10.2.5 (curseforge 1592): Bagnon, Bagnon_Config"
+    );
+}
+
+#[test]
+fn format_skipped_lists_folders_of_multi_folder_groups() {
+    let mut skipped = libwau::adopt::SkippedGroup {
+        name: "MyHack".into(),
+        folders: vec!["MyHack".into()],
+        reason: libwau::adopt::SkipReason::NoProviderId,
+    };
+    assert_eq!(
+        format_skipped(&skipped),
+        "skip   MyHack: no provider ID in its .toc"
+    );
+
+    skipped.folders.push("MyHack_Options".into());
+    assert_eq!(
+        format_skipped(&skipped),
+        "skip   MyHack (MyHack, MyHack_Options): no provider ID in its .toc"
+    );
+}
+
+#[test]
+fn format_found_install_shows_tag_client_and_dir() {
+    use libwau::client::{ClientVersion, Product};
+
+    use crate::config::Install;
+
+    let found = FoundInstall {
+        install: Install {
+            tag: Tag::new("retail"),
+            flavor: libwau::model::Flavor::Retail,
+            wow_root: PathBuf::from("/wow"),
+            product: Some(Product::Retail),
+            channel: None,
+        },
+        note: String::new(),
+    };
+    let mut client = Client {
+        product: Some(Product::Retail),
+        dir: PathBuf::from("/wow/_retail_"),
+        version: ClientVersion::parse("11.2.5.64270"),
+    };
+    assert_eq!(
+        format_found_install(Some(&found), &client),
+        "found  retail (retail 11.2.5.64270): /wow/_retail_"
+    );
+
+    client.product = None;
+    client.version = None;
+    assert_eq!(
+        format_found_install(Some(&found), &client),
+        "found  retail (retail, guessed from installed addons): /wow/_retail_"
+    );
+    assert_eq!(
+        format_found_install(None, &client),
+        "found  ? (flavor not detected): /wow/_retail_"
+    );
+    assert_eq!(
+        format_undetected_client(&client),
+        "skip   /wow/_retail_: flavor not detected; run `wau init` in a terminal to pick it, or add the install to the config by hand"
+    );
+}
@@ -3,13 +3,17 @@
 //! All downstream code (app, output) receives only `Settings` variants; it must
 //! not reach back into `cli` or `config` directly.
 
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
-use libwau::model::{Channel, Flavor, Tag};
+use libwau::{
+    client::Client,
+    journal::Recovery,
+    model::{Channel, Flavor, Tag},
+};
 
 use crate::{
     cli::{Cli, Command},
-    config::{self, ConfigError},
+    config::{self, Config, ConfigError},
 };
 
 #[cfg(test)]
@@ -22,6 +26,16 @@ pub enum SettingsError {
 
     #[error("install tag '{tag}' not found in config; check the [paths.installs] section")]
     TagNotFound { tag: String },
+
+    #[error(
+        "install '{tag}' is configured as {configured}, but its client ({client}) is {detected}; fix `flavor` in [paths.installs]"
+    )]
+    FlavorMismatch {
+        tag: String,
+        configured: Flavor,
+        detected: Flavor,
+        client: String,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -32,7 +46,14 @@ pub enum SettingsError {
 #[derive(Debug)]
 pub struct ListSettings {
     pub tag: Tag,
+    pub flavor: Flavor,
+    /// Interface number of the detected client, when it could be detected.
+    pub client_interface: Option<u32>,
     pub addons_path: PathBuf,
+    pub manifest_path: PathBuf,
+    pub lock_path: PathBuf,
+    pub filter: libwau::status::ReportFilter,
+    pub sort: libwau::status::SortKey,
 }
 
 /// Resolved settings for `wau sync`.
@@ -46,7 +67,27 @@ pub struct SyncSettings {
     pub manifest_path: PathBuf,
     pub lock_path: PathBuf,
     pub update: bool,
+    /// Restrict the run to the addons recorded as failed by the previous sync.
+    pub retry_failed: bool,
+    pub fail_fast: bool,
+    pub conflicts: libwau::providers::ConflictPolicy,
+    pub concurrency: libwau::ops::Concurrency,
     pub provider_config: libwau::providers::ProviderConfig,
+    pub limits: libwau::providers::Limits,
+}
+
+/// Resolved settings for `wau outdated`.
+#[derive(Debug)]
+pub struct OutdatedSettings {
+    pub tag: Tag,
+    pub flavor: Flavor,
+    pub channel: Channel,
+    pub addons_path: PathBuf,
+    pub cache_dir: PathBuf,
+    pub manifest_path: PathBuf,
+    pub lock_path: PathBuf,
+    pub provider_config: libwau::providers::ProviderConfig,
+    pub limits: libwau::providers::Limits,
 }
 
 /// Resolved settings for `wau remove`.
@@ -54,11 +95,77 @@ pub struct SyncSettings {
 pub struct RemoveSettings {
     pub tag: Tag,
     pub flavor: Flavor,
+    pub channel: Channel,
     pub addons_path: PathBuf,
+    pub cache_dir: PathBuf,
     pub lock_path: PathBuf,
+    pub provider_config: libwau::providers::ProviderConfig,
+    pub limits: libwau::providers::Limits,
     pub addons: Vec<String>,
 }
 
+/// Resolved settings for `wau recover`.
+#[derive(Debug)]
+pub struct RecoverSettings {
+    pub tag: Tag,
+    pub flavor: Flavor,
+    pub channel: Channel,
+    pub addons_path: PathBuf,
+    pub cache_dir: PathBuf,
+    pub lock_path: PathBuf,
+    pub provider_config: libwau::providers::ProviderConfig,
+    pub limits: libwau::providers::Limits,
+    pub recovery: Recovery,
+}
+
+/// Resolved settings for `wau owns`.
+#[derive(Debug)]
+pub struct OwnsSettings {
+    pub tag: Tag,
+    pub flavor: Flavor,
+    pub addons_path: PathBuf,
+    pub lock_path: PathBuf,
+    /// Folder name directly under `addons_path`.
+    pub folder: String,
+}
+
+/// Resolved settings for `wau files`.
+#[derive(Debug)]
+pub struct FilesSettings {
+    pub tag: Tag,
+    pub flavor: Flavor,
+    pub addons_path: PathBuf,
+    pub lock_path: PathBuf,
+    pub addon: String,
+}
+
+/// Resolved settings for `wau adopt`.
+#[derive(Debug)]
+pub struct AdoptSettings {
+    pub tag: Tag,
+    pub flavor: Flavor,
+    pub channel: Channel,
+    pub addons_path: PathBuf,
+    pub manifest_path: PathBuf,
+    pub lock_path: PathBuf,
+    pub dry_run: bool,
+    /// Supplies the CurseForge key for fingerprinting folders without `.toc` IDs.
+    #[cfg(feature = "curseforge")]
+    pub provider_config: libwau::providers::ProviderConfig,
+}
+
+/// Resolved settings for `wau init`.
+#[derive(Debug)]
+pub struct InitSettings {
+    pub config_path: PathBuf,
+    pub manifest_path: PathBuf,
+    /// Paths to search for clients: `--root` paths first, then, unless
+    /// non-interactive, the usual Wine/Lutris/Proton locations.
+    pub roots: Vec<PathBuf>,
+    pub interactive: bool,
+    pub force: bool,
+}
+
 // ---------------------------------------------------------------------------
 // Constructors
 // ---------------------------------------------------------------------------
@@ -68,21 +175,40 @@ impl ListSettings {
         let config_path = config::resolved_path(cli.config.as_deref());
         let config = config::load(&config_path)?;
 
-        let tag = if let Command::List(args) = &cli.command
-            && let Some(t) = &args.tag
-        {
-            Tag::new(t)
-        } else {
-            config.defaults.install_tag.clone()
+        let args = match &cli.command {
+            Command::List(args) => Some(args),
+            _ => None,
         };
+        let tag = args
+            .and_then(|a| a.tag.as_deref())
+            .map(Tag::new)
+            .unwrap_or_else(|| config.defaults.install_tag.clone());
 
         let addons_path = config
             .addons_path(&tag)
             .ok_or_else(|| SettingsError::TagNotFound {
                 tag: tag.to_string(),
             })?;
+        let client = detect_client(&config, &tag, &addons_path)?;
+
+        let flavor = config.flavor_for(&tag);
+        let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
+        let lock_path = config_dir.join(format!("{}.lock.toml", tag.as_str()));
 
-        Ok(ListSettings { tag, addons_path })
+        Ok(ListSettings {
+            tag,
+            flavor,
+            client_interface: client.and_then(|c| c.interface()),
+            addons_path,
+            manifest_path: config_dir.join("manifest.toml"),
+            lock_path,
+            filter: libwau::status::ReportFilter {
+                provider: args.and_then(|a| a.provider.clone()),
+                status: args.and_then(|a| a.status),
+                flavor: args.and_then(|a| a.flavor.clone()),
+            },
+            sort: args.map(|a| a.sort).unwrap_or_default(),
+        })
     }
 }
 
@@ -91,15 +217,74 @@ impl SyncSettings {
         let config_path = config::resolved_path(cli.config.as_deref());
         let config = config::load(&config_path)?;
 
-        let (tag, manifest_override, update) = if let Command::Sync(args) = &cli.command {
+        let args = match &cli.command {
+            Command::Sync(args) => Some(args),
+            _ => None,
+        };
+        let tag = args
+            .and_then(|a| a.tag.as_deref())
+            .map(Tag::new)
+            .unwrap_or_else(|| config.defaults.install_tag.clone());
+        let manifest_override = args.and_then(|a| a.manifest.clone());
+
+        let addons_path = config
+            .addons_path(&tag)
+            .ok_or_else(|| SettingsError::TagNotFound {
+                tag: tag.to_string(),
+            })?;
+        detect_client(&config, &tag, &addons_path)?;
+
+        let flavor = args
+            .and_then(|a| a.flavor.clone())
+            .unwrap_or_else(|| config.flavor_for(&tag));
+        let channel = args
+            .and_then(|a| a.channel.clone())
+            .unwrap_or_else(|| config.channel_for(&tag));
+        let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
+        let manifest_path = manifest_override.unwrap_or_else(|| config_dir.join("manifest.toml"));
+        let lock_path = config_dir.join(format!("{}.lock.toml", tag.as_str()));
+        let provider_config = provider_config(&config);
+
+        Ok(SyncSettings {
+            tag,
+            flavor,
+            channel,
+            addons_path,
+            cache_dir: config.paths.cache,
+            manifest_path,
+            lock_path,
+            update: args.is_some_and(|a| a.update),
+            retry_failed: args.is_some_and(|a| a.retry_failed),
+            fail_fast: args.is_some_and(|a| a.fail_fast),
+            conflicts: if args.is_some_and(|a| a.allow_shared) {
+                libwau::providers::ConflictPolicy::Share
+            } else {
+                libwau::providers::ConflictPolicy::Refuse
+            },
+            concurrency: libwau::ops::Concurrency {
+                default: config.network.concurrency,
+                per_provider: config.network.provider_concurrency,
+            },
+            provider_config,
+            limits: config.limits.to_bytes(),
+        })
+    }
+}
+
+impl OutdatedSettings {
+    pub fn for_outdated(cli: &Cli) -> Result<Self, SettingsError> {
+        let config_path = config::resolved_path(cli.config.as_deref());
+        let config = config::load(&config_path)?;
+
+        let (tag, manifest_override) = if let Command::Outdated(args) = &cli.command {
             let tag = args
                 .tag
                 .as_deref()
                 .map(Tag::new)
                 .unwrap_or_else(|| config.defaults.install_tag.clone());
-            (tag, args.manifest.clone(), args.update)
+            (tag, args.manifest.clone())
         } else {
-            (config.defaults.install_tag.clone(), None, false)
+            (config.defaults.install_tag.clone(), None)
         };
 
         let addons_path = config
@@ -107,34 +292,25 @@ impl SyncSettings {
             .ok_or_else(|| SettingsError::TagNotFound {
                 tag: tag.to_string(),
             })?;
+        detect_client(&config, &tag, &addons_path)?;
 
+        let flavor = config.flavor_for(&tag);
+        let channel = config.channel_for(&tag);
         let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
         let manifest_path = manifest_override.unwrap_or_else(|| config_dir.join("manifest.toml"));
         let lock_path = config_dir.join(format!("{}.lock.toml", tag.as_str()));
+        let provider_config = provider_config(&config);
 
-        let provider_config = libwau::providers::ProviderConfig {
-            curseforge_api_key: config
-                .providers
-                .curseforge
-                .as_ref()
-                .map(|c| c.api_key.clone()),
-            github_token: config
-                .providers
-                .github
-                .as_ref()
-                .and_then(|g| g.token.clone()),
-        };
-
-        Ok(SyncSettings {
+        Ok(OutdatedSettings {
             tag,
-            flavor: config.defaults.flavor,
-            channel: config.defaults.channel,
+            flavor,
+            channel,
             addons_path,
             cache_dir: config.paths.cache,
             manifest_path,
             lock_path,
-            update,
             provider_config,
+            limits: config.limits.to_bytes(),
         })
     }
 }
@@ -161,15 +337,318 @@ impl RemoveSettings {
                 tag: tag.to_string(),
             })?;
 
+        let flavor = config.flavor_for(&tag);
+        let channel = config.channel_for(&tag);
         let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
         let lock_path = config_dir.join(format!("{}.lock.toml", tag.as_str()));
+        let provider_config = provider_config(&config);
 
         Ok(RemoveSettings {
             tag,
-            flavor: config.defaults.flavor,
+            flavor,
+            channel,
             addons_path,
+            cache_dir: config.paths.cache,
             lock_path,
+            provider_config,
+            limits: config.limits.to_bytes(),
             addons,
         })
     }
 }
+
+impl RecoverSettings {
+    pub fn for_recover(cli: &Cli) -> Result<Self, SettingsError> {
+        let config_path = config::resolved_path(cli.config.as_deref());
+        let config = config::load(&config_path)?;
+
+        let (tag, recovery) = if let Command::Recover(args) = &cli.command {
+            let tag = args
+                .tag
+                .as_deref()
+                .map(Tag::new)
+                .unwrap_or_else(|| config.defaults.install_tag.clone());
+            let recovery = if args.back {
+                Recovery::RollBack
+            } else {
+                Recovery::RollForward
+            };
+            (tag, recovery)
+        } else {
+            (config.defaults.install_tag.clone(), Recovery::RollForward)
+        };
+
+        let addons_path = config
+            .addons_path(&tag)
+            .ok_or_else(|| SettingsError::TagNotFound {
+                tag: tag.to_string(),
+            })?;
+
+        let flavor = config.flavor_for(&tag);
+        let channel = config.channel_for(&tag);
+        let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
+        let lock_path = config_dir.join(format!("{}.lock.toml", tag.as_str()));
+        let provider_config = provider_config(&config);
+
+        Ok(RecoverSettings {
+            tag,
+            flavor,
+            channel,
+            addons_path,
+            cache_dir: config.paths.cache,
+            lock_path,
+            provider_config,
+            limits: config.limits.to_bytes(),
+            recovery,
+        })
+    }
+}
+
+impl OwnsSettings {
+    pub fn for_owns(cli: &Cli) -> Result<Self, SettingsError> {
+        let config_path = config::resolved_path(cli.config.as_deref());
+        let config = config::load(&config_path)?;
+
+        let (tag, folder_arg) = if let Command::Owns(args) = &cli.command {
+            let tag = args
+                .tag
+                .as_deref()
+                .map(Tag::new)
+                .unwrap_or_else(|| config.defaults.install_tag.clone());
+            (tag, args.folder.clone())
+        } else {
+            (config.defaults.install_tag.clone(), PathBuf::new())
+        };
+
+        let addons_path = config
+            .addons_path(&tag)
+            .ok_or_else(|| SettingsError::TagNotFound {
+                tag: tag.to_string(),
+            })?;
+
+        let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
+        let lock_path = config_dir.join(format!("{}.lock.toml", tag.as_str()));
+        let folder = folder_name(&addons_path, &folder_arg);
+        let flavor = config.flavor_for(&tag);
+
+        Ok(OwnsSettings {
+            tag,
+            flavor,
+            addons_path,
+            lock_path,
+            folder,
+        })
+    }
+}
+
+impl FilesSettings {
+    pub fn for_files(cli: &Cli) -> Result<Self, SettingsError> {
+        let config_path = config::resolved_path(cli.config.as_deref());
+        let config = config::load(&config_path)?;
+
+        let (tag, addon) = if let Command::Files(args) = &cli.command {
+            let tag = args
+                .tag
+                .as_deref()
+                .map(Tag::new)
+                .unwrap_or_else(|| config.defaults.install_tag.clone());
+            (tag, args.addon.clone())
+        } else {
+            (config.defaults.install_tag.clone(), String::new())
+        };
+
+        let addons_path = config
+            .addons_path(&tag)
+            .ok_or_else(|| SettingsError::TagNotFound {
+                tag: tag.to_string(),
+            })?;
+
+        let flavor = config.flavor_for(&tag);
+        let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
+        let lock_path = config_dir.join(format!("{}.lock.toml", tag.as_str()));
+
+        Ok(FilesSettings {
+            tag,
+            flavor,
+            addons_path,
+            lock_path,
+            addon,
+        })
+    }
+}
+
+impl AdoptSettings {
+    pub fn for_adopt(cli: &Cli) -> Result<Self, SettingsError> {
+        let config_path = config::resolved_path(cli.config.as_deref());
+        let config = config::load(&config_path)?;
+
+        let args = match &cli.command {
+            Command::Adopt(args) => Some(args),
+            _ => None,
+        };
+        let tag = args
+            .and_then(|a| a.tag.as_deref())
+            .map(Tag::new)
+            .unwrap_or_else(|| config.defaults.install_tag.clone());
+
+        let addons_path = config
+            .addons_path(&tag)
+            .ok_or_else(|| SettingsError::TagNotFound {
+                tag: tag.to_string(),
+            })?;
+        detect_client(&config, &tag, &addons_path)?;
+
+        let flavor = config.flavor_for(&tag);
+        let channel = config.channel_for(&tag);
+        let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
+        let lock_path = config_dir.join(format!("{}.lock.toml", tag.as_str()));
+        #[cfg(feature = "curseforge")]
+        let provider_config = provider_config(&config);
+
+        Ok(AdoptSettings {
+            tag,
+            flavor,
+            channel,
+            addons_path,
+            manifest_path: config_dir.join("manifest.toml"),
+            lock_path,
+            dry_run: args.is_some_and(|a| a.dry_run),
+            #[cfg(feature = "curseforge")]
+            provider_config,
+        })
+    }
+}
+
+impl InitSettings {
+    pub fn for_init(cli: &Cli) -> Self {
+        let config_path = config::resolved_path(cli.config.as_deref());
+        let config_dir = config_path.parent().unwrap_or(&config_path).to_path_buf();
+
+        let (mut roots, interactive, force) = if let Command::Init(args) = &cli.command {
+            (args.roots.clone(), !args.non_interactive, args.force)
+        } else {
+            (Vec::new(), true, false)
+        };
+        if interactive {
+            roots.extend(default_search_roots());
+        }
+        let mut seen = Vec::new();
+        roots.retain(|root| {
+            let key = std::fs::canonicalize(root).unwrap_or_else(|_| root.clone());
+            let new = !seen.contains(&key);
+            seen.push(key);
+            new
+        });
+
+        InitSettings {
+            manifest_path: config_dir.join("manifest.toml"),
+            config_path,
+            roots,
+            interactive,
+            force,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Reduces a `wau owns` argument to the folder directly under `addons_path` it names.
+///
+/// Accepts a bare folder name, a path below `addons_path` (absolute, or relative
+/// to the current dir), any path through an `AddOns` dir, or a path relative to
+/// `addons_path` itself (`WeakAuras/Libs/…`); files resolve to their top folder.
+fn folder_name(addons_path: &Path, arg: &Path) -> String {
+    let absolute = std::path::absolute(arg).unwrap_or_else(|_| arg.to_path_buf());
+    let rel = match absolute.strip_prefix(addons_path) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => {
+            let parts: Vec<Component> = arg.components().collect();
+            match parts.iter().rposition(|c| c.as_os_str() == "AddOns") {
+                Some(i) => parts[i + 1..].iter().collect(),
+                None => arg.to_path_buf(),
+            }
+        }
+    };
+    rel.components()
+        .find_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Detects the client that reads `addons_path` and checks it against the flavor
+/// configured for install `tag`. Installs without a recognisable client, such as
+/// flat private-server layouts, pass unchecked.
+fn detect_client(
+    config: &Config,
+    tag: &Tag,
+    addons_path: &Path,
+) -> Result<Option<Client>, SettingsError> {
+    let client = addons_path
+        .parent()
+        .and_then(Path::parent)
+        .and_then(libwau::client::client_at);
+    match config.install(tag) {
+        Some(install) => check_flavor(tag, &install.flavor, client),
+        None => Ok(client),
+    }
+}
+
+fn check_flavor(
+    tag: &Tag,
+    configured: &Flavor,
+    client: Option<Client>,
+) -> Result<Option<Client>, SettingsError> {
+    match client.as_ref().and_then(Client::flavor) {
+        Some(detected) if &detected != configured => Err(SettingsError::FlavorMismatch {
+            tag: tag.to_string(),
+            configured: configured.clone(),
+            detected,
+            client: client.map(|c| c.to_string()).unwrap_or_default(),
+        }),
+        _ => Ok(client),
+    }
+}
+
+/// Where Linux WoW installs usually live: `$WINEPREFIX` and `~/.wine`, Lutris'
+/// `~/Games` prefixes, winetricks prefixes, and Steam's Proton prefixes (native
+/// and Flatpak). `~/.steam/steam` is usually a link to the native Steam dir;
+/// [`InitSettings::for_init`] drops such duplicates.
+fn default_search_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = std::env::var_os("WINEPREFIX")
+        .map(PathBuf::from)
+        .into_iter()
+        .collect();
+    if let Some(home) = dirs::home_dir() {
+        roots.extend(
+            [
+                ".wine",
+                "Games",
+                ".local/share/wineprefixes",
+                ".local/share/Steam/steamapps/compatdata",
+                ".steam/steam/steamapps/compatdata",
+                ".var/app/com.valvesoftware.Steam/.local/share/Steam/steamapps/compatdata",
+            ]
+            .map(|p| home.join(p)),
+        );
+    }
+    roots
+}
+
+fn provider_config(config: &Config) -> libwau::providers::ProviderConfig {
+    libwau::providers::ProviderConfig {
+        curseforge_api_key: config
+            .providers
+            .curseforge
+            .as_ref()
+            .map(|c| c.api_key.clone()),
+        github_token: config
+            .providers
+            .github
+            .as_ref()
+            .and_then(|g| g.token.clone()),
+    }
+}
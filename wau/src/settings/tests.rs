@@ -1,3 +1,11 @@
+use std::path::Path;
+
+use libwau::{
+    client::{Client, ClientVersion, Product},
+    model::{Flavor, Tag},
+};
+
+use super::{check_flavor, folder_name};
 use crate::cli::{Cli, Command, ListArgs, RemoveArgs, SyncArgs};
 
 fn make_list_cli(tag: Option<&str>) -> Cli {
@@ -5,6 +13,10 @@ fn make_list_cli(tag: Option<&str>) -> Cli {
         config: None,
         command: Command::List(ListArgs {
             tag: tag.map(str::to_owned),
+            provider: None,
+            status: None,
+            flavor: None,
+            sort: Default::default(),
         }),
     }
 }
@@ -16,6 +28,11 @@ fn make_sync_cli(tag: Option<&str>, update: bool) -> Cli {
             tag: tag.map(str::to_owned),
             manifest: None,
             update,
+            retry_failed: false,
+            fail_fast: false,
+            allow_shared: false,
+            flavor: None,
+            channel: None,
         }),
     }
 }
@@ -74,3 +91,58 @@ fn remove_addons_stored() {
     };
     assert_eq!(args.addons, vec!["WeakAuras", "Bagnon"]);
 }
+
+#[test]
+fn folder_name_accepts_bare_names_and_paths() {
+    let addons = Path::new("/games/wow/_retail_/Interface/AddOns");
+    for arg in [
+        "WeakAuras",
+        "WeakAuras/",
+        "WeakAuras/Libs/LibStub/LibStub.lua",
+        "/games/wow/_retail_/Interface/AddOns/WeakAuras",
+        "/games/wow/_retail_/Interface/AddOns/WeakAuras/WeakAuras.toc",
+        "Interface/AddOns/WeakAuras",
+    ] {
+        assert_eq!(folder_name(addons, Path::new(arg)), "WeakAuras", "{arg}");
+    }
+}
+
+fn client(product: Product, version: &str) -> Client {
+    Client {
+        product: Some(product),
+        dir: "/games/wow/_classic_".into(),
+        version: ClientVersion::parse(version),
+    }
+}
+
+#[test]
+fn check_flavor_accepts_matching_or_undetected_clients() {
+    let tag = Tag::new("classic");
+    let found = check_flavor(
+        &tag,
+        &Flavor::Mop,
+        Some(client(Product::Classic, "5.5.1.63311")),
+    );
+    assert_eq!(found.unwrap().unwrap().interface(), Some(50501));
+    assert!(check_flavor(&tag, &Flavor::Mop, None).unwrap().is_none());
+    // Progression Classic without a version cannot be checked.
+    let unknown = Client {
+        version: None,
+        ..client(Product::Classic, "")
+    };
+    assert!(check_flavor(&tag, &Flavor::Cata, Some(unknown)).is_ok());
+}
+
+#[test]
+fn check_flavor_rejects_a_client_of_another_flavor() {
+    let err = check_flavor(
+        &Tag::new("classic"),
+        &Flavor::Era,
+        Some(client(Product::Classic, "5.5.1.63311")),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "install 'classic' is configured as classic-era, but its client (classic 5.5.1.63311) is classic-mop; fix `flavor` in [paths.installs]"
+    );
+}